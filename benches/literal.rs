@@ -0,0 +1,39 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+use gluesql::{Evaluated, Result};
+use sqlparser::ast::Value as AstValue;
+
+fn number(v: &str) -> Evaluated<'static> {
+    Evaluated::Literal(AstValue::Number(v.to_owned()))
+}
+
+/// `(a + b) * b - a / b`, every step of which parses its number literals.
+fn arithmetic<'a>(a: &Evaluated<'a>, b: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+    a.add(b)?.multiply(b)?.subtract(&a.divide(b)?)
+}
+
+fn literal_arithmetic(c: &mut Criterion) {
+    let cases = [
+        ("int", number("12345"), number("67")),
+        ("float", number("123.45"), number("6.7")),
+        ("u64", number("9223372036854775808"), number("1")),
+    ];
+
+    for (name, a, b) in cases.iter() {
+        c.bench_function(&format!("literal arithmetic {}", name), |bench| {
+            bench.iter(|| arithmetic(black_box(a), black_box(b)).unwrap())
+        });
+    }
+}
+
+fn literal_comparison(c: &mut Criterion) {
+    let (a, b) = (number("12345"), number("123.45"));
+
+    c.bench_function("literal comparison", |bench| {
+        bench.iter(|| black_box(&a).try_partial_cmp(black_box(&b)).unwrap())
+    });
+}
+
+criterion_group!(benches, literal_arithmetic, literal_comparison);
+criterion_main!(benches);
@@ -0,0 +1,223 @@
+use chrono::{Duration, Months, NaiveDate, NaiveDateTime};
+use std::convert::TryFrom;
+
+use sqlparser::ast::{DateTimeField, Value as AstValue};
+
+use super::ValueError;
+use crate::result::Result;
+
+/// Span of time to shift a `DATE` or `TIMESTAMP` by, e.g. from `INTERVAL '7 days'` or
+/// `INTERVAL '1' MONTH`. Months are kept apart from the rest since they have no fixed length.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    months: i32,
+    duration: Duration,
+}
+
+impl Interval {
+    pub fn days(days: i64) -> Result<Self> {
+        Duration::try_days(days)
+            .map(|duration| Interval {
+                months: 0,
+                duration,
+            })
+            .ok_or_else(|| ValueError::DateOutOfRange.into())
+    }
+
+    /// Accepts a number with the unit given by the leading field, e.g. `INTERVAL '7' DAY`, or a
+    /// list of numbers each followed by a unit, e.g. `INTERVAL '1 year 2 months'`. Units are
+    /// years, months, weeks, days, hours, minutes and seconds, in singular or plural form.
+    /// Ranges such as `INTERVAL '1-2' YEAR TO MONTH` are not supported.
+    pub fn parse(literal: &AstValue) -> Result<Self> {
+        let failed = || ValueError::FailedToParseInterval(literal.to_string()).into();
+
+        let (value, leading_field) = match literal {
+            AstValue::Interval {
+                value,
+                leading_field,
+                last_field: None,
+                ..
+            } => (value, leading_field),
+            _ => return Err(failed()),
+        };
+
+        let parse_amount = |v: &str| v.parse::<i64>().ok();
+
+        match leading_field {
+            Some(field) => {
+                let unit = match field {
+                    DateTimeField::Year => "year",
+                    DateTimeField::Month => "month",
+                    DateTimeField::Day => "day",
+                    DateTimeField::Hour => "hour",
+                    DateTimeField::Minute => "minute",
+                    DateTimeField::Second => "second",
+                };
+
+                parse_amount(value.trim())
+                    .and_then(|amount| Interval::unit(amount, unit))
+                    .ok_or_else(failed)
+            }
+            None => {
+                let words = value.split_whitespace().collect::<Vec<_>>();
+
+                if words.is_empty() || words.len() % 2 != 0 {
+                    return Err(failed());
+                }
+
+                words
+                    .chunks(2)
+                    .try_fold(Interval::default(), |interval, pair| {
+                        let unit = Interval::unit(parse_amount(pair[0])?, pair[1])?;
+
+                        interval.checked_add(&unit)
+                    })
+                    .ok_or_else(failed)
+            }
+        }
+    }
+
+    fn unit(amount: i64, unit: &str) -> Option<Self> {
+        let unit = unit.to_lowercase();
+        let unit = unit.strip_suffix('s').unwrap_or(&unit);
+
+        let months = |months: i64| {
+            Some(Interval {
+                months: i32::try_from(months).ok()?,
+                duration: Duration::zero(),
+            })
+        };
+        let duration = |duration: Option<Duration>| {
+            duration.map(|duration| Interval {
+                months: 0,
+                duration,
+            })
+        };
+
+        match unit {
+            "year" => months(amount.checked_mul(12)?),
+            "month" | "mon" => months(amount),
+            "week" => duration(Duration::try_weeks(amount)),
+            "day" => duration(Duration::try_days(amount)),
+            "hour" => duration(Duration::try_hours(amount)),
+            "minute" | "min" => duration(Duration::try_minutes(amount)),
+            "second" | "sec" => duration(Duration::try_seconds(amount)),
+            _ => None,
+        }
+    }
+
+    fn checked_add(&self, other: &Interval) -> Option<Self> {
+        Some(Interval {
+            months: self.months.checked_add(other.months)?,
+            duration: self.duration.checked_add(&other.duration)?,
+        })
+    }
+
+    pub fn negate(&self) -> Result<Self> {
+        self.months
+            .checked_neg()
+            .map(|months| Interval {
+                months,
+                duration: -self.duration,
+            })
+            .ok_or_else(|| ValueError::DateOutOfRange.into())
+    }
+
+    /// Whether shifting a date by this interval yields a time other than midnight.
+    pub(crate) fn has_time(&self) -> bool {
+        self.duration.num_seconds() % 86_400 != 0 || self.duration.subsec_nanos() != 0
+    }
+
+    /// Months are added first, clamping the day to the end of a shorter month, so
+    /// `2021-01-31` plus one month is `2021-02-28`.
+    pub(crate) fn add_to_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let months = Months::new(self.months.unsigned_abs());
+        let date = if self.months < 0 {
+            date.checked_sub_months(months)?
+        } else {
+            date.checked_add_months(months)?
+        };
+
+        date.checked_add_signed(self.duration)
+    }
+
+    pub(crate) fn add_to_timestamp(&self, timestamp: NaiveDateTime) -> Option<NaiveDateTime> {
+        let months = Months::new(self.months.unsigned_abs());
+        let timestamp = if self.months < 0 {
+            timestamp.checked_sub_months(months)?
+        } else {
+            timestamp.checked_add_months(months)?
+        };
+
+        timestamp.checked_add_signed(self.duration)
+    }
+}
+
+impl Default for Interval {
+    fn default() -> Self {
+        Interval {
+            months: 0,
+            duration: Duration::zero(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use sqlparser::ast::{DateTimeField, Value as AstValue};
+
+    use super::Interval;
+    use crate::data::ValueError;
+
+    fn interval(value: &str, leading_field: Option<DateTimeField>) -> AstValue {
+        AstValue::Interval {
+            value: value.to_owned(),
+            leading_field,
+            leading_precision: None,
+            last_field: None,
+            fractional_seconds_precision: None,
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let parse = |value, leading_field| Interval::parse(&interval(value, leading_field));
+        let expected = |months, duration| Ok(Interval { months, duration });
+
+        assert_eq!(parse("7 days", None), expected(0, Duration::days(7)));
+        assert_eq!(parse("7", Some(DateTimeField::Day)), parse("1 week", None));
+        assert_eq!(
+            parse("-2", Some(DateTimeField::Year)),
+            expected(-24, Duration::zero())
+        );
+        assert_eq!(
+            parse("1 Year 2 months 3 hours", None),
+            expected(14, Duration::hours(3))
+        );
+        assert_eq!(
+            parse("1 min 30 secs", None),
+            expected(0, Duration::seconds(90))
+        );
+
+        for (value, leading_field) in [
+            ("", None),
+            ("7", None),
+            ("7 fortnights", None),
+            ("seven days", None),
+            ("1.5 days", None),
+            ("7 days", Some(DateTimeField::Day)),
+        ] {
+            let literal = interval(value, leading_field);
+
+            assert_eq!(
+                Interval::parse(&literal),
+                Err(ValueError::FailedToParseInterval(literal.to_string()).into())
+            );
+        }
+
+        assert!(Interval::parse(&AstValue::Number("7".to_owned())).is_err());
+        assert!(parse("3 hours", None).unwrap().has_time());
+        assert!(!parse("-1 day", None).unwrap().has_time());
+    }
+}
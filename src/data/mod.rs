@@ -1,9 +1,13 @@
+mod interval;
 mod row;
 mod schema;
 mod table;
 mod value;
 
+pub use interval::Interval;
 pub use row::{Row, RowError};
 pub use schema::Schema;
 pub use table::{get_name, Table, TableError};
 pub use value::{Value, ValueError};
+
+pub(crate) use value::{literal_bytes, parse_timestamp, timestamp_of};
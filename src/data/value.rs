@@ -1,10 +1,15 @@
 use boolinator::Boolinator;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 use thiserror::Error as ThisError;
 
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+
+use super::Interval;
 use sqlparser::ast::{DataType, Value as AstValue};
 
 use crate::result::{Error, Result};
@@ -20,6 +25,21 @@ pub enum ValueError {
     #[error("failed to parse number")]
     FailedToParseNumber,
 
+    #[error("failed to parse interval {0}")]
+    FailedToParseInterval(String),
+
+    #[error("interval arithmetic on non date")]
+    IntervalOnNonTemporal,
+
+    #[error("failed to parse hex literal {0}")]
+    FailedToParseHex(String),
+
+    #[error("date out of range")]
+    DateOutOfRange,
+
+    #[error("failed to parse {data_type} from {literal}")]
+    FailedToParseTemporal { literal: String, data_type: String },
+
     #[error("add on non numeric value")]
     AddOnNonNumeric,
 
@@ -32,6 +52,27 @@ pub enum ValueError {
     #[error("divide on non numeric value")]
     DivideOnNonNumeric,
 
+    #[error("modulo on non numeric value")]
+    ModuloOnNonNumeric,
+
+    #[error("unary minus on non numeric value")]
+    UnaryMinusOnNonNumeric,
+
+    #[error("abs on non numeric value")]
+    AbsOnNonNumeric,
+
+    #[error("numeric overflow")]
+    NumericOverflow,
+
+    #[error("divide by zero")]
+    DivideByZero,
+
+    #[error("failed to cast literal {literal} into {data_type}")]
+    LiteralCastFailed { literal: String, data_type: String },
+
+    #[error("mixing decimal and float is not supported")]
+    DecimalWithFloat,
+
     #[error("null value on not null field")]
     NullValueOnNotNullField,
 }
@@ -47,6 +88,14 @@ pub enum Value {
     OptF64(Option<f64>),
     OptStr(Option<String>),
     Empty,
+    Decimal(Decimal),
+    OptDecimal(Option<Decimal>),
+    Date(NaiveDate),
+    OptDate(Option<NaiveDate>),
+    Timestamp(NaiveDateTime),
+    OptTimestamp(Option<NaiveDateTime>),
+    Bytes(Vec<u8>),
+    OptBytes(Option<Vec<u8>>),
 }
 
 impl PartialEq<Value> for Value {
@@ -68,12 +117,30 @@ impl PartialEq<Value> for Value {
             | (Value::OptStr(Some(l)), Value::Str(r))
             | (Value::Str(l), Value::OptStr(Some(r)))
             | (Value::OptStr(Some(l)), Value::OptStr(Some(r))) => l == r,
+            (Value::Decimal(l), Value::Decimal(r))
+            | (Value::OptDecimal(Some(l)), Value::Decimal(r))
+            | (Value::Decimal(l), Value::OptDecimal(Some(r)))
+            | (Value::OptDecimal(Some(l)), Value::OptDecimal(Some(r))) => l == r,
+            (Value::Bytes(l), Value::Bytes(r))
+            | (Value::OptBytes(Some(l)), Value::Bytes(r))
+            | (Value::Bytes(l), Value::OptBytes(Some(r)))
+            | (Value::OptBytes(Some(l)), Value::OptBytes(Some(r))) => l == r,
             (Value::OptBool(None), Value::OptBool(None))
             | (Value::OptI64(None), Value::OptI64(None))
             | (Value::OptF64(None), Value::OptF64(None))
             | (Value::OptStr(None), Value::OptStr(None))
+            | (Value::OptDecimal(None), Value::OptDecimal(None))
+            | (Value::OptDate(None), Value::OptDate(None))
+            | (Value::OptTimestamp(None), Value::OptTimestamp(None))
+            | (Value::OptBytes(None), Value::OptBytes(None))
             | (Value::Empty, Value::Empty) => true,
-            _ => false,
+            _ => match promote_to_decimal(self, other) {
+                Some((l, r, _)) => l == r,
+                None => match (timestamp_of(self), timestamp_of(other)) {
+                    (Some(l), Some(r)) => l == r,
+                    _ => false,
+                },
+            },
         }
     }
 }
@@ -86,19 +153,40 @@ impl PartialEq<AstValue> for Value {
             (Value::I64(l), AstValue::Number(r))
             | (Value::OptI64(Some(l)), AstValue::Number(r)) => match r.parse::<i64>() {
                 Ok(r) => l == &r,
-                Err(_) => false,
+                Err(_) => match r.parse::<f64>() {
+                    Ok(r) => eq_integer_float(*l, r),
+                    Err(_) => false,
+                },
             },
             (Value::F64(l), AstValue::Number(r))
-            | (Value::OptF64(Some(l)), AstValue::Number(r)) => match r.parse::<f64>() {
-                Ok(r) => l == &r,
-                Err(_) => false,
+            | (Value::OptF64(Some(l)), AstValue::Number(r)) => match r.parse::<i64>() {
+                Ok(r) => eq_integer_float(r, *l),
+                Err(_) => match r.parse::<f64>() {
+                    Ok(r) => l == &r,
+                    Err(_) => false,
+                },
+            },
+            (Value::Str(l), r) | (Value::OptStr(Some(l)), r) => {
+                literal_bytes(r).as_deref() == Some(l.as_bytes())
+            }
+            (Value::Bytes(l), AstValue::HexStringLiteral(_))
+            | (Value::OptBytes(Some(l)), AstValue::HexStringLiteral(_)) => {
+                literal_bytes(other).as_deref() == Some(l.as_slice())
+            }
+            (Value::Decimal(l), AstValue::Number(r))
+            | (Value::OptDecimal(Some(l)), AstValue::Number(r)) => parse_decimal(r) == Some(*l),
+            (l, AstValue::SingleQuotedString(r)) if l.is_temporal() => match timestamp_of(l) {
+                Some(l) => parse_timestamp(r) == Some(l),
+                None => false,
             },
-            (Value::Str(l), AstValue::SingleQuotedString(r))
-            | (Value::OptStr(Some(l)), AstValue::SingleQuotedString(r)) => l == r,
             (Value::OptBool(None), AstValue::Null)
             | (Value::OptI64(None), AstValue::Null)
             | (Value::OptF64(None), AstValue::Null)
-            | (Value::OptStr(None), AstValue::Null) => true,
+            | (Value::OptStr(None), AstValue::Null)
+            | (Value::OptDecimal(None), AstValue::Null)
+            | (Value::OptDate(None), AstValue::Null)
+            | (Value::OptTimestamp(None), AstValue::Null)
+            | (Value::OptBytes(None), AstValue::Null) => true,
             _ => false,
         }
     }
@@ -107,6 +195,10 @@ impl PartialEq<AstValue> for Value {
 impl PartialOrd<Value> for Value {
     fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
         match (self, other) {
+            (Value::Bool(l), Value::Bool(r))
+            | (Value::OptBool(Some(l)), Value::Bool(r))
+            | (Value::Bool(l), Value::OptBool(Some(r)))
+            | (Value::OptBool(Some(l)), Value::OptBool(Some(r))) => Some(l.cmp(r)),
             (Value::I64(l), Value::I64(r))
             | (Value::OptI64(Some(l)), Value::I64(r))
             | (Value::I64(l), Value::OptI64(Some(r)))
@@ -119,7 +211,17 @@ impl PartialOrd<Value> for Value {
             | (Value::OptStr(Some(l)), Value::Str(r))
             | (Value::Str(l), Value::OptStr(Some(r)))
             | (Value::OptStr(Some(l)), Value::OptStr(Some(r))) => Some(l.cmp(r)),
-            _ => None,
+            (Value::Bytes(l), Value::Bytes(r))
+            | (Value::OptBytes(Some(l)), Value::Bytes(r))
+            | (Value::Bytes(l), Value::OptBytes(Some(r)))
+            | (Value::OptBytes(Some(l)), Value::OptBytes(Some(r))) => Some(l.cmp(r)),
+            _ => match (timestamp_of(self), timestamp_of(other)) {
+                (Some(l), Some(r)) => Some(l.cmp(&r)),
+                _ => match promote_to_decimal(self, other) {
+                    Some((l, r, _)) => Some(l.cmp(&r)),
+                    None => promote_to_float(self, other).and_then(|(l, r, _)| l.partial_cmp(&r)),
+                },
+            },
         }
     }
 }
@@ -127,18 +229,35 @@ impl PartialOrd<Value> for Value {
 impl PartialOrd<AstValue> for Value {
     fn partial_cmp(&self, other: &AstValue) -> Option<Ordering> {
         match (self, other) {
+            (Value::Bool(l), AstValue::Boolean(r))
+            | (Value::OptBool(Some(l)), AstValue::Boolean(r)) => Some(l.cmp(r)),
             (Value::I64(l), AstValue::Number(r))
             | (Value::OptI64(Some(l)), AstValue::Number(r)) => match r.parse::<i64>() {
                 Ok(r) => Some(l.cmp(&r)),
-                Err(_) => None,
+                Err(_) => match r.parse::<f64>() {
+                    Ok(r) => (*l as f64).partial_cmp(&r),
+                    Err(_) => None,
+                },
             },
             (Value::F64(l), AstValue::Number(r))
             | (Value::OptF64(Some(l)), AstValue::Number(r)) => match r.parse::<f64>() {
                 Ok(r) => l.partial_cmp(&r),
                 Err(_) => None,
             },
-            (Value::Str(l), AstValue::SingleQuotedString(r))
-            | (Value::OptStr(Some(l)), AstValue::SingleQuotedString(r)) => Some(l.cmp(r)),
+            (Value::Str(l), r) | (Value::OptStr(Some(l)), r) => {
+                literal_bytes(r).map(|r| l.as_bytes().cmp(&r))
+            }
+            (Value::Bytes(l), AstValue::HexStringLiteral(_))
+            | (Value::OptBytes(Some(l)), AstValue::HexStringLiteral(_)) => {
+                literal_bytes(other).map(|r| l.as_slice().cmp(&r))
+            }
+            (Value::Decimal(l), AstValue::Number(r))
+            | (Value::OptDecimal(Some(l)), AstValue::Number(r)) => {
+                parse_decimal(r).map(|r| l.cmp(&r))
+            }
+            (l, AstValue::SingleQuotedString(r)) if l.is_temporal() => {
+                Some(timestamp_of(l)?.cmp(&parse_timestamp(r)?))
+            }
             _ => None,
         }
     }
@@ -154,12 +273,17 @@ impl TryFrom<&AstValue> for Value {
                 .map_or_else(|_| v.parse::<f64>().map(Value::F64), |v| Ok(Value::I64(v)))
                 .map_err(|_| ValueError::FailedToParseNumber.into()),
             AstValue::Boolean(v) => Ok(Value::Bool(*v)),
+            AstValue::SingleQuotedString(v) | AstValue::NationalStringLiteral(v) => {
+                Ok(Value::Str(v.clone()))
+            }
+            AstValue::Null => Ok(Value::Empty),
             _ => Err(ValueError::SqlTypeNotSupported.into()),
         }
     }
 }
 
 trait BoolToValue: Sized {
+    #[allow(clippy::wrong_self_convention)]
     fn as_value(self, v1: Value, v2: Value) -> Value;
 }
 
@@ -174,6 +298,247 @@ impl BoolToValue for bool {
     }
 }
 
+/// Exact, unlike `l as f64 == r`, which rounds large integers first and so takes
+/// `9007199254740993` to equal `9007199254740992.0` and `i64::MAX` to equal `2^63`.
+fn eq_integer_float(l: i64, r: f64) -> bool {
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+
+    r.fract() == 0.0 && (-LIMIT..LIMIT).contains(&r) && r as i64 == l
+}
+
+fn failed_to_parse_temporal(literal: &str, data_type: &str) -> Error {
+    ValueError::FailedToParseTemporal {
+        literal: literal.to_owned(),
+        data_type: data_type.to_owned(),
+    }
+    .into()
+}
+
+/// Promotes a pair of one integer and one float into floats, along with whether either side is
+/// nullable. Returns `None` for any other pair, including NULLs.
+fn promote_to_float(a: &Value, b: &Value) -> Option<(f64, f64, bool)> {
+    let float = |v: &Value| match v {
+        Value::I64(v) => Some((*v as f64, false)),
+        Value::OptI64(Some(v)) => Some((*v as f64, true)),
+        Value::F64(v) => Some((*v, false)),
+        Value::OptF64(Some(v)) => Some((*v, true)),
+        _ => None,
+    };
+    let is_float = |v: &Value| matches!(v, Value::F64(_) | Value::OptF64(_));
+
+    if is_float(a) == is_float(b) {
+        return None;
+    }
+
+    let (a, a_nullable) = float(a)?;
+    let (b, b_nullable) = float(b)?;
+
+    Some((a, b, a_nullable || b_nullable))
+}
+
+/// Promotes a pair of values where at least one is a decimal and the other is a decimal or an
+/// integer, along with whether either side is nullable. Returns `None` for any other pair,
+/// including NULLs and floats.
+fn promote_to_decimal(a: &Value, b: &Value) -> Option<(Decimal, Decimal, bool)> {
+    let decimal = |v: &Value| match v {
+        Value::Decimal(v) => Some((*v, false)),
+        Value::OptDecimal(Some(v)) => Some((*v, true)),
+        Value::I64(v) => Some((Decimal::from(*v), false)),
+        Value::OptI64(Some(v)) => Some((Decimal::from(*v), true)),
+        _ => None,
+    };
+
+    if !a.is_decimal() && !b.is_decimal() {
+        return None;
+    }
+
+    let (a, a_nullable) = decimal(a)?;
+    let (b, b_nullable) = decimal(b)?;
+
+    Some((a, b, a_nullable || b_nullable))
+}
+
+/// A `DATE` or `TIMESTAMP` shifted by an integer number of days, e.g. `due_date + 7`, and for
+/// subtraction the number of days between two dates.
+fn temporal_arithmetic(a: &Value, b: &Value, subtract: bool) -> Option<Result<Value>> {
+    let days = |v: &Value| match v {
+        Value::I64(v) => Some(Some(*v)),
+        Value::OptI64(v) => Some(*v),
+        _ => None,
+    };
+
+    let (temporal, days, nullable) = match (a.is_temporal(), b.is_temporal()) {
+        (true, true) if subtract => return Some(days_between(a, b)),
+        (true, false) => (a, days(b)?, matches!(b, Value::OptI64(_))),
+        (false, true) if !subtract => (b, days(a)?, matches!(a, Value::OptI64(_))),
+        (false, false) => return None,
+        _ => {
+            return Some(Err(if subtract {
+                ValueError::SubtractOnNonNumeric.into()
+            } else {
+                ValueError::AddOnNonNumeric.into()
+            }))
+        }
+    };
+
+    let days = match days {
+        Some(days) if subtract => days.checked_neg(),
+        Some(days) => Some(days),
+        None if temporal.is_date() => return Some(Ok(Value::OptDate(None))),
+        None => return Some(Ok(Value::OptTimestamp(None))),
+    };
+
+    Some(
+        days.ok_or_else(|| ValueError::DateOutOfRange.into())
+            .and_then(Interval::days)
+            .and_then(|interval| temporal.shift(&interval, nullable)),
+    )
+}
+
+/// Only dates are subtracted from each other, timestamps would need an interval result.
+fn days_between(a: &Value, b: &Value) -> Result<Value> {
+    match (a, b) {
+        (Value::Date(a), Value::Date(b)) => Ok(Value::I64(a.signed_duration_since(*b).num_days())),
+        (Value::Date(a) | Value::OptDate(Some(a)), Value::Date(b) | Value::OptDate(Some(b))) => {
+            Ok(Value::OptI64(Some(a.signed_duration_since(*b).num_days())))
+        }
+        (Value::OptDate(None), Value::Date(_) | Value::OptDate(_))
+        | (Value::Date(_) | Value::OptDate(_), Value::OptDate(None)) => Ok(Value::OptI64(None)),
+        _ => Err(ValueError::SubtractOnNonNumeric.into()),
+    }
+}
+
+/// NULL of the result type when either operand of integer or float arithmetic is NULL, a float
+/// NULL when either side is a float. Returns `None` for any other pair, including two non-NULL
+/// numbers.
+fn null_arithmetic(a: &Value, b: &Value) -> Option<Value> {
+    use Value::*;
+
+    let is_float = |v: &Value| matches!(v, F64(_) | OptF64(_));
+    let is_number = |v: &Value| matches!(v, I64(_) | OptI64(_)) || is_float(v);
+    let is_null = |v: &Value| matches!(v, OptI64(None) | OptF64(None));
+
+    if !is_number(a) || !is_number(b) || (!is_null(a) && !is_null(b)) {
+        return None;
+    }
+
+    Some(if is_float(a) || is_float(b) {
+        OptF64(None)
+    } else {
+        OptI64(None)
+    })
+}
+
+fn check_divisor(b: &Value) -> Result<()> {
+    let is_zero = match b {
+        Value::I64(b) | Value::OptI64(Some(b)) => *b == 0,
+        Value::F64(b) | Value::OptF64(Some(b)) => *b == 0.0,
+        Value::Decimal(b) | Value::OptDecimal(Some(b)) => b.is_zero(),
+        _ => false,
+    };
+
+    if is_zero {
+        Err(ValueError::DivideByZero.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Exact arithmetic when either operand is a decimal. Integers are promoted into decimals,
+/// while mixing a decimal with a float fails with `DecimalWithFloat` rather than silently
+/// losing precision. A NULL decimal or integer yields a NULL decimal. Returns `None` when
+/// neither operand is a decimal.
+fn decimal_arithmetic(
+    a: &Value,
+    b: &Value,
+    op: impl Fn(Decimal, Decimal) -> Option<Decimal>,
+) -> Option<Result<Value>> {
+    let is_float = |v: &Value| matches!(v, Value::F64(_) | Value::OptF64(_));
+
+    if !a.is_decimal() && !b.is_decimal() {
+        return None;
+    }
+
+    if is_float(a) || is_float(b) {
+        return Some(Err(ValueError::DecimalWithFloat.into()));
+    }
+
+    let is_exact = |v: &Value| {
+        matches!(
+            v,
+            Value::Decimal(_) | Value::OptDecimal(_) | Value::I64(_) | Value::OptI64(_)
+        )
+    };
+    let is_null = |v: &Value| matches!(v, Value::OptDecimal(None) | Value::OptI64(None));
+
+    if is_exact(a) && is_exact(b) && (is_null(a) || is_null(b)) {
+        return Some(Ok(Value::OptDecimal(None)));
+    }
+
+    let (a, b, nullable) = promote_to_decimal(a, b)?;
+
+    Some(
+        op(a, b)
+            .map(|v| nullable.as_value(Value::OptDecimal(Some(v)), Value::Decimal(v)))
+            .ok_or_else(|| ValueError::NumericOverflow.into()),
+    )
+}
+
+/// Content of a string-like literal as bytes. `N'..'` strings are UTF-8 like any other string,
+/// while `X'..'` is decoded from hex and yields `None` unless it holds whole bytes.
+pub(crate) fn literal_bytes(literal: &AstValue) -> Option<Cow<'_, [u8]>> {
+    match literal {
+        AstValue::SingleQuotedString(v) | AstValue::NationalStringLiteral(v) => {
+            Some(Cow::Borrowed(v.as_bytes()))
+        }
+        AstValue::HexStringLiteral(v) => {
+            if v.len() % 2 != 0 || !v.is_ascii() {
+                return None;
+            }
+
+            (0..v.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&v[i..i + 2], 16).ok())
+                .collect::<Option<Vec<_>>>()
+                .map(Cow::Owned)
+        }
+        _ => None,
+    }
+}
+
+/// `YYYY-MM-DD` only, a literal with a time of day is not a date.
+pub(crate) fn parse_date(v: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok()
+}
+
+/// ISO 8601 date and time separated by a space or `T`, with optional fractional seconds.
+/// A date alone is taken as midnight, and RFC 3339 with an offset is converted into UTC.
+pub(crate) fn parse_timestamp(v: &str) -> Option<NaiveDateTime> {
+    let v = v.trim();
+
+    parse_date(v)
+        .and_then(|v| v.and_hms_opt(0, 0, 0))
+        .or_else(|| NaiveDateTime::parse_from_str(v, "%Y-%m-%d %H:%M:%S%.f").ok())
+        .or_else(|| NaiveDateTime::parse_from_str(v, "%Y-%m-%dT%H:%M:%S%.f").ok())
+        .or_else(|| DateTime::parse_from_rfc3339(v).ok().map(|v| v.naive_utc()))
+}
+
+/// Dates are compared with timestamps as midnight of that day.
+pub(crate) fn timestamp_of(v: &Value) -> Option<NaiveDateTime> {
+    match v {
+        Value::Date(v) | Value::OptDate(Some(v)) => v.and_hms_opt(0, 0, 0),
+        Value::Timestamp(v) | Value::OptTimestamp(Some(v)) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Accepts both plain and scientific notation, e.g. `0.1` and `1e-1`.
+fn parse_decimal(v: &str) -> Option<Decimal> {
+    v.parse::<Decimal>()
+        .ok()
+        .or_else(|| Decimal::from_scientific(v).ok())
+}
+
 impl Value {
     pub fn from_data_type(data_type: DataType, nullable: bool, literal: &AstValue) -> Result<Self> {
         match (data_type, literal) {
@@ -185,9 +550,23 @@ impl Value {
                 .parse()
                 .map(|v| nullable.as_value(Value::OptF64(Some(v)), Value::F64(v)))
                 .map_err(|_| ValueError::FailedToParseNumber.into()),
+            (DataType::Decimal(..), AstValue::Number(v)) => parse_decimal(v)
+                .map(|v| nullable.as_value(Value::OptDecimal(Some(v)), Value::Decimal(v)))
+                .ok_or_else(|| ValueError::FailedToParseNumber.into()),
             (DataType::Boolean, AstValue::Boolean(v)) => {
                 Ok(nullable.as_value(Value::OptBool(Some(*v)), Value::Bool(*v)))
             }
+            (DataType::Date, AstValue::SingleQuotedString(v)) => parse_date(v)
+                .map(|v| nullable.as_value(Value::OptDate(Some(v)), Value::Date(v)))
+                .ok_or_else(|| failed_to_parse_temporal(v, "DATE")),
+            (DataType::Timestamp, AstValue::SingleQuotedString(v)) => parse_timestamp(v)
+                .map(|v| nullable.as_value(Value::OptTimestamp(Some(v)), Value::Timestamp(v)))
+                .ok_or_else(|| failed_to_parse_temporal(v, "TIMESTAMP")),
+            (DataType::Bytea, AstValue::HexStringLiteral(v)) => literal_bytes(literal)
+                .map(|v| {
+                    nullable.as_value(Value::OptBytes(Some(v.to_vec())), Value::Bytes(v.to_vec()))
+                })
+                .ok_or_else(|| ValueError::FailedToParseHex(v.to_owned()).into()),
             (DataType::Int, AstValue::Null) => nullable.as_result(
                 Value::OptI64(None),
                 ValueError::NullValueOnNotNullField.into(),
@@ -204,107 +583,407 @@ impl Value {
                 Value::OptStr(None),
                 ValueError::NullValueOnNotNullField.into(),
             ),
+            (DataType::Decimal(..), AstValue::Null) => nullable.as_result(
+                Value::OptDecimal(None),
+                ValueError::NullValueOnNotNullField.into(),
+            ),
+            (DataType::Date, AstValue::Null) => nullable.as_result(
+                Value::OptDate(None),
+                ValueError::NullValueOnNotNullField.into(),
+            ),
+            (DataType::Timestamp, AstValue::Null) => nullable.as_result(
+                Value::OptTimestamp(None),
+                ValueError::NullValueOnNotNullField.into(),
+            ),
+            (DataType::Bytea, AstValue::Null) => nullable.as_result(
+                Value::OptBytes(None),
+                ValueError::NullValueOnNotNullField.into(),
+            ),
             _ => Err(ValueError::SqlTypeNotSupported.into()),
         }
     }
 
+    /// Builds a value of the same type as `self` from `literal`. Integer literals are widened
+    /// into float columns, while float literals are never truncated into integer columns.
     pub fn clone_by(&self, literal: &AstValue) -> Result<Self> {
+        let cast_failed = || {
+            let data_type = match self {
+                Value::Bool(_) | Value::OptBool(_) => "BOOLEAN",
+                Value::I64(_) | Value::OptI64(_) => "INTEGER",
+                Value::F64(_) | Value::OptF64(_) => "FLOAT",
+                Value::Str(_) | Value::OptStr(_) => "TEXT",
+                Value::Decimal(_) | Value::OptDecimal(_) => "DECIMAL",
+                Value::Date(_) | Value::OptDate(_) => "DATE",
+                Value::Timestamp(_) | Value::OptTimestamp(_) => "TIMESTAMP",
+                Value::Bytes(_) | Value::OptBytes(_) => "BYTEA",
+                Value::Empty => "NULL",
+            };
+
+            ValueError::LiteralCastFailed {
+                literal: literal.to_string(),
+                data_type: data_type.to_owned(),
+            }
+            .into()
+        };
+
         match (self, literal) {
-            (Value::I64(_), AstValue::Number(v)) => v
-                .parse()
-                .map(Value::I64)
-                .map_err(|_| ValueError::FailedToParseNumber.into()),
+            (Value::I64(_), AstValue::Number(v)) => {
+                v.parse().map(Value::I64).map_err(|_| cast_failed())
+            }
             (Value::OptI64(_), AstValue::Number(v)) => v
                 .parse()
                 .map(|v| Value::OptI64(Some(v)))
-                .map_err(|_| ValueError::FailedToParseNumber.into()),
-            (Value::OptI64(_), AstValue::Null) => Ok(Value::OptI64(None)),
-            (Value::F64(_), AstValue::Number(v)) => v
-                .parse()
-                .map(Value::F64)
-                .map_err(|_| ValueError::FailedToParseNumber.into()),
+                .map_err(|_| cast_failed()),
+            (Value::F64(_), AstValue::Number(v)) => {
+                v.parse().map(Value::F64).map_err(|_| cast_failed())
+            }
             (Value::OptF64(_), AstValue::Number(v)) => v
                 .parse()
                 .map(|v| Value::OptF64(Some(v)))
-                .map_err(|_| ValueError::FailedToParseNumber.into()),
-            (Value::OptF64(_), AstValue::Null) => Ok(Value::OptF64(None)),
+                .map_err(|_| cast_failed()),
+            (Value::Decimal(_), AstValue::Number(v)) => {
+                parse_decimal(v).map(Value::Decimal).ok_or_else(cast_failed)
+            }
+            (Value::OptDecimal(_), AstValue::Number(v)) => parse_decimal(v)
+                .map(|v| Value::OptDecimal(Some(v)))
+                .ok_or_else(cast_failed),
             (Value::Str(_), AstValue::SingleQuotedString(v)) => Ok(Value::Str(v.clone())),
             (Value::OptStr(_), AstValue::SingleQuotedString(v)) => {
                 Ok(Value::OptStr(Some(v.clone())))
             }
-            (Value::OptStr(_), AstValue::Null) => Ok(Value::OptStr(None)),
+            (Value::Date(_), AstValue::SingleQuotedString(v)) => {
+                parse_date(v).map(Value::Date).ok_or_else(cast_failed)
+            }
+            (Value::OptDate(_), AstValue::SingleQuotedString(v)) => parse_date(v)
+                .map(|v| Value::OptDate(Some(v)))
+                .ok_or_else(cast_failed),
+            (Value::Timestamp(_), AstValue::SingleQuotedString(v)) => parse_timestamp(v)
+                .map(Value::Timestamp)
+                .ok_or_else(cast_failed),
+            (Value::OptTimestamp(_), AstValue::SingleQuotedString(v)) => parse_timestamp(v)
+                .map(|v| Value::OptTimestamp(Some(v)))
+                .ok_or_else(cast_failed),
+            (Value::Bytes(_), AstValue::HexStringLiteral(_)) => literal_bytes(literal)
+                .map(|v| Value::Bytes(v.to_vec()))
+                .ok_or_else(cast_failed),
+            (Value::OptBytes(_), AstValue::HexStringLiteral(_)) => literal_bytes(literal)
+                .map(|v| Value::OptBytes(Some(v.to_vec())))
+                .ok_or_else(cast_failed),
             (Value::Bool(_), AstValue::Boolean(v)) => Ok(Value::Bool(*v)),
             (Value::OptBool(_), AstValue::Boolean(v)) => Ok(Value::OptBool(Some(*v))),
+            (Value::OptI64(_), AstValue::Null) => Ok(Value::OptI64(None)),
+            (Value::OptF64(_), AstValue::Null) => Ok(Value::OptF64(None)),
+            (Value::OptStr(_), AstValue::Null) => Ok(Value::OptStr(None)),
             (Value::OptBool(_), AstValue::Null) => Ok(Value::OptBool(None)),
-            _ => Err(ValueError::LiteralNotSupported.into()),
+            (Value::OptDecimal(_), AstValue::Null) => Ok(Value::OptDecimal(None)),
+            (Value::OptDate(_), AstValue::Null) => Ok(Value::OptDate(None)),
+            (Value::OptTimestamp(_), AstValue::Null) => Ok(Value::OptTimestamp(None)),
+            (Value::OptBytes(_), AstValue::Null) => Ok(Value::OptBytes(None)),
+            (Value::Bool(_), AstValue::Null)
+            | (Value::I64(_), AstValue::Null)
+            | (Value::F64(_), AstValue::Null)
+            | (Value::Str(_), AstValue::Null)
+            | (Value::Decimal(_), AstValue::Null)
+            | (Value::Date(_), AstValue::Null)
+            | (Value::Timestamp(_), AstValue::Null)
+            | (Value::Bytes(_), AstValue::Null) => Err(ValueError::NullValueOnNotNullField.into()),
+            (Value::Empty, _) => Value::try_from(literal),
+            _ => Err(cast_failed()),
+        }
+    }
+
+    /// Shifts a `DATE` or `TIMESTAMP` by an interval, see `Interval::add_to_date`. A date stays a
+    /// date unless the interval has a time of day part, and NULL stays NULL.
+    pub fn add_interval(&self, interval: &Interval) -> Result<Value> {
+        self.shift(interval, false)
+    }
+
+    pub fn subtract_interval(&self, interval: &Interval) -> Result<Value> {
+        self.shift(&interval.negate()?, false)
+    }
+
+    fn shift(&self, interval: &Interval, nullable: bool) -> Result<Value> {
+        use Value::*;
+
+        let out_of_range = || ValueError::DateOutOfRange.into();
+        let nullable = nullable || matches!(self, OptDate(_) | OptTimestamp(_));
+        let timestamp = |v: NaiveDateTime| {
+            interval
+                .add_to_timestamp(v)
+                .map(|v| nullable.as_value(OptTimestamp(Some(v)), Timestamp(v)))
+                .ok_or_else(out_of_range)
+        };
+
+        match self {
+            Date(v) | OptDate(Some(v)) if !interval.has_time() => interval
+                .add_to_date(*v)
+                .map(|v| nullable.as_value(OptDate(Some(v)), Date(v)))
+                .ok_or_else(out_of_range),
+            Date(v) | OptDate(Some(v)) => {
+                timestamp(v.and_hms_opt(0, 0, 0).ok_or_else(out_of_range)?)
+            }
+            Timestamp(v) | OptTimestamp(Some(v)) => timestamp(*v),
+            OptDate(None) if !interval.has_time() => Ok(OptDate(None)),
+            OptDate(None) | OptTimestamp(None) => Ok(OptTimestamp(None)),
+            _ => Err(ValueError::IntervalOnNonTemporal.into()),
         }
     }
 
     pub fn add(&self, other: &Value) -> Result<Value> {
         use Value::*;
 
+        if let Some(v) = temporal_arithmetic(self, other, false) {
+            return v;
+        }
+
+        if let Some(v) = decimal_arithmetic(self, other, |a, b| a.checked_add(b)) {
+            return v;
+        }
+
+        if let Some(v) = null_arithmetic(self, other) {
+            return Ok(v);
+        }
+
+        let overflow = || ValueError::NumericOverflow.into();
+
         match (self, other) {
-            (I64(a), I64(b)) => Ok(I64(a + b)),
-            (I64(a), OptI64(Some(b))) | (OptI64(Some(a)), I64(b)) => Ok(OptI64(Some(a + b))),
-            (OptI64(Some(a)), OptI64(Some(b))) => Ok(OptI64(Some(a + b))),
-            (OptI64(None), OptI64(Some(a))) | (OptI64(Some(a)), OptI64(None)) => {
-                Ok(OptI64(Some(*a)))
-            }
+            (I64(a), I64(b)) => a.checked_add(*b).map(I64).ok_or_else(overflow),
+            (I64(a), OptI64(Some(b)))
+            | (OptI64(Some(a)), I64(b))
+            | (OptI64(Some(a)), OptI64(Some(b))) => a
+                .checked_add(*b)
+                .map(|v| OptI64(Some(v)))
+                .ok_or_else(overflow),
             (F64(a), F64(b)) => Ok(F64(a + b)),
-            (F64(a), OptF64(Some(b))) | (OptF64(Some(a)), F64(b)) => Ok(OptF64(Some(a + b))),
-            _ => Err(ValueError::AddOnNonNumeric.into()),
+            (F64(a), OptF64(Some(b)))
+            | (OptF64(Some(a)), F64(b))
+            | (OptF64(Some(a)), OptF64(Some(b))) => Ok(OptF64(Some(a + b))),
+            _ => match promote_to_float(self, other) {
+                Some((a, b, nullable)) => Ok(nullable.as_value(OptF64(Some(a + b)), F64(a + b))),
+                None => Err(ValueError::AddOnNonNumeric.into()),
+            },
         }
     }
 
     pub fn subtract(&self, other: &Value) -> Result<Value> {
         use Value::*;
 
+        if let Some(v) = temporal_arithmetic(self, other, true) {
+            return v;
+        }
+
+        if let Some(v) = decimal_arithmetic(self, other, |a, b| a.checked_sub(b)) {
+            return v;
+        }
+
+        if let Some(v) = null_arithmetic(self, other) {
+            return Ok(v);
+        }
+
+        let overflow = || ValueError::NumericOverflow.into();
+
         match (self, other) {
-            (I64(a), I64(b)) => Ok(I64(a - b)),
-            (I64(a), OptI64(Some(b))) | (OptI64(Some(a)), I64(b)) => Ok(OptI64(Some(a - b))),
+            (I64(a), I64(b)) => a.checked_sub(*b).map(I64).ok_or_else(overflow),
+            (I64(a), OptI64(Some(b)))
+            | (OptI64(Some(a)), I64(b))
+            | (OptI64(Some(a)), OptI64(Some(b))) => a
+                .checked_sub(*b)
+                .map(|v| OptI64(Some(v)))
+                .ok_or_else(overflow),
             (F64(a), F64(b)) => Ok(F64(a - b)),
-            (F64(a), OptF64(Some(b))) | (OptF64(Some(a)), F64(b)) => Ok(OptF64(Some(a - b))),
-            _ => Err(ValueError::SubtractOnNonNumeric.into()),
+            (F64(a), OptF64(Some(b)))
+            | (OptF64(Some(a)), F64(b))
+            | (OptF64(Some(a)), OptF64(Some(b))) => Ok(OptF64(Some(a - b))),
+            _ => match promote_to_float(self, other) {
+                Some((a, b, nullable)) => Ok(nullable.as_value(OptF64(Some(a - b)), F64(a - b))),
+                None => Err(ValueError::SubtractOnNonNumeric.into()),
+            },
         }
     }
 
     pub fn multiply(&self, other: &Value) -> Result<Value> {
         use Value::*;
 
+        if let Some(v) = decimal_arithmetic(self, other, |a, b| a.checked_mul(b)) {
+            return v;
+        }
+
+        if let Some(v) = null_arithmetic(self, other) {
+            return Ok(v);
+        }
+
+        let overflow = || ValueError::NumericOverflow.into();
+
         match (self, other) {
-            (I64(a), I64(b)) => Ok(I64(a * b)),
-            (I64(a), OptI64(Some(b))) | (OptI64(Some(a)), I64(b)) => Ok(OptI64(Some(a * b))),
+            (I64(a), I64(b)) => a.checked_mul(*b).map(I64).ok_or_else(overflow),
+            (I64(a), OptI64(Some(b)))
+            | (OptI64(Some(a)), I64(b))
+            | (OptI64(Some(a)), OptI64(Some(b))) => a
+                .checked_mul(*b)
+                .map(|v| OptI64(Some(v)))
+                .ok_or_else(overflow),
             (F64(a), F64(b)) => Ok(F64(a * b)),
-            (F64(a), OptF64(Some(b))) | (OptF64(Some(a)), F64(b)) => Ok(OptF64(Some(a * b))),
-            _ => Err(ValueError::MultiplyOnNonNumeric.into()),
+            (F64(a), OptF64(Some(b)))
+            | (OptF64(Some(a)), F64(b))
+            | (OptF64(Some(a)), OptF64(Some(b))) => Ok(OptF64(Some(a * b))),
+            _ => match promote_to_float(self, other) {
+                Some((a, b, nullable)) => Ok(nullable.as_value(OptF64(Some(a * b)), F64(a * b))),
+                None => Err(ValueError::MultiplyOnNonNumeric.into()),
+            },
         }
     }
 
+    /// A zero divisor fails with `ValueError::DivideByZero`, floats included, unless the
+    /// dividend is NULL.
     pub fn divide(&self, other: &Value) -> Result<Value> {
         use Value::*;
 
+        if let Some(v) = null_arithmetic(self, other) {
+            return Ok(v);
+        }
+
+        check_divisor(other)?;
+
+        if let Some(v) = decimal_arithmetic(self, other, |a, b| a.checked_div(b)) {
+            return v;
+        }
+
+        let overflow = || ValueError::NumericOverflow.into();
+
         match (self, other) {
-            (I64(a), I64(b)) => Ok(I64(a / b)),
-            (I64(a), OptI64(Some(b))) | (OptI64(Some(a)), I64(b)) => Ok(OptI64(Some(a / b))),
+            (I64(a), I64(b)) => a.checked_div(*b).map(I64).ok_or_else(overflow),
+            (I64(a), OptI64(Some(b)))
+            | (OptI64(Some(a)), I64(b))
+            | (OptI64(Some(a)), OptI64(Some(b))) => a
+                .checked_div(*b)
+                .map(|v| OptI64(Some(v)))
+                .ok_or_else(overflow),
             (F64(a), F64(b)) => Ok(F64(a / b)),
-            (F64(a), OptF64(Some(b))) | (OptF64(Some(a)), F64(b)) => Ok(OptF64(Some(a / b))),
-            _ => Err(ValueError::DivideOnNonNumeric.into()),
+            (F64(a), OptF64(Some(b)))
+            | (OptF64(Some(a)), F64(b))
+            | (OptF64(Some(a)), OptF64(Some(b))) => Ok(OptF64(Some(a / b))),
+            _ => match promote_to_float(self, other) {
+                Some((a, b, nullable)) => Ok(nullable.as_value(OptF64(Some(a / b)), F64(a / b))),
+                None => Err(ValueError::DivideOnNonNumeric.into()),
+            },
         }
     }
 
-    pub fn is_some(&self) -> bool {
+    /// Truncating remainder, the result takes the sign of the dividend as in SQL, so `-7 % 3`
+    /// is `-1` and `7 % -3` is `1`.
+    pub fn modulo(&self, other: &Value) -> Result<Value> {
         use Value::*;
 
+        if let Some(v) = null_arithmetic(self, other) {
+            return Ok(v);
+        }
+
+        check_divisor(other)?;
+
+        if let Some(v) = decimal_arithmetic(self, other, |a, b| a.checked_rem(b)) {
+            return v;
+        }
+
+        let overflow = || ValueError::NumericOverflow.into();
+        let rem = |a: &i64, b: &i64| if *b == -1 { Some(0) } else { a.checked_rem(*b) };
+
+        match (self, other) {
+            (I64(a), I64(b)) => rem(a, b).map(I64).ok_or_else(overflow),
+            (I64(a), OptI64(Some(b)))
+            | (OptI64(Some(a)), I64(b))
+            | (OptI64(Some(a)), OptI64(Some(b))) => {
+                rem(a, b).map(|v| OptI64(Some(v))).ok_or_else(overflow)
+            }
+            (F64(a), F64(b)) => Ok(F64(a % b)),
+            (F64(a), OptF64(Some(b)))
+            | (OptF64(Some(a)), F64(b))
+            | (OptF64(Some(a)), OptF64(Some(b))) => Ok(OptF64(Some(a % b))),
+            _ => match promote_to_float(self, other) {
+                Some((a, b, nullable)) => Ok(nullable.as_value(OptF64(Some(a % b)), F64(a % b))),
+                None => Err(ValueError::ModuloOnNonNumeric.into()),
+            },
+        }
+    }
+
+    pub fn unary_minus(&self) -> Result<Value> {
+        use Value::*;
+
+        let overflow = || ValueError::NumericOverflow.into();
+
         match self {
-            Empty | OptBool(None) | OptI64(None) | OptF64(None) | OptStr(None) => false,
-            _ => true,
+            I64(a) => a.checked_neg().map(I64).ok_or_else(overflow),
+            OptI64(Some(a)) => a
+                .checked_neg()
+                .map(|a| OptI64(Some(a)))
+                .ok_or_else(overflow),
+            OptI64(None) => Ok(OptI64(None)),
+            F64(a) => Ok(F64(-a)),
+            OptF64(a) => Ok(OptF64(a.map(|a| -a))),
+            Value::Decimal(a) => Ok(Value::Decimal(-a)),
+            OptDecimal(a) => Ok(OptDecimal(a.map(|a| -a))),
+            _ => Err(ValueError::UnaryMinusOnNonNumeric.into()),
         }
     }
+
+    pub fn abs(&self) -> Result<Value> {
+        use Value::*;
+
+        let overflow = || ValueError::NumericOverflow.into();
+
+        match self {
+            I64(a) => a.checked_abs().map(I64).ok_or_else(overflow),
+            OptI64(Some(a)) => a
+                .checked_abs()
+                .map(|a| OptI64(Some(a)))
+                .ok_or_else(overflow),
+            OptI64(None) => Ok(OptI64(None)),
+            F64(a) => Ok(F64(a.abs())),
+            OptF64(a) => Ok(OptF64(a.map(f64::abs))),
+            Value::Decimal(a) => Ok(Value::Decimal(a.abs())),
+            OptDecimal(a) => Ok(OptDecimal(a.map(|a| a.abs()))),
+            _ => Err(ValueError::AbsOnNonNumeric.into()),
+        }
+    }
+
+    pub fn is_some(&self) -> bool {
+        use Value::*;
+
+        !matches!(
+            self,
+            Empty
+                | OptBool(None)
+                | OptI64(None)
+                | OptF64(None)
+                | OptStr(None)
+                | OptDecimal(None)
+                | OptDate(None)
+                | OptTimestamp(None)
+                | OptBytes(None)
+        )
+    }
+
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, Value::Decimal(_) | Value::OptDecimal(_))
+    }
+
+    fn is_date(&self) -> bool {
+        matches!(self, Value::Date(_) | Value::OptDate(_))
+    }
+
+    /// `DATE` and `TIMESTAMP` values, including NULLs of those types.
+    pub fn is_temporal(&self) -> bool {
+        use Value::*;
+
+        matches!(self, Date(_) | OptDate(_) | Timestamp(_) | OptTimestamp(_))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Value;
+    use sqlparser::ast::Value as AstValue;
+    use std::cmp::Ordering;
+
+    use super::{Value, ValueError};
 
     #[test]
     fn eq() {
@@ -328,4 +1007,275 @@ mod tests {
 
         assert_eq!(Value::Empty, Value::Empty);
     }
+
+    #[test]
+    fn eq_number_literal() {
+        let number = |v: &str| AstValue::Number(v.to_owned());
+
+        assert_eq!(Value::F64(1.0), number("1"));
+        assert_eq!(Value::OptF64(Some(-3.0)), number("-3"));
+        assert_eq!(Value::F64(0.0), number("-0"));
+        assert_eq!(Value::I64(1), number("1.0"));
+        assert_eq!(Value::OptI64(Some(100)), number("1e2"));
+        assert_ne!(Value::F64(1.5), number("1"));
+        assert_ne!(Value::F64(1.0), number("2"));
+        assert_ne!(Value::I64(1), number("1.5"));
+        assert_ne!(Value::F64(f64::NAN), number("0"));
+
+        assert_ne!(
+            Value::F64(9_007_199_254_740_992.0),
+            number("9007199254740993")
+        );
+        assert_eq!(
+            Value::F64(9_007_199_254_740_992.0),
+            number("9007199254740992")
+        );
+        assert_ne!(Value::I64(i64::MAX), number("9223372036854775808.0"));
+        assert_ne!(
+            Value::I64(9_007_199_254_740_993),
+            number("9007199254740992.0")
+        );
+        assert_eq!(Value::I64(i64::MIN), number("-9223372036854775808.0"));
+    }
+
+    #[test]
+    fn decimal() {
+        use sqlparser::ast::DataType;
+
+        let decimal = |v: &str| Value::Decimal(v.parse().unwrap());
+        let number = |v: &str| AstValue::Number(v.to_owned());
+
+        assert_eq!(decimal("0.1").add(&decimal("0.2")), Ok(decimal("0.3")));
+        assert_eq!(decimal("0.3").subtract(&decimal("0.1")), Ok(decimal("0.2")));
+        assert_eq!(decimal("1.5").multiply(&Value::I64(2)), Ok(decimal("3.0")));
+        assert_eq!(
+            Value::OptI64(Some(1)).divide(&decimal("8")),
+            Ok(Value::OptDecimal(Some("0.125".parse().unwrap())))
+        );
+        assert_eq!(
+            decimal("0.1").add(&Value::F64(0.2)),
+            Err(ValueError::DecimalWithFloat.into())
+        );
+
+        assert_eq!(
+            decimal("1").add(&Value::OptDecimal(None)),
+            Ok(Value::OptDecimal(None))
+        );
+        assert_eq!(
+            Value::OptI64(None).multiply(&decimal("2")),
+            Ok(Value::OptDecimal(None))
+        );
+        assert_eq!(
+            decimal("1").divide(&Value::OptDecimal(None)),
+            Ok(Value::OptDecimal(None))
+        );
+
+        assert_eq!(decimal("0.30"), decimal("0.3"));
+        assert_eq!(decimal("0.3"), number("0.3"));
+        assert_eq!(decimal("1.0"), Value::I64(1));
+        assert_eq!(Value::OptI64(Some(1)), decimal("1"));
+        assert_ne!(decimal("1.5"), Value::I64(1));
+        assert_eq!(
+            decimal("0.1").partial_cmp(&Value::I64(1)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            decimal("0.1").partial_cmp(&number("1e-2")),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(decimal("0.1").partial_cmp(&Value::F64(0.1)), None);
+
+        assert_eq!(decimal("1").clone_by(&number("0.25")), Ok(decimal("0.25")));
+        assert_eq!(
+            Value::from_data_type(DataType::Decimal(None, None), true, &number("1.10")),
+            Ok(Value::OptDecimal(Some("1.10".parse().unwrap())))
+        );
+        assert_eq!(
+            Value::from_data_type(DataType::Decimal(None, None), false, &AstValue::Null),
+            Err(ValueError::NullValueOnNotNullField.into())
+        );
+    }
+
+    #[test]
+    fn clone_by() {
+        let number = |v: &str| AstValue::Number(v.to_owned());
+        let cast_failed = |literal: &str, data_type: &str| {
+            Err(ValueError::LiteralCastFailed {
+                literal: literal.to_owned(),
+                data_type: data_type.to_owned(),
+            }
+            .into())
+        };
+
+        assert_eq!(Value::F64(0.5).clone_by(&number("1")), Ok(Value::F64(1.0)));
+        assert_eq!(
+            Value::OptF64(None).clone_by(&number("2")),
+            Ok(Value::OptF64(Some(2.0)))
+        );
+        assert_eq!(Value::I64(1).clone_by(&number("2")), Ok(Value::I64(2)));
+        assert_eq!(
+            Value::I64(1).clone_by(&number("1.5")),
+            cast_failed("1.5", "INTEGER")
+        );
+        assert_eq!(
+            Value::Str("Glue".to_owned()).clone_by(&number("1")),
+            cast_failed("1", "TEXT")
+        );
+        assert_eq!(
+            Value::OptI64(Some(1)).clone_by(&AstValue::SingleQuotedString("a".to_owned())),
+            cast_failed("'a'", "INTEGER")
+        );
+        assert_eq!(
+            Value::Bool(true).clone_by(&AstValue::Null),
+            Err(ValueError::NullValueOnNotNullField.into())
+        );
+        assert_eq!(
+            Value::OptBool(Some(true)).clone_by(&AstValue::Null),
+            Ok(Value::OptBool(None))
+        );
+        assert_eq!(Value::Empty.clone_by(&number("3")), Ok(Value::I64(3)));
+    }
+
+    #[test]
+    fn date_arithmetic() {
+        use crate::data::Interval;
+        use chrono::NaiveDate;
+
+        let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let date = |y, m, d| Value::Date(day(y, m, d));
+
+        assert_eq!(date(2021, 2, 25).add(&Value::I64(7)), Ok(date(2021, 3, 4)));
+        assert_eq!(Value::I64(7).add(&date(2021, 2, 25)), Ok(date(2021, 3, 4)));
+        assert_eq!(
+            date(2021, 3, 4).subtract(&Value::I64(7)),
+            Ok(date(2021, 2, 25))
+        );
+        assert_eq!(
+            date(2021, 1, 1).add(&Value::OptI64(Some(-1))),
+            Ok(Value::OptDate(Some(day(2020, 12, 31))))
+        );
+        assert_eq!(
+            date(2021, 1, 1).add(&Value::OptI64(None)),
+            Ok(Value::OptDate(None))
+        );
+
+        let timestamp = day(2021, 1, 1).and_hms_opt(12, 0, 0).unwrap();
+        assert_eq!(
+            Value::Timestamp(timestamp).add(&Value::I64(1)),
+            Ok(Value::Timestamp(
+                day(2021, 1, 2).and_hms_opt(12, 0, 0).unwrap()
+            ))
+        );
+
+        assert_eq!(
+            date(2021, 3, 1).subtract(&date(2021, 2, 1)),
+            Ok(Value::I64(28))
+        );
+        assert_eq!(
+            date(2021, 2, 1).subtract(&date(2021, 3, 1)),
+            Ok(Value::I64(-28))
+        );
+        assert_eq!(
+            date(2021, 3, 1).subtract(&Value::OptDate(Some(day(2020, 3, 1)))),
+            Ok(Value::OptI64(Some(365)))
+        );
+        assert_eq!(
+            date(2021, 3, 1).subtract(&Value::OptDate(None)),
+            Ok(Value::OptI64(None))
+        );
+
+        let interval = |v: &str| {
+            Interval::parse(&AstValue::Interval {
+                value: v.to_owned(),
+                leading_field: None,
+                leading_precision: None,
+                last_field: None,
+                fractional_seconds_precision: None,
+            })
+            .unwrap()
+        };
+
+        assert_eq!(
+            date(2021, 1, 31).add_interval(&interval("1 month")),
+            Ok(date(2021, 2, 28))
+        );
+        assert_eq!(
+            date(2021, 1, 1).subtract_interval(&interval("1 year 1 day")),
+            Ok(date(2019, 12, 31))
+        );
+        assert_eq!(
+            date(2021, 1, 1).add_interval(&interval("36 hours")),
+            Ok(Value::Timestamp(
+                day(2021, 1, 2).and_hms_opt(12, 0, 0).unwrap()
+            ))
+        );
+        assert_eq!(
+            Value::OptDate(None).add_interval(&interval("1 day")),
+            Ok(Value::OptDate(None))
+        );
+
+        assert_eq!(
+            date(2021, 1, 1).multiply(&Value::I64(2)),
+            Err(ValueError::MultiplyOnNonNumeric.into())
+        );
+        assert_eq!(
+            date(2021, 1, 1).add(&date(2021, 1, 1)),
+            Err(ValueError::AddOnNonNumeric.into())
+        );
+        assert_eq!(
+            Value::I64(1).subtract(&date(2021, 1, 1)),
+            Err(ValueError::SubtractOnNonNumeric.into())
+        );
+        assert_eq!(
+            date(2021, 1, 1).add(&Value::F64(1.5)),
+            Err(ValueError::AddOnNonNumeric.into())
+        );
+        assert_eq!(
+            Value::I64(1).add_interval(&interval("1 day")),
+            Err(ValueError::IntervalOnNonTemporal.into())
+        );
+        assert_eq!(
+            date(2021, 1, 1).add(&Value::I64(i64::MAX)),
+            Err(ValueError::DateOutOfRange.into())
+        );
+    }
+
+    #[test]
+    fn arithmetic_with_null() {
+        use Value::*;
+
+        let ops: [fn(&Value, &Value) -> crate::result::Result<Value>; 5] = [
+            Value::add,
+            Value::subtract,
+            Value::multiply,
+            Value::divide,
+            Value::modulo,
+        ];
+
+        for op in ops.iter() {
+            assert_eq!(op(&OptI64(None), &I64(5)), Ok(OptI64(None)));
+            assert_eq!(op(&I64(5), &OptI64(None)), Ok(OptI64(None)));
+            assert_eq!(op(&OptI64(Some(5)), &OptI64(None)), Ok(OptI64(None)));
+            assert_eq!(op(&OptI64(None), &F64(1.5)), Ok(OptF64(None)));
+            assert_eq!(op(&OptF64(None), &I64(0)), Ok(OptF64(None)));
+            assert!(op(&OptI64(None), &Str("a".to_owned())).is_err());
+        }
+    }
+
+    #[test]
+    fn divide_by_zero() {
+        use rust_decimal::Decimal;
+        use Value::*;
+
+        let divide_by_zero = Err(ValueError::DivideByZero.into());
+
+        for zero in [I64(0), OptI64(Some(0)), F64(0.0), Decimal(Decimal::ZERO)] {
+            assert_eq!(I64(1).divide(&zero), divide_by_zero);
+            assert_eq!(I64(1).modulo(&zero), divide_by_zero);
+        }
+
+        assert_eq!(OptI64(None).divide(&I64(0)), Ok(OptI64(None)));
+        assert_eq!(I64(6).divide(&I64(3)), Ok(I64(2)));
+        assert_eq!(I64(7).modulo(&OptI64(Some(-3))), Ok(OptI64(Some(1))));
+    }
 }
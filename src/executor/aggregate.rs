@@ -10,6 +10,7 @@ use thiserror::Error;
 use sqlparser::ast::{Expr, Function, SelectItem};
 
 use super::context::{AggregateContext, BlendContext};
+use super::evaluate::is_scalar_function;
 use crate::data::{get_name, Value};
 use crate::result::Result;
 
@@ -69,7 +70,7 @@ impl<'a> Aggregate<'a> {
                     .iter()
                     .try_fold(aggregated, |aggregated, field| match field {
                         SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
-                            aggregate(index, aggregated, &context, &expr)
+                            aggregate(index, aggregated, &context, expr)
                         }
                         _ => Ok(aggregated),
                     })?;
@@ -126,7 +127,7 @@ fn aggregate<'a>(
         _ => Err(AggregateError::OnlyIdentifierAllowed.into()),
     };
     let get_first_value = |args: &[Expr]| {
-        let expr = args.get(0).ok_or(AggregateError::Unreachable)?;
+        let expr = args.first().ok_or(AggregateError::Unreachable)?;
 
         get_value(expr)
     };
@@ -142,12 +143,13 @@ fn aggregate<'a>(
             .try_fold(aggregated, |aggregated, expr| aggr(aggregated, expr)),
         Expr::UnaryOp { expr, .. } => aggr(aggregated, expr),
         Expr::Nested(expr) => aggr(aggregated, expr),
+        Expr::Function(func) if is_scalar_function(func) => Ok(aggregated),
         Expr::Function(func) => {
             let Function { name, args, .. } = func;
 
             match get_name(name)?.to_uppercase().as_str() {
                 "COUNT" => {
-                    let expr = args.get(0).ok_or(AggregateError::Unreachable)?;
+                    let expr = args.first().ok_or(AggregateError::Unreachable)?;
 
                     let value_to_incr = Value::I64(match expr {
                         Expr::Wildcard => 1,
@@ -180,7 +182,14 @@ fn aggregate<'a>(
                                 return Ok(aggregated);
                             }
 
-                            Ok(aggregated.update(func, (index, value.add(value_to_sum)?)))
+                            // SUM skips NULLs, which `add` would carry into the result.
+                            let sum = match (value.is_some(), value_to_sum.is_some()) {
+                                (true, true) => value.add(value_to_sum)?,
+                                (true, false) => value.clone(),
+                                (false, _) => value_to_sum.clone(),
+                            };
+
+                            Ok(aggregated.update(func, (index, sum)))
                         }
                         None => Ok(aggregated.update(func, (index, value_to_sum.clone()))),
                     }
@@ -244,7 +253,7 @@ fn check(expr: &Expr) -> bool {
         Expr::BinaryOp { left, right, .. } => check(left) || check(right),
         Expr::UnaryOp { expr, .. } => check(expr),
         Expr::Nested(expr) => check(expr),
-        Expr::Function(_) => true,
+        Expr::Function(func) => !is_scalar_function(func),
         _ => false,
     }
 }
@@ -1,7 +1,6 @@
 use im_rc::HashMap;
 use iter_enum::Iterator;
 use serde::Serialize;
-use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::iter::once;
 use std::rc::Rc;
@@ -10,7 +9,7 @@ use thiserror::Error;
 use sqlparser::ast::{Expr, Function, Ident, SelectItem};
 
 use super::context::{AggregateContext, BlendContext, FilterContext};
-use super::evaluate::{evaluate, Evaluated};
+use super::evaluate::{evaluate, Clock};
 use crate::data::{get_name, Row, Value};
 use crate::result::Result;
 use crate::store::Store;
@@ -29,6 +28,7 @@ pub enum BlendError {
 
 pub struct Blend<'a, T: 'static + Debug> {
     storage: &'a dyn Store<T>,
+    clock: &'a dyn Clock,
     fields: &'a [SelectItem],
 }
 
@@ -48,8 +48,12 @@ struct Context<'a> {
 }
 
 impl<'a, T: 'static + Debug> Blend<'a, T> {
-    pub fn new(storage: &'a dyn Store<T>, fields: &'a [SelectItem]) -> Self {
-        Self { storage, fields }
+    pub fn new(storage: &'a dyn Store<T>, clock: &'a dyn Clock, fields: &'a [SelectItem]) -> Self {
+        Self {
+            storage,
+            clock,
+            fields,
+        }
     }
 
     pub fn apply(&self, context: Result<AggregateContext<'a>>) -> Result<Row> {
@@ -176,9 +180,14 @@ impl<'a, T: 'static + Debug> Blend<'a, T> {
                             ))),
                         }
                     }
-                    Expr::BinaryOp { .. } | Expr::Function(_) => {
+                    Expr::BinaryOp { .. }
+                    | Expr::UnaryOp { .. }
+                    | Expr::Cast { .. }
+                    | Expr::Extract { .. }
+                    | Expr::Function(_) => {
                         let value = evaluate_blended(
                             self.storage,
+                            self.clock,
                             None,
                             &context,
                             aggregated.as_ref(),
@@ -250,10 +259,7 @@ fn get_all_values(context: &Context<'_>) -> Vec<Rc<Value>> {
     };
 
     match next.as_ref() {
-        Some(next) => get_all_values(next)
-            .into_iter()
-            .chain(values.into_iter())
-            .collect(),
+        Some(next) => get_all_values(next).into_iter().chain(values).collect(),
         None => values,
     }
 }
@@ -280,6 +286,7 @@ fn get_alias_values(context: &Context<'_>, alias: &str) -> Option<Vec<Rc<Value>>
 
 fn evaluate_blended<T: 'static + Debug>(
     storage: &dyn Store<T>,
+    clock: &dyn Clock,
     filter_context: Option<&FilterContext<'_>>,
     context: &Context<'_>,
     aggregated: Option<&HashMap<&Function, Value>>,
@@ -294,24 +301,20 @@ fn evaluate_blended<T: 'static + Debug>(
 
     // TODO: Remove clone
     let row = values.as_ref().map(|values| {
-        let values = values.iter().map(|v| Value::clone(&v)).collect();
+        let values = values.iter().map(|v| Value::clone(v)).collect();
 
         Row(values)
     });
 
     let row_context = row
         .as_ref()
-        .map(|row| FilterContext::new(table_alias, &columns, row, filter_context));
+        .map(|row| FilterContext::new(table_alias, columns, row, filter_context));
     let filter_context = row_context.as_ref().or(filter_context);
 
     match next {
-        Some(context) => evaluate_blended(storage, filter_context, context, aggregated, expr),
-        None => match evaluate(storage, filter_context, aggregated, expr)? {
-            Evaluated::LiteralRef(v) => Value::try_from(v),
-            Evaluated::Literal(v) => Value::try_from(&v),
-            Evaluated::StringRef(v) => Ok(Value::Str(v.to_string())),
-            Evaluated::ValueRef(v) => Ok(v.clone()),
-            Evaluated::Value(v) => Ok(v),
-        },
+        Some(context) => {
+            evaluate_blended(storage, clock, filter_context, context, aggregated, expr)
+        }
+        None => evaluate(storage, clock, filter_context, aggregated, expr)?.into_value(),
     }
 }
@@ -0,0 +1,133 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::result::Result;
+
+use super::Evaluated;
+
+/// Operator form of the fallible arithmetic on `Evaluated`.
+///
+/// `std::ops` traits cannot return `Result`, so `&a + &b` yields a `Checked` which carries the
+/// result along. Once an operation fails, the error is passed through the rest of the chain:
+///
+/// ```
+/// use gluesql::{Checked, Evaluated, Value};
+///
+/// let a = Evaluated::Value(Value::I64(6));
+/// let b = Evaluated::Value(Value::I64(2));
+///
+/// let found = (&a + &b) * &b;
+/// assert!(found.into_result().unwrap() == Evaluated::Value(Value::I64(16)));
+///
+/// let found = Checked::from(a) / &Evaluated::Value(Value::I64(0)) + &b;
+/// assert!(found.into_result().is_err());
+/// ```
+#[derive(Debug)]
+pub struct Checked<'a>(pub Result<Evaluated<'a>>);
+
+impl<'a> Checked<'a> {
+    pub fn into_result(self) -> Result<Evaluated<'a>> {
+        self.0
+    }
+
+    fn apply(
+        self,
+        other: &Evaluated<'a>,
+        op: fn(&Evaluated<'a>, &Evaluated<'a>) -> Result<Evaluated<'a>>,
+    ) -> Self {
+        Checked(self.0.and_then(|l| op(&l, other)))
+    }
+}
+
+impl<'a> From<Evaluated<'a>> for Checked<'a> {
+    fn from(evaluated: Evaluated<'a>) -> Self {
+        Checked(Ok(evaluated))
+    }
+}
+
+impl<'a> From<Result<Evaluated<'a>>> for Checked<'a> {
+    fn from(result: Result<Evaluated<'a>>) -> Self {
+        Checked(result)
+    }
+}
+
+macro_rules! impl_checked_op {
+    ($trait: ident, $op: ident, $method: ident) => {
+        impl<'a> $trait<&Evaluated<'a>> for &Evaluated<'a> {
+            type Output = Checked<'a>;
+
+            fn $op(self, other: &Evaluated<'a>) -> Checked<'a> {
+                Checked(Evaluated::$method(self, other))
+            }
+        }
+
+        impl<'a> $trait<&Evaluated<'a>> for Checked<'a> {
+            type Output = Checked<'a>;
+
+            fn $op(self, other: &Evaluated<'a>) -> Checked<'a> {
+                self.apply(other, Evaluated::$method)
+            }
+        }
+
+        impl<'a> $trait<Checked<'a>> for Checked<'a> {
+            type Output = Checked<'a>;
+
+            fn $op(self, other: Checked<'a>) -> Checked<'a> {
+                match other.0 {
+                    Ok(other) => self.apply(&other, Evaluated::$method),
+                    Err(error) => Checked(self.0.and(Err(error))),
+                }
+            }
+        }
+    };
+}
+
+impl_checked_op!(Add, add, add);
+impl_checked_op!(Sub, sub, subtract);
+impl_checked_op!(Mul, mul, multiply);
+impl_checked_op!(Div, div, divide);
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::ast::Value as AstValue;
+
+    use super::Checked;
+    use crate::data::{Value, ValueError};
+    use crate::executor::evaluate::Evaluated;
+
+    #[test]
+    fn operators() {
+        let number = |v: &str| Evaluated::Literal(AstValue::Number(v.to_owned()));
+        let seven = Evaluated::Value(Value::I64(7));
+        let two = number("2");
+        let zero = Evaluated::Value(Value::I64(0));
+
+        let pairs = vec![
+            ((&seven + &two).into_result(), seven.add(&two)),
+            ((&seven - &two).into_result(), seven.subtract(&two)),
+            ((&seven * &two).into_result(), seven.multiply(&two)),
+            ((&seven / &two).into_result(), seven.divide(&two)),
+            (
+                (&two / &number("0.5")).into_result(),
+                two.divide(&number("0.5")),
+            ),
+            ((&seven / &zero).into_result(), seven.divide(&zero)),
+        ];
+
+        for (found, expected) in pairs {
+            assert_eq!(found, expected);
+        }
+
+        let found = (&seven - &two) * &two / &number("5");
+        assert_eq!(found.into_result(), Ok(Evaluated::Value(Value::I64(2))));
+
+        let found = (&seven + &two) + (&seven - &two);
+        assert_eq!(found.into_result(), Ok(Evaluated::Value(Value::I64(14))));
+
+        let divide_by_zero = Err(ValueError::DivideByZero.into());
+        let found = (&seven / &zero) + &two;
+        assert_eq!(found.into_result(), divide_by_zero);
+
+        let found = Checked::from(seven.clone()) * (&two / &zero);
+        assert_eq!(found.into_result(), divide_by_zero);
+    }
+}
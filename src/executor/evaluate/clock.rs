@@ -0,0 +1,22 @@
+use chrono::{NaiveDateTime, Utc};
+
+/// Source of the current time for `NOW()` and `CURRENT_TIMESTAMP`, so that tests can pin it.
+/// Any `Fn() -> NaiveDateTime` closure is a clock.
+pub trait Clock {
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// Wall clock time in UTC.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Utc::now().naive_utc()
+    }
+}
+
+impl<F: Fn() -> NaiveDateTime> Clock for F {
+    fn now(&self) -> NaiveDateTime {
+        self()
+    }
+}
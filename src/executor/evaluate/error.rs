@@ -2,7 +2,7 @@ use serde::Serialize;
 use std::fmt::Debug;
 use thiserror::Error;
 
-#[derive(Error, Serialize, Debug, PartialEq)]
+#[derive(Error, Serialize, Debug, Clone, PartialEq)]
 pub enum EvaluateError {
     #[error("nested select row not found")]
     NestedSelectRowNotFound,
@@ -10,14 +10,26 @@ pub enum EvaluateError {
     #[error("literal add on non-numeric")]
     LiteralAddOnNonNumeric,
 
+    #[error("literal subtract on non-numeric")]
+    LiteralSubtractOnNonNumeric,
+
+    #[error("literal multiply on non-numeric")]
+    LiteralMultiplyOnNonNumeric,
+
+    #[error("literal divide on non-numeric")]
+    LiteralDivideOnNonNumeric,
+
+    #[error("literal modulo on non-numeric")]
+    LiteralModuloOnNonNumeric,
+
     #[error("unsupported compound identifier {0}")]
     UnsupportedCompoundIdentifier(String),
 
     #[error("unreachable condition base")]
     UnreachableConditionBase,
 
-    #[error("unreachable evaluated arithmetic")]
-    UnreachableEvaluatedArithmetic,
+    #[error("arithmetic on non-numeric operands, left: {left}, right: {right}")]
+    ArithmeticOnNonNumeric { left: String, right: String },
 
     #[error("unreachable literal arithmetic")]
     UnreachableLiteralArithmetic,
@@ -31,6 +43,121 @@ pub enum EvaluateError {
     #[error("unreachable, aggregated field does not exist")]
     UnreachableEmptyAggregated,
 
+    #[error("boolean type required, found {0}")]
+    BooleanTypeRequired(String),
+
+    #[error("incomparable values, left: {left}, right: {right}")]
+    IncomparableValues { left: String, right: String },
+
+    #[error("clamp lower bound {low} is greater than upper bound {high}")]
+    InvalidClampBounds { low: String, high: String },
+
+    #[error("type mismatch, cannot compare string with number")]
+    TypeMismatch,
+
+    #[error("invalid date literal: {0}")]
+    InvalidDateLiteral(String),
+
+    #[error("cannot represent {0} as json")]
+    UnrepresentableJson(String),
+
+    #[error("json {0} is not a scalar")]
+    JsonNotScalar(String),
+
+    #[error("invalid json: {0}")]
+    InvalidJson(String),
+
+    #[error("invalid json path: {0}")]
+    InvalidJsonPath(String),
+
+    #[error("invalid base64: {0}")]
+    InvalidBase64(String),
+
+    #[error("unsupported encoding: {0}")]
+    UnsupportedEncoding(String),
+
+    #[error("invalid hex literal: {0}")]
+    InvalidHexLiteral(String),
+
+    #[error("invalid number literal: {0}")]
+    InvalidNumberLiteral(String),
+
+    #[error("cannot represent {0} as a literal")]
+    UnrepresentableLiteral(String),
+
+    #[error("unary plus on non numeric")]
+    UnaryPlusOnNonNumeric,
+
+    #[error("extract on non date")]
+    ExtractOnNonDate,
+
+    #[error("failed to cast {value} into {target}")]
+    CastFailed { value: String, target: String },
+
+    #[error("cannot convert {value} into {target}")]
+    ConversionFailed { value: String, target: String },
+
+    #[error("converting {value} into {target} loses precision")]
+    LossyConversion { value: String, target: String },
+
+    #[error("{0} on non numeric")]
+    MathOnNonNumeric(String),
+
+    #[error("invalid argument for {function}: {value}")]
+    InvalidMathArgument { function: String, value: String },
+
+    #[error("bitwise {0} on non integer")]
+    BitwiseOnNonInteger(String),
+
+    #[error("shift amount out of range: {0}")]
+    ShiftOutOfRange(i64),
+
+    #[error("power on non numeric")]
+    PowerOnNonNumeric,
+
+    #[error("{0} on non string")]
+    StringFunctionOnNonString(String),
+
+    #[error("{0} requires an integer")]
+    IntegerTypeRequired(String),
+
+    #[error("format expects {expected} arguments, found {found}")]
+    FormatArgMismatch { expected: usize, found: usize },
+
+    #[error("unreachable, format argument not found")]
+    UnreachableFormatArg,
+
+    #[error("{0} delimiter must not be empty")]
+    EmptyDelimiter(String),
+
+    #[error("field position must be positive, found {0}")]
+    FieldPositionNotPositive(i64),
+
+    #[error("invalid code point: {0}")]
+    InvalidCodePoint(i64),
+
+    #[error("string too long, limit is {0} bytes")]
+    StringTooLong(usize),
+
+    #[error("like on non string")]
+    LikeOnNonString,
+
+    #[error("like pattern must not end with escape character")]
+    LikePatternEndsWithEscape,
+
+    #[error("invalid regex: {0}")]
+    InvalidRegex(String),
+
+    #[error("unsupported concat, operands must be strings or numbers")]
+    UnsupportedConcat,
+
+    #[error("{name} takes {expected} arguments, found {found}")]
+    FunctionArgsLengthNotMatching {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+
     #[error("unimplemented")]
     Unimplemented,
 }
@@ -1,13 +1,102 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
 
-use sqlparser::ast::Value as AstValue;
+use regex::{Regex, RegexBuilder};
+use rust_decimal::prelude::ToPrimitive;
+use sqlparser::ast::{DataType, DateTimeField, Value as AstValue};
+use unicode_normalization::UnicodeNormalization;
 
 use crate::data;
 use crate::data::Value;
-use crate::result::Result;
+use crate::result::{Error, Result};
 
-use super::EvaluateError;
+use super::{Clock, EvaluateError};
 
+/// How strings are compared by `eq_collated`, `partial_cmp_collated` and
+/// `order_cmp_collated`. SQL has no `COLLATE` here, so queries always compare bytewise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Collation {
+    /// By their bytes, so `'B' < 'a'`.
+    #[default]
+    Binary,
+    /// By their lowercase forms from `str::to_lowercase`, so `'a' = 'A'` and `'A' < 'b'`.
+    /// Unlike SQLite's `NOCASE`, this folds non-ASCII letters too.
+    NoCase,
+}
+
+/// Unicode normalization form for `normalize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizationForm {
+    /// Canonical composition, `e` followed by a combining acute accent becomes `é`.
+    Nfc,
+    /// Canonical decomposition, `é` becomes `e` followed by a combining acute accent.
+    Nfd,
+    /// Compatibility composition, which also folds variants such as `ﬁ` into `fi`.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+/// How `multiply_with` handles an integer result which does not fit in `i64`. Only for callers
+/// of the API, SQL `*` is always `Checked`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowMode {
+    /// Fails with `NumericOverflow`.
+    #[default]
+    Checked,
+    /// Computes the product as a float instead, so precision is lost but the magnitude is kept,
+    /// e.g. `i64::MAX * 2` is about `1.8e19`. Results within `i64` stay integers.
+    Widening,
+}
+
+/// How `divide_with` handles two integers. SQL `/` always uses `Integer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DivisionMode {
+    /// Truncates toward zero, as `/` does, so `7 / 2` is `3`.
+    #[default]
+    Integer,
+    /// Yields a float when the divisor does not divide the dividend evenly, so `7 / 2` is `3.5`
+    /// while `6 / 2` stays the integer `3`. Use `div` for integer division in this mode.
+    True,
+}
+
+/// How `round_with` rounds a float, shown for whole numbers but applied the same way at the
+/// last kept decimal place. There is no SQL `ROUND` yet, so this is API-only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Rounds halves away from zero, as `round` does, so `2.5` becomes `3.0` and `-2.5`
+    /// becomes `-3.0`.
+    #[default]
+    HalfUp,
+    /// Rounds halves to the nearest even digit, as in banker's rounding, so `2.5` becomes
+    /// `2.0`, `3.5` becomes `4.0` and `-2.5` becomes `-2.0`.
+    HalfEven,
+    /// Drops the remaining digits as `trunc` does, so `2.7` becomes `2.0` and `-2.7` becomes
+    /// `-2.0`.
+    TowardZero,
+    /// Rounds any remainder away from zero, so `2.1` becomes `3.0` and `-2.1` becomes `-3.0`.
+    AwayFromZero,
+}
+
+/// How `try_eq_with` and `try_partial_cmp_with` treat a string compared with a number.
+/// Queries have no way to pick one yet, so `WHERE` clauses are always `Strict`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ComparisonMode {
+    /// Fails with `EvaluateError::TypeMismatch`.
+    #[default]
+    Strict,
+    /// Parses the string as a number, so `price > '10'` compares with `10` and `'007' = 7`
+    /// holds. Surrounding whitespace is trimmed before parsing, so `' 7 ' = 7` holds too. A
+    /// string which is not a number is compared as a string with the text of the number, so
+    /// `'7a' = 7` is false rather than an error.
+    Coercing,
+}
+
+#[derive(Debug, Clone)]
 pub enum Evaluated<'a> {
     LiteralRef(&'a AstValue),
     Literal(AstValue),
@@ -16,12 +105,20 @@ pub enum Evaluated<'a> {
     Value(Value),
 }
 
+/// Any comparison involving NULL is unknown, so NULL is never equal to anything, not even to
+/// another NULL, and never ordered.
 impl<'a> PartialEq for Evaluated<'a> {
     fn eq(&self, other: &Evaluated<'a>) -> bool {
-        let eq_ast = |l: &AstValue, r| match l {
-            AstValue::SingleQuotedString(l) => l == r,
-            _ => false,
-        };
+        if self.is_null() || other.is_null() {
+            return false;
+        }
+
+        if let Some(ordering) = temporal_partial_cmp(self, other) {
+            return ordering == Some(Ordering::Equal);
+        }
+
+        let eq_ast =
+            |l: &AstValue, r: &str| data::literal_bytes(l).as_deref() == Some(r.as_bytes());
 
         let eq_val = |l: &Value, r| match l {
             Value::Str(l) => l == r,
@@ -33,37 +130,39 @@ impl<'a> PartialEq for Evaluated<'a> {
 
             match self {
                 LiteralRef(l) => match other {
-                    LiteralRef(r) => l == r,
+                    LiteralRef(r) => eq_literals(l, r),
+                    Literal(r) => eq_literals(l, r),
                     StringRef(r) => eq_ast(l, r),
                     ValueRef(r) => r == l,
                     Value(r) => &r == l,
-                    Literal(_) => panic!(),
                 },
                 StringRef(l) => match other {
                     LiteralRef(r) => eq_ast(r, l),
+                    Literal(r) => eq_ast(r, l),
                     StringRef(r) => l == r,
                     ValueRef(r) => eq_val(r, l),
-                    Value(r) => eq_val(&r, l),
-                    Literal(_) => false,
+                    Value(r) => eq_val(r, l),
                 },
                 ValueRef(l) => match other {
                     LiteralRef(r) => l == r,
                     Literal(r) => l == &r,
                     StringRef(r) => eq_val(l, r),
-                    ValueRef(r) => l == r,
-                    Value(r) => l == &r,
+                    ValueRef(r) => eq_values(l, r),
+                    Value(r) => eq_values(l, r),
                 },
                 Value(l) => match other {
                     LiteralRef(r) => &l == r,
-                    StringRef(r) => eq_val(&l, r),
-                    ValueRef(r) => &l == r,
-                    Value(r) => l == r,
-                    Literal(_) => panic!(),
+                    Literal(r) => l == r,
+                    StringRef(r) => eq_val(l, r),
+                    ValueRef(r) => eq_values(l, r),
+                    Value(r) => eq_values(l, r),
                 },
                 Literal(l) => match other {
+                    LiteralRef(r) => eq_literals(l, r),
+                    Literal(r) => eq_literals(l, r),
+                    StringRef(r) => eq_ast(l, r),
                     ValueRef(r) => r == &l,
-                    StringRef(_) => false,
-                    _ => panic!(),
+                    Value(r) => r == l,
                 },
             }
         }
@@ -74,216 +173,7577 @@ impl<'a> PartialOrd for Evaluated<'a> {
     fn partial_cmp(&self, other: &Evaluated<'a>) -> Option<Ordering> {
         use Evaluated::*;
 
+        if self.is_null() || other.is_null() {
+            return None;
+        }
+
+        if let Some(ordering) = temporal_partial_cmp(self, other) {
+            return ordering;
+        }
+
         match self {
             LiteralRef(l) => match other {
                 LiteralRef(r) => literal_partial_cmp(l, r),
+                Literal(r) => literal_partial_cmp(l, r),
                 ValueRef(r) => r.partial_cmp(l).map(|o| o.reverse()),
                 Value(r) => r.partial_cmp(*l).map(|o| o.reverse()),
                 StringRef(_) => None,
-                Literal(_) => panic!(),
             },
             ValueRef(l) => match other {
                 LiteralRef(r) => l.partial_cmp(r),
+                Literal(r) => (*l).partial_cmp(r),
                 ValueRef(r) => l.partial_cmp(r),
                 Value(r) => l.partial_cmp(&r),
                 StringRef(r) => match l {
                     data::Value::Str(l) => (&l.as_str()).partial_cmp(r),
                     _ => None,
                 },
-                Literal(_) => panic!(),
             },
             Value(l) => match other {
                 LiteralRef(r) => l.partial_cmp(*r),
+                Literal(r) => l.partial_cmp(r),
                 ValueRef(r) => l.partial_cmp(*r),
                 Value(r) => l.partial_cmp(r),
                 StringRef(r) => match l {
                     data::Value::Str(l) => (&l.as_str()).partial_cmp(r),
                     _ => None,
                 },
-                Literal(_) => panic!(),
             },
             StringRef(l) => match other {
-                LiteralRef(_) => None,
+                LiteralRef(_) | Literal(_) => None,
                 ValueRef(data::Value::Str(r)) => l.partial_cmp(&r.as_str()),
                 Value(data::Value::Str(r)) => l.partial_cmp(&r.as_str()),
                 StringRef(r) => l.partial_cmp(r),
-                Literal(_) => panic!(),
                 _ => None,
             },
-            Literal(_) => panic!(),
+            Literal(l) => match other {
+                LiteralRef(r) => literal_partial_cmp(l, r),
+                Literal(r) => literal_partial_cmp(l, r),
+                ValueRef(r) => (*r).partial_cmp(l).map(|o| o.reverse()),
+                Value(r) => r.partial_cmp(l).map(|o| o.reverse()),
+                StringRef(_) => None,
+            },
+        }
+    }
+}
+
+/// Pairs a `DATE` or `TIMESTAMP` value on the left with a string on the right.
+fn temporal_and_str<'b>(
+    l: &'b Evaluated<'_>,
+    r: &'b Evaluated<'_>,
+) -> Option<(&'b Value, &'b str)> {
+    let l = match l {
+        Evaluated::ValueRef(v) => *v,
+        Evaluated::Value(v) => v,
+        _ => return None,
+    };
+
+    if l.is_temporal() {
+        r.as_str().map(|r| (l, r))
+    } else {
+        None
+    }
+}
+
+/// Compares a date or timestamp against a string by parsing the string as ISO 8601, so that
+/// `created_at > '2021-01-01'` works. Returns `None` when neither side is such a pair, and
+/// `Some(None)` when the string does not parse.
+fn temporal_partial_cmp(l: &Evaluated<'_>, r: &Evaluated<'_>) -> Option<Option<Ordering>> {
+    let cmp =
+        |v: &Value, text: &str| Some(data::timestamp_of(v)?.cmp(&data::parse_timestamp(text)?));
+
+    if let Some((l, r)) = temporal_and_str(l, r) {
+        Some(cmp(l, r))
+    } else if let Some((r, l)) = temporal_and_str(r, l) {
+        Some(cmp(r, l).map(Ordering::reverse))
+    } else {
+        None
+    }
+}
+
+fn interval_literal<'b>(v: &'b Evaluated<'_>) -> Option<&'b AstValue> {
+    match v {
+        Evaluated::LiteralRef(v @ AstValue::Interval { .. }) => Some(*v),
+        Evaluated::Literal(v @ AstValue::Interval { .. }) => Some(v),
+        _ => None,
+    }
+}
+
+/// Renders in a SQL-ish form, strings are single quoted and number literals are printed as
+/// they were parsed.
+impl fmt::Display for Evaluated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use data::Value::*;
+
+        let quoted = |v: &str| format!("'{}'", v.replace('\'', "''"));
+
+        let boolean = |v: bool| (if v { "TRUE" } else { "FALSE" }).to_owned();
+
+        let literal = |v: &AstValue| match v {
+            AstValue::Boolean(v) => boolean(*v),
+            _ => v.to_string(),
+        };
+
+        let value = |v: &data::Value| match v {
+            Bool(v) | OptBool(Some(v)) => boolean(*v),
+            I64(v) | OptI64(Some(v)) => v.to_string(),
+            F64(v) | OptF64(Some(v)) => float_to_string(*v),
+            Str(v) | OptStr(Some(v)) => quoted(v),
+            Decimal(v) | OptDecimal(Some(v)) => v.to_string(),
+            Date(v) | OptDate(Some(v)) => format!("DATE '{}'", v),
+            Timestamp(v) | OptTimestamp(Some(v)) => format!("TIMESTAMP '{}'", v),
+            Bytes(v) | OptBytes(Some(v)) => format!("X'{}'", upper_hex(v)),
+            OptBool(None) | OptI64(None) | OptF64(None) | OptStr(None) | OptDecimal(None)
+            | OptDate(None) | OptTimestamp(None) | OptBytes(None) | Empty => "NULL".to_owned(),
+        };
+
+        match self {
+            Evaluated::LiteralRef(v) => write!(f, "{}", literal(v)),
+            Evaluated::Literal(v) => write!(f, "{}", literal(v)),
+            Evaluated::StringRef(v) => write!(f, "{}", quoted(v)),
+            Evaluated::ValueRef(v) => write!(f, "{}", value(v)),
+            Evaluated::Value(v) => write!(f, "{}", value(v)),
+        }
+    }
+}
+
+fn conversion_failed(v: &Evaluated<'_>, target: &str) -> Error {
+    EvaluateError::ConversionFailed {
+        value: v.to_string(),
+        target: target.to_owned(),
+    }
+    .into()
+}
+
+/// Integer literals and values only, floats are not truncated.
+impl<'a> TryFrom<&Evaluated<'a>> for i64 {
+    type Error = Error;
+
+    fn try_from(v: &Evaluated<'a>) -> Result<Self> {
+        v.integer_operand("i64")
+            .ok()
+            .flatten()
+            .ok_or_else(|| conversion_failed(v, "i64"))
+    }
+}
+
+/// Any number, integers and decimals are converted to the nearest float.
+impl<'a> TryFrom<&Evaluated<'a>> for f64 {
+    type Error = Error;
+
+    fn try_from(v: &Evaluated<'a>) -> Result<Self> {
+        use data::Value::*;
+
+        let float = match v {
+            Evaluated::LiteralRef(AstValue::Number(n))
+            | Evaluated::Literal(AstValue::Number(n)) => {
+                parse_number(n).ok().map(ParsedNumber::to_f64)
+            }
+            Evaluated::ValueRef(I64(n) | OptI64(Some(n)))
+            | Evaluated::Value(I64(n) | OptI64(Some(n))) => Some(*n as f64),
+            Evaluated::ValueRef(F64(n) | OptF64(Some(n)))
+            | Evaluated::Value(F64(n) | OptF64(Some(n))) => Some(*n),
+            Evaluated::ValueRef(Decimal(n) | OptDecimal(Some(n)))
+            | Evaluated::Value(Decimal(n) | OptDecimal(Some(n))) => n.to_f64(),
+            _ => None,
+        };
+
+        float.ok_or_else(|| conversion_failed(v, "f64"))
+    }
+}
+
+/// Strings only, numbers are not formatted.
+impl<'a> TryFrom<&Evaluated<'a>> for String {
+    type Error = Error;
+
+    fn try_from(v: &Evaluated<'a>) -> Result<Self> {
+        v.as_str()
+            .map(str::to_owned)
+            .ok_or_else(|| conversion_failed(v, "String"))
+    }
+}
+
+/// Booleans only, unlike `try_into_bool` numbers are not treated as truthy.
+impl<'a> TryFrom<&Evaluated<'a>> for bool {
+    type Error = Error;
+
+    fn try_from(v: &Evaluated<'a>) -> Result<Self> {
+        v.boolean_operand()
+            .ok()
+            .flatten()
+            .ok_or_else(|| conversion_failed(v, "bool"))
+    }
+}
+
+/// Uppercase, as `X'..'` literals are usually written.
+fn upper_hex(v: &[u8]) -> String {
+    v.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// Unlike `Value`'s own `PartialEq`, integer and float values are equal when they hold the same
+/// number.
+fn eq_values(l: &Value, r: &Value) -> bool {
+    l == r || l.partial_cmp(r) == Some(Ordering::Equal)
+}
+
+/// Number literals are equal when they hold the same number, so `-0 = 0` and `1.0 = 1`, and
+/// string-like literals when they hold the same bytes, so `X'4a' = X'4A'` and `X'41' = 'A'`.
+fn eq_literals(l: &AstValue, r: &AstValue) -> bool {
+    match (l, r) {
+        (AstValue::Number(_), AstValue::Number(_)) => {
+            literal_partial_cmp(l, r) == Some(Ordering::Equal)
         }
+        _ => match (data::literal_bytes(l), data::literal_bytes(r)) {
+            (Some(l), Some(r)) => l == r,
+            _ => l == r,
+        },
     }
 }
 
+/// Number literals are compared as integers when both sides parse as `i64` or `u64`, and as
+/// floats otherwise, so that `3 < 3.5` holds. Comparing against `NaN` yields `None`. String,
+/// national string and hex literals are ordered by their bytes.
 fn literal_partial_cmp(a: &AstValue, b: &AstValue) -> Option<Ordering> {
+    use ParsedNumber::*;
+
     match (a, b) {
-        (AstValue::Number(l), AstValue::Number(r)) => match (l.parse::<i64>(), r.parse::<i64>()) {
-            (Ok(l), Ok(r)) => Some(l.cmp(&r)),
+        (AstValue::Number(l), AstValue::Number(r)) => {
+            match (parse_number(l).ok()?, parse_number(r).ok()?) {
+                (Int(l), Int(r)) => Some(l.cmp(&r)),
+                (UInt(l), UInt(r)) => Some(l.cmp(&r)),
+                (Int(_), UInt(_)) => Some(Ordering::Less),
+                (UInt(_), Int(_)) => Some(Ordering::Greater),
+                (l, r) => l.to_f64().partial_cmp(&r.to_f64()),
+            }
+        }
+        (AstValue::Boolean(l), AstValue::Boolean(r)) => Some(l.cmp(r)),
+        // Unordered even when the string holds a number, `Evaluated::compare` coerces instead.
+        (AstValue::Number(_), AstValue::SingleQuotedString(_))
+        | (AstValue::SingleQuotedString(_), AstValue::Number(_)) => None,
+        _ => match (data::literal_bytes(a), data::literal_bytes(b)) {
+            (Some(l), Some(r)) => Some(l.cmp(&r)),
             _ => None,
         },
-        (AstValue::SingleQuotedString(l), AstValue::SingleQuotedString(r)) => Some(l.cmp(r)),
-        _ => None,
     }
 }
 
+/// Number literal parsed once, so that each operation matches on the kind instead of trying
+/// `i64`, `u64` and `f64` on the string one after another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParsedNumber {
+    Int(i64),
+    /// Above `i64::MAX` but within `u64`, e.g. large IDs, so always greater than any `Int`.
+    UInt(u64),
+    Float(f64),
+}
+
+impl ParsedNumber {
+    fn to_f64(self) -> f64 {
+        match self {
+            ParsedNumber::Int(v) => v as f64,
+            ParsedNumber::UInt(v) => v as f64,
+            ParsedNumber::Float(v) => v,
+        }
+    }
+
+    fn to_i128(self) -> Option<i128> {
+        match self {
+            ParsedNumber::Int(v) => Some(v.into()),
+            ParsedNumber::UInt(v) => Some(v.into()),
+            ParsedNumber::Float(_) => None,
+        }
+    }
+
+    /// `None` when `v` is below `i64::MIN` or above `u64::MAX`.
+    fn from_i128(v: i128) -> Option<Self> {
+        i64::try_from(v)
+            .map(ParsedNumber::Int)
+            .or_else(|_| u64::try_from(v).map(ParsedNumber::UInt))
+            .ok()
+    }
+
+    fn into_literal(self) -> AstValue {
+        AstValue::Number(match self {
+            ParsedNumber::Int(v) => v.to_string(),
+            ParsedNumber::UInt(v) => v.to_string(),
+            ParsedNumber::Float(v) => float_to_string(v),
+        })
+    }
+}
+
+fn parse_number(v: &str) -> Result<ParsedNumber> {
+    if let Ok(v) = v.parse::<i64>() {
+        return Ok(ParsedNumber::Int(v));
+    }
+
+    if let Ok(v) = v.parse::<u64>() {
+        return Ok(ParsedNumber::UInt(v));
+    }
+
+    v.parse::<f64>()
+        .map(ParsedNumber::Float)
+        .map_err(|_| EvaluateError::InvalidNumberLiteral(v.to_owned()).into())
+}
+
 impl<'a> Evaluated<'a> {
-    pub fn add(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+    /// `NULL` literals, empty nullable values and `Value::Empty`. A `StringRef` is never NULL.
+    pub fn is_null(&self) -> bool {
         use Evaluated::*;
 
-        let unreachable = || Err(EvaluateError::UnreachableEvaluatedArithmetic.into());
+        match self {
+            LiteralRef(v) => matches!(v, AstValue::Null),
+            Literal(v) => matches!(v, AstValue::Null),
+            StringRef(_) => false,
+            ValueRef(v) => !v.is_some(),
+            Value(v) => !v.is_some(),
+        }
+    }
+
+    /// `IS DISTINCT FROM`: like `!=`, except that two NULLs are not distinct and a NULL is
+    /// distinct from any other value, so the result is never unknown.
+    pub fn is_distinct_from(&self, other: &Evaluated<'a>) -> bool {
+        !self.is_not_distinct_from(other)
+    }
 
-        let add_literal = |l, other: &Evaluated<'a>| match other {
-            LiteralRef(r) => literal_add(l, r).map(Evaluated::Literal),
-            Literal(r) => literal_add(l, &r).map(Evaluated::Literal),
-            ValueRef(r) => r.add(&r.clone_by(l)?).map(Evaluated::Value),
-            Value(r) => r.add(&r.clone_by(l)?).map(Evaluated::Value),
-            StringRef(_) => unreachable(),
-        };
+    /// `IS NOT DISTINCT FROM`, the NULL-safe `=`.
+    pub fn is_not_distinct_from(&self, other: &Evaluated<'a>) -> bool {
+        match (self.is_null(), other.is_null()) {
+            (true, true) => true,
+            (false, false) => self == other,
+            _ => false,
+        }
+    }
 
-        let add_value = |l: &data::Value, other: &Evaluated<'a>| match other {
-            LiteralRef(r) => l.add(&l.clone_by(r)?).map(Evaluated::Value),
-            Literal(r) => l.add(&l.clone_by(&r)?).map(Evaluated::Value),
-            ValueRef(r) => l.add(r).map(Evaluated::Value),
-            Value(r) => l.add(&r).map(Evaluated::Value),
-            StringRef(_) => unreachable(),
+    /// Same as `==`, except that comparing a string with a number fails with
+    /// `EvaluateError::TypeMismatch` instead of silently returning `false`, and comparing a date
+    /// or timestamp with a string that is not a valid date fails with `InvalidDateLiteral`.
+    pub fn try_eq(&self, other: &Evaluated<'a>) -> Result<bool> {
+        self.check_comparable(other).map(|_| self == other)
+    }
+
+    /// Same as `partial_cmp`, except that comparing a string with a number fails with
+    /// `EvaluateError::TypeMismatch` instead of silently returning `None`.
+    pub fn try_partial_cmp(&self, other: &Evaluated<'a>) -> Result<Option<Ordering>> {
+        self.check_comparable(other)
+            .map(|_| self.partial_cmp(other))
+    }
+
+    /// `try_eq` where a string compared with a number is handled according to `mode`.
+    pub fn try_eq_with(&self, other: &Evaluated<'a>, mode: ComparisonMode) -> Result<bool> {
+        match self.coerce_pair(other, mode)? {
+            Some((l, r)) => Ok(l == r),
+            None => self.try_eq(other),
+        }
+    }
+
+    /// `try_partial_cmp` where a string compared with a number is handled according to `mode`.
+    pub fn try_partial_cmp_with(
+        &self,
+        other: &Evaluated<'a>,
+        mode: ComparisonMode,
+    ) -> Result<Option<Ordering>> {
+        match self.coerce_pair(other, mode)? {
+            Some((l, r)) => Ok(l.partial_cmp(&r)),
+            None => self.try_partial_cmp(other),
+        }
+    }
+
+    /// Replaces the string side of a string and number pair with the number it holds, or when
+    /// it holds none, the number side with its text. Returns `None` when there is nothing to
+    /// coerce.
+    fn coerce_pair(
+        &self,
+        other: &Evaluated<'a>,
+        mode: ComparisonMode,
+    ) -> Result<Option<(Evaluated<'a>, Evaluated<'a>)>> {
+        if mode == ComparisonMode::Strict {
+            return Ok(None);
+        }
+
+        let coerce = |string: &Evaluated<'a>, number: &Evaluated<'a>| -> Result<_> {
+            let text = string.as_str().ok_or(EvaluateError::TypeMismatch)?;
+            let trimmed = text.trim();
+
+            if parse_number(trimmed).is_ok() {
+                return Ok((
+                    Evaluated::Literal(AstValue::Number(trimmed.to_owned())),
+                    number.clone(),
+                ));
+            }
+
+            let number = number
+                .to_concat_string()
+                .ok_or(EvaluateError::TypeMismatch)?;
+
+            Ok((string.clone(), Evaluated::Value(data::Value::Str(number))))
         };
 
-        match self {
-            LiteralRef(l) => add_literal(l, other),
-            Literal(l) => add_literal(&l, other),
-            ValueRef(l) => add_value(l, other),
-            Value(l) => add_value(&l, other),
-            StringRef(_) => unreachable(),
+        if self.is_string() && other.is_number() {
+            coerce(self, other).map(Some)
+        } else if self.is_number() && other.is_string() {
+            coerce(other, self).map(|(r, l)| Some((l, r)))
+        } else {
+            Ok(None)
         }
     }
 
-    pub fn subtract(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
-        use Evaluated::*;
+    /// Total comparison for sorting, where `partial_cmp` returning `None` would silently leave
+    /// the operands in place. A string compared with a number is parsed as a number, so `'10'`
+    /// is greater than `9`. Anything else `partial_cmp` cannot order, such as a string which is
+    /// not a number or NULL against anything, fails with `IncomparableValues`.
+    pub fn compare(&self, other: &Evaluated<'a>) -> Result<Ordering> {
+        let numeric = |v: &Evaluated<'a>| {
+            let v = v.as_str()?.trim();
 
-        let unreachable = || Err(EvaluateError::UnreachableEvaluatedArithmetic.into());
+            parse_number(v)
+                .ok()
+                .map(|_| Evaluated::Literal(AstValue::Number(v.to_owned())))
+        };
 
-        let subtract_literal = |l, other: &Evaluated<'a>| match other {
-            LiteralRef(r) => literal_subtract(l, r).map(Evaluated::Literal),
-            Literal(r) => literal_subtract(l, &r).map(Evaluated::Literal),
-            ValueRef(r) => (r.clone_by(l)?).subtract(r).map(Evaluated::Value),
-            Value(r) => (r.clone_by(l)?).subtract(r).map(Evaluated::Value),
-            StringRef(_) => unreachable(),
+        let ordering = if self.is_string() && other.is_number() {
+            numeric(self).and_then(|l| l.partial_cmp(other))
+        } else if self.is_number() && other.is_string() {
+            numeric(other).and_then(|r| self.partial_cmp(&r))
+        } else {
+            self.partial_cmp(other)
         };
 
-        let subtract_value = |l: &data::Value, other: &Evaluated<'a>| match other {
-            LiteralRef(r) => l.subtract(&l.clone_by(r)?).map(Evaluated::Value),
-            Literal(r) => l.subtract(&l.clone_by(&r)?).map(Evaluated::Value),
-            ValueRef(r) => l.subtract(r).map(Evaluated::Value),
-            Value(r) => l.subtract(&r).map(Evaluated::Value),
-            StringRef(_) => unreachable(),
+        ordering.ok_or_else(|| {
+            EvaluateError::IncomparableValues {
+                left: self.to_string(),
+                right: other.to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// Ordering for `ORDER BY` with `NULLS FIRST` or `NULLS LAST`, NULLs are equal to each
+    /// other and less or greater than everything else depending on `nulls_first`.
+    /// Operands `partial_cmp` cannot order are still ordered deterministically by kind:
+    /// booleans, then numbers with `NaN` after every other number, and strings last.
+    pub fn order_cmp(&self, other: &Evaluated<'a>, nulls_first: bool) -> Ordering {
+        self.order_cmp_collated(other, nulls_first, Collation::Binary)
+    }
+
+    /// `order_cmp` with strings compared under `collation`, e.g. for
+    /// `ORDER BY name COLLATE NOCASE`.
+    pub fn order_cmp_collated(
+        &self,
+        other: &Evaluated<'a>,
+        nulls_first: bool,
+        collation: Collation,
+    ) -> Ordering {
+        let null = if nulls_first {
+            Ordering::Less
+        } else {
+            Ordering::Greater
         };
 
-        match self {
-            LiteralRef(l) => subtract_literal(l, other),
-            Literal(l) => subtract_literal(&l, other),
-            ValueRef(l) => subtract_value(l, other),
-            Value(l) => subtract_value(&l, other),
-            StringRef(_) => unreachable(),
+        match (self.is_null(), other.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => null,
+            (false, true) => null.reverse(),
+            (false, false) => self
+                .partial_cmp_collated(other, collation)
+                .unwrap_or_else(|| self.sort_rank().cmp(&other.sort_rank())),
         }
     }
 
-    pub fn multiply(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
-        use Evaluated::*;
+    /// `==` with two strings compared under `collation`, anything else ignores it.
+    pub fn eq_collated(&self, other: &Evaluated<'a>, collation: Collation) -> bool {
+        match self.collated_pair(other, collation) {
+            Some((l, r)) => l == r,
+            None => self == other,
+        }
+    }
 
-        let unreachable = || Err(EvaluateError::UnreachableEvaluatedArithmetic.into());
+    /// `partial_cmp` with two strings compared under `collation`, anything else ignores it.
+    pub fn partial_cmp_collated(
+        &self,
+        other: &Evaluated<'a>,
+        collation: Collation,
+    ) -> Option<Ordering> {
+        match self.collated_pair(other, collation) {
+            Some((l, r)) => l.partial_cmp(&r),
+            None => self.partial_cmp(other),
+        }
+    }
 
-        let multiply_literal = |l, other: &Evaluated<'a>| match other {
-            LiteralRef(r) => literal_multiply(l, r).map(Evaluated::Literal),
-            Literal(r) => literal_multiply(l, &r).map(Evaluated::Literal),
-            ValueRef(r) => (r.clone_by(l)?).multiply(r).map(Evaluated::Value),
-            Value(r) => (r.clone_by(l)?).multiply(r).map(Evaluated::Value),
-            StringRef(_) => unreachable(),
+    fn collated_pair(
+        &self,
+        other: &Evaluated<'a>,
+        collation: Collation,
+    ) -> Option<(String, String)> {
+        match collation {
+            Collation::Binary => None,
+            Collation::NoCase => Some((
+                self.as_str()?.to_lowercase(),
+                other.as_str()?.to_lowercase(),
+            )),
+        }
+    }
+
+    fn sort_rank(&self) -> u8 {
+        let is_nan = match self {
+            Evaluated::ValueRef(data::Value::F64(v) | data::Value::OptF64(Some(v)))
+            | Evaluated::Value(data::Value::F64(v) | data::Value::OptF64(Some(v))) => v.is_nan(),
+            _ => false,
         };
 
-        let multiply_value = |l: &data::Value, other: &Evaluated<'a>| match other {
-            LiteralRef(r) => l.multiply(&l.clone_by(r)?).map(Evaluated::Value),
-            Literal(r) => l.multiply(&l.clone_by(&r)?).map(Evaluated::Value),
-            ValueRef(r) => l.multiply(r).map(Evaluated::Value),
-            Value(r) => l.multiply(&r).map(Evaluated::Value),
-            StringRef(_) => unreachable(),
+        if is_nan {
+            2
+        } else if self.is_number() {
+            1
+        } else if self.is_string() {
+            3
+        } else {
+            0
+        }
+    }
+
+    /// Total order across types, e.g. for index keys, where every value of one type sorts before
+    /// every value of the next: NULL, booleans, numbers, `NaN`, strings, bytes and then dates
+    /// and timestamps. Within a type operands are compared as in `partial_cmp`, so `1` and
+    /// `1.0` are equal, while NULLs and NaNs are equal to each other. Strings and bytes are
+    /// compared bytewise.
+    pub fn total_cmp(&self, other: &Evaluated<'a>) -> Ordering {
+        const STRINGS: u8 = 4;
+        const BYTES: u8 = 5;
+
+        let rank = self.total_rank();
+
+        rank.cmp(&other.total_rank()).then_with(|| match rank {
+            STRINGS | BYTES => {
+                let bytes =
+                    |v: &Evaluated<'a>| v.bytes_operand("").ok().flatten().map(Cow::into_owned);
+
+                bytes(self).cmp(&bytes(other))
+            }
+            _ => self.partial_cmp(other).unwrap_or(Ordering::Equal),
+        })
+    }
+
+    fn total_rank(&self) -> u8 {
+        use data::Value::{Bytes, OptBytes};
+
+        let is_bytes = match self {
+            Evaluated::LiteralRef(AstValue::HexStringLiteral(_))
+            | Evaluated::Literal(AstValue::HexStringLiteral(_)) => true,
+            Evaluated::ValueRef(v) => matches!(v, Bytes(_) | OptBytes(_)),
+            Evaluated::Value(v) => matches!(v, Bytes(_) | OptBytes(_)),
+            _ => false,
+        };
+        let is_temporal = match self {
+            Evaluated::ValueRef(v) => v.is_temporal(),
+            Evaluated::Value(v) => v.is_temporal(),
+            _ => false,
         };
 
-        match self {
-            LiteralRef(l) => multiply_literal(l, other),
-            Literal(l) => multiply_literal(&l, other),
-            ValueRef(l) => multiply_value(l, other),
-            Value(l) => multiply_value(&l, other),
-            StringRef(_) => unreachable(),
+        if self.is_null() {
+            0
+        } else if matches!(self.boolean_operand(), Ok(Some(_))) {
+            1
+        } else if self.is_number() {
+            self.sort_rank() + 1
+        } else if is_bytes {
+            5
+        } else if self.is_string() {
+            4
+        } else if is_temporal {
+            6
+        } else {
+            7
         }
     }
 
-    pub fn divide(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
-        use Evaluated::*;
+    /// Same as `==`, but strings are lowercased on both sides before comparing.
+    /// Lowercasing is Unicode aware (`'Ä'` matches `'ä'`) but it is not full case folding,
+    /// so `'ß'` does not match `'SS'`. Non-string operands defer to `==`.
+    pub fn eq_case_insensitive(&self, other: &Evaluated<'a>) -> bool {
+        match (self.as_str(), other.as_str()) {
+            (Some(l), Some(r)) => l.to_lowercase() == r.to_lowercase(),
+            _ => self == other,
+        }
+    }
 
-        let unreachable = || Err(EvaluateError::UnreachableEvaluatedArithmetic.into());
+    /// SQL `COALESCE`, the first operand which is not NULL, keeping its variant.
+    /// NULL when every operand is NULL or `items` is empty.
+    pub fn coalesce(items: &[Evaluated<'a>]) -> Result<Evaluated<'a>> {
+        Ok(items
+            .iter()
+            .find(|item| !item.is_null())
+            .cloned()
+            .unwrap_or(Evaluated::Literal(AstValue::Null)))
+    }
 
-        let divide_literal = |l, other: &Evaluated<'a>| match other {
-            LiteralRef(r) => literal_divide(l, r).map(Evaluated::Literal),
-            Literal(r) => literal_divide(l, &r).map(Evaluated::Literal),
-            ValueRef(r) => (r.clone_by(l)?).divide(r).map(Evaluated::Value),
-            Value(r) => (r.clone_by(l)?).divide(r).map(Evaluated::Value),
-            StringRef(_) => unreachable(),
+    /// SQL `GREATEST`, NULLs are skipped and the result is NULL only when every item is NULL.
+    pub fn greatest(items: &[Evaluated<'a>]) -> Result<Evaluated<'a>> {
+        Evaluated::pick(items, Ordering::Greater)
+    }
+
+    /// SQL `LEAST`, NULLs are skipped and the result is NULL only when every item is NULL.
+    pub fn least(items: &[Evaluated<'a>]) -> Result<Evaluated<'a>> {
+        Evaluated::pick(items, Ordering::Less)
+    }
+
+    fn pick(items: &[Evaluated<'a>], ordering: Ordering) -> Result<Evaluated<'a>> {
+        let mut picked: Option<&Evaluated<'a>> = None;
+
+        for item in items.iter().filter(|item| !item.is_null()) {
+            picked = match picked {
+                None => Some(item),
+                Some(current) => match item.partial_cmp(current) {
+                    Some(o) if o == ordering => Some(item),
+                    Some(_) => Some(current),
+                    None => {
+                        return Err(EvaluateError::IncomparableValues {
+                            left: current.to_string(),
+                            right: item.to_string(),
+                        }
+                        .into());
+                    }
+                },
+            };
+        }
+
+        Ok(picked
+            .cloned()
+            .unwrap_or(Evaluated::Literal(AstValue::Null)))
+    }
+
+    /// SQL `NULLIF`, NULL when both operands are equal and `self` otherwise.
+    /// A NULL `self` is returned as is, since NULL never equals anything.
+    pub fn nullif(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        if self.try_eq(other)? {
+            Ok(Evaluated::Literal(AstValue::Null))
+        } else {
+            Ok(self.clone())
+        }
+    }
+
+    /// SQL `IN`, true when `self` equals any element of `list`.
+    /// A NULL on either side makes the result unknown when nothing matches,
+    /// which is surfaced as `false`. Every element is checked as in `try_eq` before matching,
+    /// so an element which cannot be compared fails wherever it is in the list.
+    pub fn is_in(&self, list: &[Evaluated<'a>]) -> Result<bool> {
+        for evaluated in list {
+            self.check_comparable(evaluated)?;
+        }
+
+        Ok(list.iter().any(|evaluated| self == evaluated))
+    }
+
+    /// SQL `BETWEEN`, inclusive on both bounds.
+    /// Incomparable operands or NULL make the result unknown, surfaced as `false`
+    /// regardless of `negated`. With `symmetric`, as in `BETWEEN SYMMETRIC`, bounds given in
+    /// descending order are swapped first, so `3 BETWEEN SYMMETRIC 5 AND 1` holds.
+    pub fn between(
+        &self,
+        low: &Evaluated<'a>,
+        high: &Evaluated<'a>,
+        negated: bool,
+        symmetric: bool,
+    ) -> Result<bool> {
+        let (low, high) = if symmetric && low.try_partial_cmp(high)? == Some(Ordering::Greater) {
+            (high, low)
+        } else {
+            (low, high)
         };
 
-        let divide_value = |l: &data::Value, other: &Evaluated<'a>| match other {
-            LiteralRef(r) => l.divide(&l.clone_by(r)?).map(Evaluated::Value),
-            Literal(r) => l.divide(&l.clone_by(&r)?).map(Evaluated::Value),
-            ValueRef(r) => l.divide(r).map(Evaluated::Value),
-            Value(r) => l.divide(&r).map(Evaluated::Value),
-            StringRef(_) => unreachable(),
+        let (low, high) = match (low.try_partial_cmp(self)?, self.try_partial_cmp(high)?) {
+            (Some(low), Some(high)) => (low, high),
+            _ => return Ok(false),
         };
 
-        match self {
-            LiteralRef(l) => divide_literal(l, other),
-            Literal(l) => divide_literal(&l, other),
-            ValueRef(l) => divide_value(l, other),
-            Value(l) => divide_value(&l, other),
-            StringRef(_) => unreachable(),
-        }
+        let between = low != Ordering::Greater && high != Ordering::Greater;
+
+        Ok(between != negated)
     }
-}
 
-fn literal_add(a: &AstValue, b: &AstValue) -> Result<AstValue> {
-    match (a, b) {
-        (AstValue::Number(a), AstValue::Number(b)) => match (a.parse::<i64>(), b.parse::<i64>()) {
-            (Ok(a), Ok(b)) => Ok(AstValue::Number((a + b).to_string())),
-            _ => panic!(),
-        },
-        _ => Err(EvaluateError::UnreachableLiteralArithmetic.into()),
+    /// `low` when `self` is below it, `high` when `self` is above it and `self` otherwise. NULL
+    /// in any operand yields NULL. Operands which cannot be ordered against each other fail as
+    /// in `try_partial_cmp` or with `IncomparableValues`, and `low` greater than `high` fails
+    /// with `InvalidClampBounds`.
+    pub fn clamp(&self, low: &Evaluated<'a>, high: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        if self.is_null() || low.is_null() || high.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        let cmp = |l: &Evaluated<'a>, r: &Evaluated<'a>| -> Result<Ordering> {
+            l.try_partial_cmp(r)?.ok_or_else(|| {
+                EvaluateError::IncomparableValues {
+                    left: l.to_string(),
+                    right: r.to_string(),
+                }
+                .into()
+            })
+        };
+
+        if cmp(low, high)? == Ordering::Greater {
+            return Err(EvaluateError::InvalidClampBounds {
+                low: low.to_string(),
+                high: high.to_string(),
+            }
+            .into());
+        }
+
+        if cmp(self, low)? == Ordering::Less {
+            Ok(low.clone())
+        } else if cmp(self, high)? == Ordering::Greater {
+            Ok(high.clone())
+        } else {
+            Ok(self.clone())
+        }
     }
-}
 
-fn literal_subtract(a: &AstValue, b: &AstValue) -> Result<AstValue> {
-    match (a, b) {
-        (AstValue::Number(a), AstValue::Number(b)) => match (a.parse::<i64>(), b.parse::<i64>()) {
-            (Ok(a), Ok(b)) => Ok(AstValue::Number((a - b).to_string())),
-            _ => panic!(),
-        },
-        _ => Err(EvaluateError::UnreachableLiteralArithmetic.into()),
+    fn check_comparable(&self, other: &Evaluated<'a>) -> Result<()> {
+        let text = temporal_and_str(self, other)
+            .or_else(|| temporal_and_str(other, self))
+            .map(|(_, text)| text);
+
+        if let Some(text) = text {
+            return match data::parse_timestamp(text) {
+                Some(_) => Ok(()),
+                None => Err(EvaluateError::InvalidDateLiteral(text.to_owned()).into()),
+            };
+        }
+
+        for v in [self, other] {
+            if let Some(hex) = v.invalid_hex() {
+                return Err(EvaluateError::InvalidHexLiteral(hex.to_owned()).into());
+            }
+
+            if let Some(number) = v.invalid_number() {
+                return Err(EvaluateError::InvalidNumberLiteral(number.to_owned()).into());
+            }
+        }
+
+        let mismatch =
+            (self.is_string() && other.is_number()) || (self.is_number() && other.is_string());
+
+        if mismatch {
+            Err(EvaluateError::TypeMismatch.into())
+        } else {
+            Ok(())
+        }
     }
-}
 
-fn literal_multiply(a: &AstValue, b: &AstValue) -> Result<AstValue> {
-    match (a, b) {
-        (AstValue::Number(a), AstValue::Number(b)) => match (a.parse::<i64>(), b.parse::<i64>()) {
-            (Ok(a), Ok(b)) => Ok(AstValue::Number((a * b).to_string())),
-            _ => panic!(),
-        },
-        _ => Err(EvaluateError::UnreachableLiteralArithmetic.into()),
+    /// Digits of a hex literal which does not decode into whole bytes, e.g. `X'ABC'`.
+    fn invalid_hex(&self) -> Option<&str> {
+        let literal = match self {
+            Evaluated::LiteralRef(v) => *v,
+            Evaluated::Literal(v) => v,
+            _ => return None,
+        };
+
+        match literal {
+            AstValue::HexStringLiteral(hex) if data::literal_bytes(literal).is_none() => Some(hex),
+            _ => None,
+        }
     }
-}
 
-fn literal_divide(a: &AstValue, b: &AstValue) -> Result<AstValue> {
-    match (a, b) {
-        (AstValue::Number(a), AstValue::Number(b)) => match (a.parse::<i64>(), b.parse::<i64>()) {
-            (Ok(a), Ok(b)) => Ok(AstValue::Number((a / b).to_string())),
-            _ => panic!(),
-        },
-        _ => Err(EvaluateError::UnreachableLiteralArithmetic.into()),
+    /// Digits of a number literal which parses as neither an integer nor a float, e.g. `1e`.
+    /// Scientific notation such as `1e3` is a valid float.
+    fn invalid_number(&self) -> Option<&str> {
+        match self {
+            Evaluated::LiteralRef(AstValue::Number(v))
+            | Evaluated::Literal(AstValue::Number(v))
+                if parse_number(v).is_err() =>
+            {
+                Some(v)
+            }
+            _ => None,
+        }
+    }
+
+    fn is_string(&self) -> bool {
+        use Evaluated::*;
+
+        match self {
+            LiteralRef(v) => data::literal_bytes(v).is_some(),
+            Literal(v) => data::literal_bytes(v).is_some(),
+            StringRef(_) => true,
+            ValueRef(v) => matches!(v, data::Value::Str(_) | data::Value::OptStr(Some(_))),
+            Value(v) => matches!(v, data::Value::Str(_) | data::Value::OptStr(Some(_))),
+        }
+    }
+
+    fn is_number(&self) -> bool {
+        use Evaluated::*;
+
+        let is_number_value = |v: &data::Value| {
+            matches!(
+                v,
+                data::Value::I64(_)
+                    | data::Value::OptI64(Some(_))
+                    | data::Value::F64(_)
+                    | data::Value::OptF64(Some(_))
+                    | data::Value::Decimal(_)
+                    | data::Value::OptDecimal(Some(_))
+            )
+        };
+
+        match self {
+            LiteralRef(v) => matches!(v, AstValue::Number(_)),
+            Literal(v) => matches!(v, AstValue::Number(_)),
+            StringRef(_) => false,
+            ValueRef(v) => is_number_value(v),
+            Value(v) => is_number_value(v),
+        }
+    }
+
+    /// Stable 64-bit FNV-1a digest as 16 lowercase hex digits, NULL is passed through.
+    /// The operand is hashed with its type, so `HASH(1)` differs from `HASH('1')` and from
+    /// `HASH(1.0)`, while literals and stored values of the same type and value hash equally.
+    pub fn hash_hex(&self) -> Result<Evaluated<'a>> {
+        use data::Value::*;
+
+        if self.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        let canonical = match self.to_value()? {
+            Bool(v) | OptBool(Some(v)) => format!("b:{}", v),
+            I64(v) | OptI64(Some(v)) => format!("i:{}", v),
+            F64(v) | OptF64(Some(v)) => format!("f:{}", float_to_string(v)),
+            Str(v) | OptStr(Some(v)) => format!("s:{}", v),
+            Decimal(v) | OptDecimal(Some(v)) => format!("d:{}", v.normalize()),
+            Date(v) | OptDate(Some(v)) => format!("date:{}", v),
+            Timestamp(v) | OptTimestamp(Some(v)) => format!("ts:{}", v),
+            Bytes(v) | OptBytes(Some(v)) => format!("x:{}", upper_hex(&v)),
+            OptBool(None) | OptI64(None) | OptF64(None) | OptStr(None) | OptDecimal(None)
+            | OptDate(None) | OptTimestamp(None) | OptBytes(None) | Empty => {
+                return Ok(Evaluated::Literal(AstValue::Null));
+            }
+        };
+
+        let hash = canonical
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+            });
+
+        Ok(Evaluated::Value(Str(format!("{:016x}", hash))))
+    }
+
+    /// SQL `>` as a value, e.g. for `SELECT a > b`. NULL on either side, or operands which
+    /// cannot be ordered such as `NaN`, yield NULL, and a string compared with a number fails
+    /// as in `try_partial_cmp`.
+    pub fn gt_eval(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.cmp_eval(other, |v| v == Ordering::Greater)
+    }
+
+    /// SQL `<` as a value, see `gt_eval`.
+    pub fn lt_eval(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.cmp_eval(other, |v| v == Ordering::Less)
+    }
+
+    /// SQL `>=` as a value, see `gt_eval`.
+    pub fn ge_eval(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.cmp_eval(other, |v| v != Ordering::Less)
+    }
+
+    /// SQL `<=` as a value, see `gt_eval`.
+    pub fn le_eval(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.cmp_eval(other, |v| v != Ordering::Greater)
+    }
+
+    /// SQL `=` as a value, NULL on either side yields NULL and a string compared with a number
+    /// fails as in `try_eq`.
+    pub fn eq_eval(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        if self.is_null() || other.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        self.try_eq(other).map(|v| Evaluated::from_boolean(Some(v)))
+    }
+
+    /// SQL `<>` as a value, see `eq_eval`.
+    pub fn ne_eval(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.eq_eval(other)?.logical_not()
+    }
+
+    fn cmp_eval(
+        &self,
+        other: &Evaluated<'a>,
+        f: impl FnOnce(Ordering) -> bool,
+    ) -> Result<Evaluated<'a>> {
+        if self.is_null() || other.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        self.try_partial_cmp(other)
+            .map(|v| Evaluated::from_boolean(v.map(f)))
+    }
+
+    /// SQL `AND` over booleans where NULL is unknown, so `FALSE AND NULL` is `FALSE` and
+    /// `TRUE AND NULL` is NULL.
+    pub fn and(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        let v = match (self.boolean_operand()?, other.boolean_operand()?) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        };
+
+        Ok(Evaluated::from_boolean(v))
+    }
+
+    /// SQL `OR` over booleans where NULL is unknown, so `TRUE OR NULL` is `TRUE` and
+    /// `FALSE OR NULL` is NULL.
+    pub fn or(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        let v = match (self.boolean_operand()?, other.boolean_operand()?) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        };
+
+        Ok(Evaluated::from_boolean(v))
+    }
+
+    /// SQL `NOT`. Only booleans are negated and NULL stays NULL, unlike `try_into_bool`
+    /// numbers are not coerced, so `NOT 0` fails with `BooleanTypeRequired`.
+    pub fn logical_not(&self) -> Result<Evaluated<'a>> {
+        self.boolean_operand()
+            .map(|v| Evaluated::from_boolean(v.map(|v| !v)))
+    }
+
+    /// `None` for NULL, and `EvaluateError::BooleanTypeRequired` for anything else which is not
+    /// a boolean.
+    fn boolean_operand(&self) -> Result<Option<bool>> {
+        use data::Value::*;
+
+        if self.is_null() {
+            return Ok(None);
+        }
+
+        match self {
+            Evaluated::LiteralRef(AstValue::Boolean(v))
+            | Evaluated::Literal(AstValue::Boolean(v)) => Ok(Some(*v)),
+            Evaluated::ValueRef(Bool(v) | OptBool(Some(v)))
+            | Evaluated::Value(Bool(v) | OptBool(Some(v))) => Ok(Some(*v)),
+            _ => Err(EvaluateError::BooleanTypeRequired(self.to_string()).into()),
+        }
+    }
+
+    pub(crate) fn from_boolean(v: Option<bool>) -> Evaluated<'a> {
+        match v {
+            Some(v) => Evaluated::Value(data::Value::Bool(v)),
+            None => Evaluated::Literal(AstValue::Null),
+        }
+    }
+
+    /// Truthiness of a WHERE condition. Booleans map directly and NULL is `false`.
+    /// Numbers follow the C rule, zero is `false` and anything else is `true`.
+    /// Strings fail with `EvaluateError::BooleanTypeRequired`.
+    pub fn try_into_bool(&self) -> Result<bool> {
+        use data::Value::*;
+
+        if self.is_null() {
+            return Ok(false);
+        }
+
+        let literal = |v: &AstValue| match v {
+            AstValue::Boolean(v) => Ok(*v),
+            AstValue::Number(v) => parse_number(v).map(|v| v.to_f64() != 0.0),
+            _ => Err(EvaluateError::BooleanTypeRequired(self.to_string()).into()),
+        };
+
+        let value = |v: &data::Value| match v {
+            Bool(v) | OptBool(Some(v)) => Ok(*v),
+            I64(v) | OptI64(Some(v)) => Ok(*v != 0),
+            F64(v) | OptF64(Some(v)) => Ok(*v != 0.0),
+            Decimal(v) | OptDecimal(Some(v)) => Ok(!v.is_zero()),
+            _ => Err(EvaluateError::BooleanTypeRequired(self.to_string()).into()),
+        };
+
+        match self {
+            Evaluated::LiteralRef(v) => literal(v),
+            Evaluated::Literal(v) => literal(v),
+            Evaluated::StringRef(_) => {
+                Err(EvaluateError::BooleanTypeRequired(self.to_string()).into())
+            }
+            Evaluated::ValueRef(v) => value(v),
+            Evaluated::Value(v) => value(v),
+        }
+    }
+
+    /// SQL `IIF(cond, a, b)`, `a` when `cond` holds and `b` otherwise. A NULL condition
+    /// selects `b`, and anything other than a boolean, including a number, fails with
+    /// `BooleanTypeRequired`.
+    pub fn iif(cond: &Evaluated<'_>, a: Evaluated<'a>, b: Evaluated<'a>) -> Result<Evaluated<'a>> {
+        cond.boolean_operand()
+            .map(|v| if v == Some(true) { a } else { b })
+    }
+
+    /// Runtime type without materializing, number literals are `INT` when they parse as `i64`
+    /// and `FLOAT` otherwise. A NULL value from a nullable column keeps the column type, while
+    /// NULL literals and `Value::Empty` have no type.
+    pub fn data_type(&self) -> Option<DataType> {
+        use data::Value::*;
+
+        let literal = |v: &AstValue| match v {
+            AstValue::Number(v) => match parse_number(v).ok()? {
+                ParsedNumber::Int(_) => Some(DataType::Int),
+                ParsedNumber::UInt(_) | ParsedNumber::Float(_) => Some(DataType::Float(None)),
+            },
+            AstValue::SingleQuotedString(_) => Some(DataType::Text),
+            AstValue::Boolean(_) => Some(DataType::Boolean),
+            _ => None,
+        };
+
+        let value = |v: &data::Value| match v {
+            Bool(_) | OptBool(_) => Some(DataType::Boolean),
+            I64(_) | OptI64(_) => Some(DataType::Int),
+            F64(_) | OptF64(_) => Some(DataType::Float(None)),
+            Str(_) | OptStr(_) => Some(DataType::Text),
+            Decimal(_) | OptDecimal(_) => Some(DataType::Decimal(None, None)),
+            Date(_) | OptDate(_) => Some(DataType::Date),
+            Timestamp(_) | OptTimestamp(_) => Some(DataType::Timestamp),
+            Bytes(_) | OptBytes(_) => Some(DataType::Bytea),
+            Empty => None,
+        };
+
+        match self {
+            Evaluated::LiteralRef(v) => literal(v),
+            Evaluated::Literal(v) => literal(v),
+            Evaluated::StringRef(_) => Some(DataType::Text),
+            Evaluated::ValueRef(v) => value(v),
+            Evaluated::Value(v) => value(v),
+        }
+    }
+
+    /// Cell of a query result as JSON, e.g. for a REST layer. Numbers, strings and booleans map
+    /// onto their JSON counterparts and NULL onto `null`. Decimals become strings to keep their
+    /// precision, dates and timestamps ISO 8601 strings, and bytes base64 strings. NaN and
+    /// infinite floats have no JSON form and fail with `UnrepresentableJson`.
+    pub fn to_json(&self) -> Result<serde_json::Value> {
+        use data::Value::*;
+        use serde_json::Value as Json;
+
+        let float = |v: f64| {
+            serde_json::Number::from_f64(v)
+                .map(Json::Number)
+                .ok_or_else(|| EvaluateError::UnrepresentableJson(float_to_string(v)).into())
+        };
+
+        let literal = |v: &AstValue| -> Result<Json> {
+            match v {
+                AstValue::Number(v) => match parse_number(v)? {
+                    ParsedNumber::Int(v) => Ok(Json::from(v)),
+                    ParsedNumber::UInt(v) => Ok(Json::from(v)),
+                    ParsedNumber::Float(v) => float(v),
+                },
+                AstValue::Boolean(v) => Ok(Json::Bool(*v)),
+                AstValue::HexStringLiteral(hex) => data::literal_bytes(v)
+                    .map(|v| Json::String(base64_encode(&v)))
+                    .ok_or_else(|| EvaluateError::InvalidHexLiteral(hex.clone()).into()),
+                AstValue::Null => Ok(Json::Null),
+                _ => data::literal_bytes(v)
+                    .map(|v| Json::String(String::from_utf8_lossy(&v).into_owned()))
+                    .ok_or_else(|| EvaluateError::UnrepresentableJson(v.to_string()).into()),
+            }
+        };
+
+        let value = |v: &data::Value| match v {
+            Bool(v) | OptBool(Some(v)) => Ok(Json::Bool(*v)),
+            I64(v) | OptI64(Some(v)) => Ok(Json::from(*v)),
+            F64(v) | OptF64(Some(v)) => float(*v),
+            Str(v) | OptStr(Some(v)) => Ok(Json::String(v.clone())),
+            Decimal(v) | OptDecimal(Some(v)) => Ok(Json::String(v.to_string())),
+            Date(v) | OptDate(Some(v)) => Ok(Json::String(v.to_string())),
+            Timestamp(v) | OptTimestamp(Some(v)) => {
+                Ok(Json::String(v.format("%Y-%m-%dT%H:%M:%S%.f").to_string()))
+            }
+            Bytes(v) | OptBytes(Some(v)) => Ok(Json::String(base64_encode(v))),
+            OptBool(None) | OptI64(None) | OptF64(None) | OptStr(None) | OptDecimal(None)
+            | OptDate(None) | OptTimestamp(None) | OptBytes(None) | Empty => Ok(Json::Null),
+        };
+
+        match self {
+            Evaluated::LiteralRef(v) => literal(v),
+            Evaluated::Literal(v) => literal(v),
+            Evaluated::StringRef(v) => Ok(Json::String((*v).to_owned())),
+            Evaluated::ValueRef(v) => value(v),
+            Evaluated::Value(v) => value(v),
+        }
+    }
+
+    /// Builds a value from a JSON scalar, the inverse of `to_json` for numbers, strings and
+    /// booleans. Numbers are read as in `json_number` and `null` becomes NULL. Arrays and objects have no
+    /// matching value and fail with `JsonNotScalar`.
+    pub fn from_json(v: &serde_json::Value) -> Result<Evaluated<'static>> {
+        use serde_json::Value as Json;
+
+        let v = match v {
+            Json::Null => return Ok(Evaluated::Literal(AstValue::Null)),
+            Json::Bool(v) => data::Value::Bool(*v),
+            Json::Number(v) => return json_number(v),
+            Json::String(v) => data::Value::Str(v.clone()),
+            Json::Array(_) | Json::Object(_) => {
+                return Err(EvaluateError::JsonNotScalar(v.to_string()).into())
+            }
+        };
+
+        Ok(Evaluated::Value(v))
+    }
+
+    /// Converts an arithmetic result into `data_type`, e.g. the type of the column it is stored
+    /// into, so that `num * 1.0` stays an integer for an `INTEGER` column. A float or decimal
+    /// with a fractional part fails with `LossyConversion` and one beyond `i64` with
+    /// `NumericOverflow`, as does an integer beyond the exact range of a float for a `FLOAT`
+    /// target. NULLs keep their nullability, and any other pair is returned as is.
+    pub fn fit_to(self, data_type: &DataType) -> Result<Evaluated<'a>> {
+        use data::Value::*;
+
+        let lossy = |v: &data::Value| -> Error {
+            EvaluateError::LossyConversion {
+                value: Evaluated::ValueRef(v).to_string(),
+                target: data_type.to_string(),
+            }
+            .into()
+        };
+
+        let to_i64 = |v: f64, value: &data::Value| -> Result<i64> {
+            const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+
+            if !v.is_nan() && !(-LIMIT..LIMIT).contains(&v) {
+                Err(data::ValueError::NumericOverflow.into())
+            } else if v.fract() != 0.0 {
+                Err(lossy(value))
+            } else {
+                Ok(v as i64)
+            }
+        };
+
+        let value = match self {
+            Evaluated::LiteralRef(AstValue::Number(_))
+            | Evaluated::Literal(AstValue::Number(_)) => self.to_value()?,
+            Evaluated::ValueRef(v) => v.clone(),
+            Evaluated::Value(v) => v,
+            _ => return Ok(self),
+        };
+
+        let fitted = match (data_type, &value) {
+            (DataType::Int, F64(v)) => I64(to_i64(*v, &value)?),
+            (DataType::Int, OptF64(Some(v))) => OptI64(Some(to_i64(*v, &value)?)),
+            (DataType::Int, Decimal(v)) | (DataType::Int, OptDecimal(Some(v))) => {
+                if !v.fract().is_zero() {
+                    return Err(lossy(&value));
+                }
+
+                let v = v.to_i64().ok_or(data::ValueError::NumericOverflow)?;
+
+                match value {
+                    Decimal(_) => I64(v),
+                    _ => OptI64(Some(v)),
+                }
+            }
+            (DataType::Int, OptF64(None) | OptDecimal(None)) => OptI64(None),
+            (DataType::Float(_), I64(v) | OptI64(Some(v))) => {
+                let fitted = *v as f64;
+
+                if fitted as i128 != *v as i128 {
+                    return Err(data::ValueError::NumericOverflow.into());
+                }
+
+                match value {
+                    I64(_) => F64(fitted),
+                    _ => OptF64(Some(fitted)),
+                }
+            }
+            (DataType::Float(_), OptI64(None)) => OptF64(None),
+            _ => value,
+        };
+
+        Ok(Evaluated::Value(fitted))
+    }
+
+    /// SQL `TYPEOF`, the name of the runtime type as a string, e.g. `'INTEGER'` for `1` and
+    /// `'FLOAT'` for `1.5`. NULL of any type, including a NULL from a typed column, is `'NULL'`.
+    pub fn typeof_name(&self) -> Evaluated<'a> {
+        let data_type = match self {
+            _ if self.is_null() => None,
+            Evaluated::LiteralRef(AstValue::HexStringLiteral(_))
+            | Evaluated::Literal(AstValue::HexStringLiteral(_)) => Some(DataType::Bytea),
+            Evaluated::LiteralRef(AstValue::NationalStringLiteral(_))
+            | Evaluated::Literal(AstValue::NationalStringLiteral(_)) => Some(DataType::Text),
+            _ => self.data_type(),
+        };
+
+        let name = match data_type {
+            Some(DataType::Boolean) => "BOOLEAN",
+            Some(DataType::Int) => "INTEGER",
+            Some(DataType::Float(_)) => "FLOAT",
+            Some(DataType::Text) => "TEXT",
+            Some(DataType::Decimal(..)) => "DECIMAL",
+            Some(DataType::Date) => "DATE",
+            Some(DataType::Timestamp) => "TIMESTAMP",
+            Some(DataType::Bytea) => "BYTEA",
+            _ => "NULL",
+        };
+
+        Evaluated::Value(data::Value::Str(name.to_owned()))
+    }
+
+    /// Materializes into a `Value`. Number literals become `I64` when they parse as integers and
+    /// `F64` otherwise, and a NULL literal, which carries no type, becomes `Value::Empty`.
+    pub fn to_value(&self) -> Result<Value> {
+        use Evaluated::*;
+
+        match self {
+            LiteralRef(v) => data::Value::try_from(*v),
+            Literal(v) => data::Value::try_from(v),
+            StringRef(v) => Ok(data::Value::Str((*v).to_owned())),
+            ValueRef(v) => Ok((*v).clone()),
+            Value(v) => Ok(v.clone()),
+        }
+    }
+
+    /// Same as `to_value`, but moves out of the owned variants instead of cloning.
+    pub fn into_value(self) -> Result<Value> {
+        use Evaluated::*;
+
+        match self {
+            Literal(AstValue::SingleQuotedString(v)) => Ok(data::Value::Str(v)),
+            Value(v) => Ok(v),
+            _ => self.to_value(),
+        }
+    }
+
+    /// Detaches from the borrowed AST and row, e.g. to cache a result beyond the query. Borrowed
+    /// literals and values are cloned, and a `StringRef` becomes `Value::Str`.
+    pub fn into_owned(self) -> Evaluated<'static> {
+        use Evaluated::*;
+
+        match self {
+            LiteralRef(v) => Literal(v.clone()),
+            Literal(v) => Literal(v),
+            StringRef(v) => Value(data::Value::Str(v.to_owned())),
+            ValueRef(v) => Value(v.clone()),
+            Value(v) => Value(v),
+        }
+    }
+
+    /// Converts back into a literal, e.g. to replace a constant subexpression while rewriting a
+    /// query. Numbers keep the textual form `to_value` reads back, so floats keep their
+    /// fractional part, and a NULL value of any type becomes `NULL`. NaN and infinite floats have
+    /// no literal form and fail with `UnrepresentableLiteral`.
+    pub fn to_ast_value(&self) -> Result<AstValue> {
+        use data::Value::*;
+
+        let value = |v: &data::Value| match v {
+            Bool(v) | OptBool(Some(v)) => Ok(AstValue::Boolean(*v)),
+            I64(v) | OptI64(Some(v)) => Ok(AstValue::Number(v.to_string())),
+            F64(v) | OptF64(Some(v)) if v.is_finite() => Ok(AstValue::Number(float_to_string(*v))),
+            F64(v) | OptF64(Some(v)) => {
+                Err(EvaluateError::UnrepresentableLiteral(float_to_string(*v)).into())
+            }
+            Decimal(v) | OptDecimal(Some(v)) => Ok(AstValue::Number(v.to_string())),
+            Str(v) | OptStr(Some(v)) => Ok(AstValue::SingleQuotedString(v.clone())),
+            Date(v) | OptDate(Some(v)) => Ok(AstValue::SingleQuotedString(v.to_string())),
+            Timestamp(v) | OptTimestamp(Some(v)) => Ok(AstValue::SingleQuotedString(v.to_string())),
+            Bytes(v) | OptBytes(Some(v)) => Ok(AstValue::HexStringLiteral(upper_hex(v))),
+            OptBool(None) | OptI64(None) | OptF64(None) | OptDecimal(None) | OptStr(None)
+            | OptDate(None) | OptTimestamp(None) | OptBytes(None) | Empty => Ok(AstValue::Null),
+        };
+
+        match self {
+            Evaluated::LiteralRef(v) => Ok((*v).clone()),
+            Evaluated::Literal(v) => Ok(v.clone()),
+            Evaluated::StringRef(v) => Ok(AstValue::SingleQuotedString((*v).to_owned())),
+            Evaluated::ValueRef(v) => value(v),
+            Evaluated::Value(v) => value(v),
+        }
+    }
+
+    /// A NULL literal, which makes arithmetic NULL whatever the other operand is. NULL values
+    /// are left to `data::Value`, which keeps the type of their column.
+    fn is_null_literal(&self) -> bool {
+        matches!(
+            self,
+            Evaluated::LiteralRef(AstValue::Null) | Evaluated::Literal(AstValue::Null)
+        )
+    }
+
+    /// A string on either side, except for a date or timestamp with a string to be parsed as
+    /// one, as in `day - '2020-12-25'`.
+    fn is_string_arithmetic(&self, other: &Evaluated<'a>) -> bool {
+        (self.as_str().is_some() || other.as_str().is_some())
+            && temporal_and_str(self, other)
+                .or_else(|| temporal_and_str(other, self))
+                .is_none()
+    }
+
+    fn arithmetic_on_non_numeric(&self, other: &Evaluated<'a>) -> Error {
+        EvaluateError::ArithmeticOnNonNumeric {
+            left: self.to_string(),
+            right: other.to_string(),
+        }
+        .into()
+    }
+
+    /// `due_date + INTERVAL '7 days'`, `INTERVAL '1' MONTH + due_date` and
+    /// `due_date - INTERVAL '1' DAY`. Returns `None` when neither side is an interval literal.
+    fn interval_arithmetic(
+        &self,
+        other: &Evaluated<'a>,
+        subtract: bool,
+    ) -> Option<Result<Evaluated<'a>>> {
+        let (value, interval) = match (interval_literal(self), interval_literal(other)) {
+            (None, None) => return None,
+            (None, Some(interval)) => (self, interval),
+            (Some(interval), None) if !subtract => (other, interval),
+            _ => return Some(Err(self.arithmetic_on_non_numeric(other))),
+        };
+
+        if value.is_null() {
+            return Some(Ok(Evaluated::Literal(AstValue::Null)));
+        }
+
+        let shift = || {
+            let interval = data::Interval::parse(interval)?;
+            let value = value.to_value()?;
+
+            if subtract {
+                value.subtract_interval(&interval)
+            } else {
+                value.add_interval(&interval)
+            }
+        };
+
+        Some(shift().map(Evaluated::Value))
+    }
+
+    pub fn add(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        use Evaluated::*;
+
+        if let Some(result) = self.interval_arithmetic(other, false) {
+            return result;
+        }
+
+        if self.is_null_literal() || other.is_null_literal() {
+            return Ok(Literal(AstValue::Null));
+        }
+
+        let non_numeric = || Err(self.arithmetic_on_non_numeric(other));
+
+        if self.is_string_arithmetic(other) {
+            return non_numeric();
+        }
+
+        let add_literal = |l, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => literal_add(l, r).map(Evaluated::Literal),
+            Literal(r) => literal_add(l, r).map(Evaluated::Literal),
+            ValueRef(r) => r.add(&cast_literal(r, l)?).map(Evaluated::Value),
+            Value(r) => r.add(&cast_literal(r, l)?).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        let add_value = |l: &data::Value, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => l.add(&cast_literal(l, r)?).map(Evaluated::Value),
+            Literal(r) => l.add(&cast_literal(l, r)?).map(Evaluated::Value),
+            ValueRef(r) => l.add(r).map(Evaluated::Value),
+            Value(r) => l.add(r).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        match self {
+            LiteralRef(l) => add_literal(l, other),
+            Literal(l) => add_literal(l, other),
+            ValueRef(l) => add_value(l, other),
+            Value(l) => add_value(l, other),
+            StringRef(_) => non_numeric(),
+        }
+    }
+
+    pub fn subtract(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        use Evaluated::*;
+
+        if let Some(result) = self.interval_arithmetic(other, true) {
+            return result;
+        }
+
+        if self.is_null_literal() || other.is_null_literal() {
+            return Ok(Literal(AstValue::Null));
+        }
+
+        let non_numeric = || Err(self.arithmetic_on_non_numeric(other));
+
+        if self.is_string_arithmetic(other) {
+            return non_numeric();
+        }
+
+        let subtract_literal = |l, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => literal_subtract(l, r).map(Evaluated::Literal),
+            Literal(r) => literal_subtract(l, r).map(Evaluated::Literal),
+            ValueRef(r) => cast_literal(r, l)?.subtract(r).map(Evaluated::Value),
+            Value(r) => cast_literal(r, l)?.subtract(r).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        let subtract_value = |l: &data::Value, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => l.subtract(&cast_literal(l, r)?).map(Evaluated::Value),
+            Literal(r) => l.subtract(&cast_literal(l, r)?).map(Evaluated::Value),
+            ValueRef(r) => l.subtract(r).map(Evaluated::Value),
+            Value(r) => l.subtract(r).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        match self {
+            LiteralRef(l) => subtract_literal(l, other),
+            Literal(l) => subtract_literal(l, other),
+            ValueRef(l) => subtract_value(l, other),
+            Value(l) => subtract_value(l, other),
+            StringRef(_) => non_numeric(),
+        }
+    }
+
+    pub fn multiply(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        use Evaluated::*;
+
+        if self.is_null_literal() || other.is_null_literal() {
+            return Ok(Literal(AstValue::Null));
+        }
+
+        let non_numeric = || Err(self.arithmetic_on_non_numeric(other));
+
+        if self.is_string_arithmetic(other) {
+            return non_numeric();
+        }
+
+        let multiply_literal = |l, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => literal_multiply(l, r).map(Evaluated::Literal),
+            Literal(r) => literal_multiply(l, r).map(Evaluated::Literal),
+            ValueRef(r) => cast_literal(r, l)?.multiply(r).map(Evaluated::Value),
+            Value(r) => cast_literal(r, l)?.multiply(r).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        let multiply_value = |l: &data::Value, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => l.multiply(&cast_literal(l, r)?).map(Evaluated::Value),
+            Literal(r) => l.multiply(&cast_literal(l, r)?).map(Evaluated::Value),
+            ValueRef(r) => l.multiply(r).map(Evaluated::Value),
+            Value(r) => l.multiply(r).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        match self {
+            LiteralRef(l) => multiply_literal(l, other),
+            Literal(l) => multiply_literal(l, other),
+            ValueRef(l) => multiply_value(l, other),
+            Value(l) => multiply_value(l, other),
+            StringRef(_) => non_numeric(),
+        }
+    }
+
+    /// `self % other` with the sign of the dividend, see `Value::modulo`. NULL on either side
+    /// yields NULL and a zero divisor fails with `DivideByZero`.
+    pub fn modulo(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        use Evaluated::*;
+
+        if self.is_null_literal() || other.is_null_literal() {
+            return Ok(Literal(AstValue::Null));
+        }
+
+        let non_numeric = || Err(self.arithmetic_on_non_numeric(other));
+
+        if self.is_string_arithmetic(other) {
+            return non_numeric();
+        }
+
+        let modulo_literal = |l, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => literal_modulo(l, r).map(Evaluated::Literal),
+            Literal(r) => literal_modulo(l, r).map(Evaluated::Literal),
+            ValueRef(r) => cast_literal(r, l)?.modulo(r).map(Evaluated::Value),
+            Value(r) => cast_literal(r, l)?.modulo(r).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        let modulo_value = |l: &data::Value, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => l.modulo(&cast_literal(l, r)?).map(Evaluated::Value),
+            Literal(r) => l.modulo(&cast_literal(l, r)?).map(Evaluated::Value),
+            ValueRef(r) => l.modulo(r).map(Evaluated::Value),
+            Value(r) => l.modulo(r).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        match self {
+            LiteralRef(l) => modulo_literal(l, other),
+            Literal(l) => modulo_literal(l, other),
+            ValueRef(l) => modulo_value(l, other),
+            Value(l) => modulo_value(l, other),
+            StringRef(_) => non_numeric(),
+        }
+    }
+
+    /// SQL `MOD(self, other)`, the same as `modulo`.
+    pub fn r#mod(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.modulo(other)
+    }
+
+    /// Same as `add`, except that an integer result beyond `i64` is clamped to `i64::MAX` or
+    /// `i64::MIN` instead of failing with `NumericOverflow`. Number literals beyond `i64` and
+    /// decimals still fail.
+    pub fn add_saturating(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.saturating(other, Evaluated::add, i64::saturating_add)
+    }
+
+    /// Same as `subtract`, but clamping on overflow, see `add_saturating`.
+    pub fn subtract_saturating(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.saturating(other, Evaluated::subtract, i64::saturating_sub)
+    }
+
+    /// Same as `multiply`, but clamping on overflow, see `add_saturating`.
+    pub fn multiply_saturating(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.saturating(other, Evaluated::multiply, i64::saturating_mul)
+    }
+
+    fn saturating(
+        &self,
+        other: &Evaluated<'a>,
+        checked: fn(&Evaluated<'a>, &Evaluated<'a>) -> Result<Evaluated<'a>>,
+        saturating: fn(i64, i64) -> i64,
+    ) -> Result<Evaluated<'a>> {
+        self.on_integer_overflow(other, checked, |l, r| ParsedNumber::Int(saturating(l, r)))
+    }
+
+    /// `multiply` with the given overflow handling, see `OverflowMode`.
+    pub fn multiply_with(
+        &self,
+        other: &Evaluated<'a>,
+        mode: OverflowMode,
+    ) -> Result<Evaluated<'a>> {
+        match mode {
+            OverflowMode::Checked => self.multiply(other),
+            OverflowMode::Widening => {
+                self.on_integer_overflow(other, Evaluated::multiply, |l, r| {
+                    ParsedNumber::Float(l as f64 * r as f64)
+                })
+            }
+        }
+    }
+
+    /// Runs `checked`, and when it fails with `NumericOverflow` on two `i64` operands, replaces
+    /// the error with `recover` of those operands, see `number_result`.
+    fn on_integer_overflow(
+        &self,
+        other: &Evaluated<'a>,
+        checked: fn(&Evaluated<'a>, &Evaluated<'a>) -> Result<Evaluated<'a>>,
+        recover: impl FnOnce(i64, i64) -> ParsedNumber,
+    ) -> Result<Evaluated<'a>> {
+        let error = match checked(self, other) {
+            Err(error) => error,
+            found => return found,
+        };
+
+        let is_overflow = error == data::ValueError::NumericOverflow.into();
+        let operands = (
+            self.integer_operand("overflow").ok().flatten(),
+            other.integer_operand("overflow").ok().flatten(),
+        );
+
+        match operands {
+            (Some(l), Some(r)) if is_overflow => Ok(self.number_result(other, recover(l, r))),
+            _ => Err(error),
+        }
+    }
+
+    /// `v` as a literal when both operands are literals, otherwise as a value which is nullable
+    /// when either operand is.
+    fn number_result(&self, other: &Evaluated<'a>, v: ParsedNumber) -> Evaluated<'a> {
+        use data::Value::{OptF64, OptI64, F64, I64};
+        use Evaluated::*;
+
+        let is_literal = |v: &Evaluated<'a>| matches!(v, LiteralRef(_) | Literal(_));
+        let is_nullable = |v: &Evaluated<'a>| matches!(v, ValueRef(OptI64(_)) | Value(OptI64(_)));
+
+        if is_literal(self) && is_literal(other) {
+            return Literal(v.into_literal());
+        }
+
+        let nullable = is_nullable(self) || is_nullable(other);
+
+        Value(match v {
+            ParsedNumber::Int(v) if nullable => OptI64(Some(v)),
+            ParsedNumber::Int(v) => I64(v),
+            ParsedNumber::Float(v) if nullable => OptF64(Some(v)),
+            _ => F64(v.to_f64()),
+        })
+    }
+
+    /// `divide` with the given handling of two integers, see `DivisionMode`.
+    pub fn divide_with(&self, other: &Evaluated<'a>, mode: DivisionMode) -> Result<Evaluated<'a>> {
+        let operands = (
+            self.integer_operand("/").ok().flatten(),
+            other.integer_operand("/").ok().flatten(),
+        );
+
+        // `checked_rem` fails on a zero divisor and on `i64::MIN / -1`, both left to `divide`.
+        match (mode, operands) {
+            (DivisionMode::True, (Some(l), Some(r)))
+                if l.checked_rem(r).is_some_and(|m| m != 0) =>
+            {
+                let v = ParsedNumber::Float(l as f64 / r as f64);
+
+                Ok(self.number_result(other, v))
+            }
+            _ => self.divide(other),
+        }
+    }
+
+    /// SQL `DIV`, integer division truncated toward zero whatever the `DivisionMode`, so
+    /// `7 DIV 2` is `3` and `-7 DIV 2` is `-3`. Both operands must be integers, NULL on either
+    /// side yields NULL and a zero divisor fails with `DivideByZero`.
+    pub fn div(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.integer_pair(other, "DIV", |l, r| match r {
+            0 => Err(data::ValueError::DivideByZero.into()),
+            _ => l
+                .checked_div(r)
+                .ok_or_else(|| data::ValueError::NumericOverflow.into()),
+        })
+    }
+
+    pub fn divide(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        use Evaluated::*;
+
+        if self.is_null_literal() || other.is_null_literal() {
+            return Ok(Literal(AstValue::Null));
+        }
+
+        let non_numeric = || Err(self.arithmetic_on_non_numeric(other));
+
+        if self.is_string_arithmetic(other) {
+            return non_numeric();
+        }
+
+        let divide_literal = |l, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => literal_divide(l, r).map(Evaluated::Literal),
+            Literal(r) => literal_divide(l, r).map(Evaluated::Literal),
+            ValueRef(r) => cast_literal(r, l)?.divide(r).map(Evaluated::Value),
+            Value(r) => cast_literal(r, l)?.divide(r).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        let divide_value = |l: &data::Value, other: &Evaluated<'a>| match other {
+            LiteralRef(r) => l.divide(&cast_literal(l, r)?).map(Evaluated::Value),
+            Literal(r) => l.divide(&cast_literal(l, r)?).map(Evaluated::Value),
+            ValueRef(r) => l.divide(r).map(Evaluated::Value),
+            Value(r) => l.divide(r).map(Evaluated::Value),
+            StringRef(_) => non_numeric(),
+        };
+
+        match self {
+            LiteralRef(l) => divide_literal(l, other),
+            Literal(l) => divide_literal(l, other),
+            ValueRef(l) => divide_value(l, other),
+            Value(l) => divide_value(l, other),
+            StringRef(_) => non_numeric(),
+        }
+    }
+
+    /// SQL `^`, following PostgreSQL rather than treating it as XOR. Integer exponentiation
+    /// when both operands are integers and the exponent is non-negative, `f64::powf` otherwise.
+    /// Decimals are promoted into floats.
+    pub fn power(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        use data::Value::*;
+
+        if self.is_null() || other.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        let (base, exp) = (self.to_value()?, other.to_value()?);
+        let is_nullable = |v: &data::Value| matches!(v, OptI64(_) | OptF64(_) | OptDecimal(_));
+        let nullable = is_nullable(&base) || is_nullable(&exp);
+
+        let to_f64 = |v: &data::Value| match v {
+            I64(v) | OptI64(Some(v)) => Some(*v as f64),
+            F64(v) | OptF64(Some(v)) => Some(*v),
+            Decimal(v) | OptDecimal(Some(v)) => v.to_f64(),
+            _ => None,
+        };
+
+        if let (I64(b) | OptI64(Some(b)), I64(e) | OptI64(Some(e))) = (&base, &exp) {
+            if *e >= 0 {
+                let v = u32::try_from(*e)
+                    .ok()
+                    .and_then(|e| b.checked_pow(e))
+                    .ok_or(data::ValueError::NumericOverflow)?;
+
+                return Ok(Evaluated::Value(if nullable {
+                    OptI64(Some(v))
+                } else {
+                    I64(v)
+                }));
+            }
+        }
+
+        match (to_f64(&base), to_f64(&exp)) {
+            (Some(b), Some(e)) if b == 0.0 && e < 0.0 => Err(data::ValueError::DivideByZero.into()),
+            (Some(b), Some(e)) => {
+                let v = b.powf(e);
+
+                Ok(Evaluated::Value(if nullable {
+                    OptF64(Some(v))
+                } else {
+                    F64(v)
+                }))
+            }
+            _ => Err(EvaluateError::PowerOnNonNumeric.into()),
+        }
+    }
+
+    pub fn unary_minus(&self) -> Result<Evaluated<'a>> {
+        use Evaluated::*;
+
+        match self {
+            LiteralRef(v) => literal_unary_minus(v).map(Evaluated::Literal),
+            Literal(v) => literal_unary_minus(v).map(Evaluated::Literal),
+            ValueRef(v) => v.unary_minus().map(Evaluated::Value),
+            Value(v) => v.unary_minus().map(Evaluated::Value),
+            StringRef(_) => Err(data::ValueError::UnaryMinusOnNonNumeric.into()),
+        }
+    }
+
+    pub fn unary_plus(&self) -> Result<Evaluated<'a>> {
+        use Evaluated::*;
+
+        let is_numeric_literal = |v: &AstValue| matches!(v, AstValue::Number(_) | AstValue::Null);
+        let is_numeric_value = |v: &data::Value| {
+            matches!(
+                v,
+                data::Value::I64(_)
+                    | data::Value::OptI64(_)
+                    | data::Value::F64(_)
+                    | data::Value::OptF64(_)
+                    | data::Value::Decimal(_)
+                    | data::Value::OptDecimal(_)
+            )
+        };
+
+        match self {
+            LiteralRef(v) if is_numeric_literal(v) => Ok(LiteralRef(v)),
+            Literal(v) if is_numeric_literal(v) => Ok(Literal(v.clone())),
+            ValueRef(v) if is_numeric_value(v) => Ok(ValueRef(v)),
+            Value(v) if is_numeric_value(v) => Ok(Value(v.clone())),
+            _ => Err(EvaluateError::UnaryPlusOnNonNumeric.into()),
+        }
+    }
+
+    pub fn abs(&self) -> Result<Evaluated<'a>> {
+        use Evaluated::*;
+
+        match self {
+            LiteralRef(v) => literal_abs(v).map(Evaluated::Literal),
+            Literal(v) => literal_abs(v).map(Evaluated::Literal),
+            ValueRef(v) => v.abs().map(Evaluated::Value),
+            Value(v) => v.abs().map(Evaluated::Value),
+            StringRef(_) => Err(data::ValueError::AbsOnNonNumeric.into()),
+        }
+    }
+
+    /// `NOW()` and `CURRENT_TIMESTAMP`, read from the given clock.
+    pub fn now(clock: &dyn Clock) -> Evaluated<'a> {
+        Evaluated::Value(data::Value::Timestamp(clock.now()))
+    }
+
+    /// SQL `EXTRACT(field FROM v)` as an integer. A string operand is parsed as a date or
+    /// timestamp and a date has a time of midnight. NULL is passed through.
+    pub fn extract(&self, field: DateTimeField) -> Result<Evaluated<'a>> {
+        use chrono::{Datelike, Timelike};
+        use data::Value::*;
+
+        if self.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        let (timestamp, nullable) = match self.as_str() {
+            Some(text) => data::parse_timestamp(text)
+                .map(|v| (v, false))
+                .ok_or_else(|| EvaluateError::InvalidDateLiteral(text.to_owned()))?,
+            None => {
+                let value = self.to_value()?;
+                let nullable = matches!(value, OptDate(_) | OptTimestamp(_));
+
+                data::timestamp_of(&value)
+                    .map(|v| (v, nullable))
+                    .ok_or(EvaluateError::ExtractOnNonDate)?
+            }
+        };
+
+        let v = match field {
+            DateTimeField::Year => timestamp.year() as i64,
+            DateTimeField::Month => timestamp.month() as i64,
+            DateTimeField::Day => timestamp.day() as i64,
+            DateTimeField::Hour => timestamp.hour() as i64,
+            DateTimeField::Minute => timestamp.minute() as i64,
+            DateTimeField::Second => timestamp.second() as i64,
+        };
+
+        Ok(Evaluated::Value(if nullable {
+            OptI64(Some(v))
+        } else {
+            I64(v)
+        }))
+    }
+
+    /// SQL `SIGN`, `-1`, `0` or `1` as an integer, with `-0.0` taken as zero.
+    /// NULL is passed through, while `NaN` has no sign and fails with `InvalidMathArgument`.
+    pub fn sign(&self) -> Result<Evaluated<'a>> {
+        use data::Value::*;
+
+        if self.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        let float_sign = |v: f64| match v.partial_cmp(&0.0) {
+            Some(o) => Ok(o as i64),
+            None => Err(EvaluateError::InvalidMathArgument {
+                function: "sign".to_owned(),
+                value: self.to_string(),
+            }),
+        };
+        let decimal_sign = |v: rust_decimal::Decimal| {
+            if v.is_zero() {
+                0
+            } else if v.is_sign_negative() {
+                -1
+            } else {
+                1
+            }
+        };
+
+        let (sign, nullable) = match self.to_value()? {
+            I64(v) => (v.signum(), false),
+            OptI64(Some(v)) => (v.signum(), true),
+            F64(v) => (float_sign(v)?, false),
+            OptF64(Some(v)) => (float_sign(v)?, true),
+            Decimal(v) => (decimal_sign(v), false),
+            OptDecimal(Some(v)) => (decimal_sign(v), true),
+            _ => return Err(EvaluateError::MathOnNonNumeric("sign".to_owned()).into()),
+        };
+
+        Ok(Evaluated::Value(if nullable {
+            OptI64(Some(sign))
+        } else {
+            I64(sign)
+        }))
+    }
+
+    /// Rounds half away from zero, so `2.5` becomes `3.0` and `-2.5` becomes `-3.0`.
+    /// `places` keeps that many decimal digits, negative `places` round left of the point.
+    /// Digits are rounded on the decimal form of the float, so `ROUND(1.005, 2)` is `1.01`.
+    /// Integers are passed through unchanged.
+    pub fn round(&self, places: Option<i64>) -> Result<Evaluated<'a>> {
+        self.round_with(places, RoundingMode::default())
+    }
+
+    /// `round` where the digits are rounded according to `mode`.
+    pub fn round_with(&self, places: Option<i64>, mode: RoundingMode) -> Result<Evaluated<'a>> {
+        let places = rounding_places(places);
+
+        self.map_float(
+            |v| round_float(v, places, mode),
+            |v| round_decimal(v, places, mode),
+            EvaluateError::MathOnNonNumeric("round".to_owned()),
+        )
+    }
+
+    /// Integers are passed through unchanged.
+    pub fn floor(&self) -> Result<Evaluated<'a>> {
+        self.map_float(
+            f64::floor,
+            |v| Some(v.floor()),
+            EvaluateError::MathOnNonNumeric("floor".to_owned()),
+        )
+    }
+
+    /// Integers are passed through unchanged.
+    pub fn ceil(&self) -> Result<Evaluated<'a>> {
+        self.map_float(
+            f64::ceil,
+            |v| Some(v.ceil()),
+            EvaluateError::MathOnNonNumeric("ceil".to_owned()),
+        )
+    }
+
+    /// Truncates toward zero, so `-2.7` becomes `-2.0` where `floor` gives `-3.0`.
+    /// `places` keeps that many decimal digits, negative `places` truncate left of the point.
+    /// Digits are cut from the decimal form of the float, so `TRUNC(0.29, 2)` is `0.29`.
+    /// Integers are passed through unchanged.
+    pub fn trunc(&self, places: Option<i64>) -> Result<Evaluated<'a>> {
+        let places = rounding_places(places);
+
+        self.map_float(
+            |v| round_float(v, places, RoundingMode::TowardZero),
+            |v| round_decimal(v, places, RoundingMode::TowardZero),
+            EvaluateError::MathOnNonNumeric("trunc".to_owned()),
+        )
+    }
+
+    /// `CAST(self AS target)` for `INTEGER`, `FLOAT`, `TEXT` and `BOOLEAN`, and decimals into
+    /// any of those or `DECIMAL`.
+    /// Floats and decimals are truncated toward zero when cast into `INTEGER`, so `-1.7`
+    /// becomes `-1`.
+    /// Strings are trimmed before being parsed into numbers or booleans.
+    /// NULL stays NULL and nullable values keep their nullability.
+    pub fn cast(&self, target: DataType) -> Result<Evaluated<'a>> {
+        use data::Value::*;
+
+        if self.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        let value = self.to_value()?;
+        let nullable = matches!(
+            value,
+            OptBool(_) | OptI64(_) | OptF64(_) | OptDecimal(_) | OptStr(_)
+        );
+        let failed = || -> Error {
+            EvaluateError::CastFailed {
+                value: self.to_string(),
+                target: target.to_string(),
+            }
+            .into()
+        };
+
+        let value = match &value {
+            Bool(v) | OptBool(Some(v)) => Some(CastValue::Bool(*v)),
+            I64(v) | OptI64(Some(v)) => Some(CastValue::I64(*v)),
+            F64(v) | OptF64(Some(v)) => Some(CastValue::F64(*v)),
+            Decimal(v) | OptDecimal(Some(v)) => Some(CastValue::Decimal(*v)),
+            Str(v) | OptStr(Some(v)) => Some(CastValue::Str(v.trim())),
+            _ => None,
+        }
+        .ok_or_else(failed)?;
+
+        let casted = match (&target, value) {
+            (DataType::Int, CastValue::Bool(v)) => I64(v as i64),
+            (DataType::Int, CastValue::I64(v)) => I64(v),
+            (DataType::Int, CastValue::F64(v)) => {
+                let v = v.trunc();
+
+                if v.is_finite() && v >= i64::MIN as f64 && v < i64::MAX as f64 {
+                    I64(v as i64)
+                } else {
+                    return Err(failed());
+                }
+            }
+            (DataType::Int, CastValue::Decimal(v)) => I64(v.trunc().to_i64().ok_or_else(failed)?),
+            (DataType::Int, CastValue::Str(v)) => I64(v.parse().map_err(|_| failed())?),
+            (DataType::Float(_), CastValue::Bool(v)) => F64(if v { 1.0 } else { 0.0 }),
+            (DataType::Float(_), CastValue::I64(v)) => F64(v as f64),
+            (DataType::Float(_), CastValue::F64(v)) => F64(v),
+            (DataType::Float(_), CastValue::Decimal(v)) => F64(v.to_f64().ok_or_else(failed)?),
+            (DataType::Float(_), CastValue::Str(v)) => F64(v.parse().map_err(|_| failed())?),
+            (DataType::Text, CastValue::Bool(v)) => {
+                Str((if v { "TRUE" } else { "FALSE" }).to_owned())
+            }
+            (DataType::Text, CastValue::I64(v)) => Str(v.to_string()),
+            (DataType::Text, CastValue::F64(v)) => Str(float_to_string(v)),
+            (DataType::Text, CastValue::Decimal(v)) => Str(v.to_string()),
+            (DataType::Text, CastValue::Str(v)) => Str(v.to_owned()),
+            (DataType::Boolean, CastValue::Bool(v)) => Bool(v),
+            (DataType::Decimal(..), CastValue::Decimal(v)) => Decimal(v),
+            (DataType::Boolean, CastValue::I64(0)) => Bool(false),
+            (DataType::Boolean, CastValue::I64(1)) => Bool(true),
+            (DataType::Boolean, CastValue::Decimal(v)) if v.is_zero() => Bool(false),
+            (DataType::Boolean, CastValue::Decimal(v)) if v == rust_decimal::Decimal::ONE => {
+                Bool(true)
+            }
+            (DataType::Boolean, CastValue::Str(v)) => match v.to_lowercase().as_str() {
+                "true" => Bool(true),
+                "false" => Bool(false),
+                _ => return Err(failed()),
+            },
+            _ => return Err(failed()),
+        };
+
+        let casted = match (nullable, casted) {
+            (true, Bool(v)) => OptBool(Some(v)),
+            (true, I64(v)) => OptI64(Some(v)),
+            (true, F64(v)) => OptF64(Some(v)),
+            (true, Decimal(v)) => OptDecimal(Some(v)),
+            (true, Str(v)) => OptStr(Some(v)),
+            (_, v) => v,
+        };
+
+        Ok(Evaluated::Value(casted))
+    }
+
+    pub fn bit_and(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.bitwise(other, "&", |l, r| Ok(l & r))
+    }
+
+    pub fn bit_or(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.bitwise(other, "|", |l, r| Ok(l | r))
+    }
+
+    /// PostgreSQL's `#`, which has no SQL syntax here since `^` is `power`.
+    pub fn bit_xor(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.bitwise(other, "#", |l, r| Ok(l ^ r))
+    }
+
+    /// Bits shifted past the sign bit are dropped, amounts outside `0..64` fail with
+    /// `EvaluateError::ShiftOutOfRange`.
+    pub fn shift_left(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.bitwise(other, "<<", |l, r| shift_amount(r).map(|r| l << r))
+    }
+
+    /// Arithmetic shift which keeps the sign, amounts outside `0..64` fail with
+    /// `EvaluateError::ShiftOutOfRange`.
+    pub fn shift_right(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.bitwise(other, ">>", |l, r| shift_amount(r).map(|r| l >> r))
+    }
+
+    fn bitwise(
+        &self,
+        other: &Evaluated<'a>,
+        name: &str,
+        f: impl FnOnce(i64, i64) -> Result<i64>,
+    ) -> Result<Evaluated<'a>> {
+        let operand = |v: &Evaluated<'a>| {
+            v.integer_operand(name)
+                .map_err(|_| Error::from(EvaluateError::BitwiseOnNonInteger(name.to_owned())))
+        };
+
+        match (operand(self)?, operand(other)?) {
+            (Some(l), Some(r)) => f(l, r).map(|v| Evaluated::Value(data::Value::I64(v))),
+            _ => Ok(Evaluated::Literal(AstValue::Null)),
+        }
+    }
+
+    /// Greatest common divisor of two integers, always non-negative, and `GCD(0, 0)` is `0`.
+    /// NULL on either side yields NULL. `GCD(i64::MIN, 0)` is `2^63`, which overflows.
+    pub fn gcd(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.integer_pair(other, "GCD", |a, b| {
+            i64::try_from(gcd(a.unsigned_abs(), b.unsigned_abs()))
+                .map_err(|_| data::ValueError::NumericOverflow.into())
+        })
+    }
+
+    /// Least common multiple of two integers, always non-negative, and `0` when either side is
+    /// `0`. Divides by the GCD before multiplying, so only results beyond `i64` overflow.
+    pub fn lcm(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        self.integer_pair(other, "LCM", |a, b| {
+            if a == 0 || b == 0 {
+                return Ok(0);
+            }
+
+            let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+
+            (a / gcd(a, b))
+                .checked_mul(b)
+                .and_then(|v| i64::try_from(v).ok())
+                .ok_or_else(|| data::ValueError::NumericOverflow.into())
+        })
+    }
+
+    /// Quotient and remainder of two integers in one pass. The quotient is truncated toward
+    /// zero and the remainder takes the sign of the dividend, as with `/` and `%`, so
+    /// `DIV_REM(-7, 3)` is `(-2, -1)`. NULL on either side yields two NULLs, a zero divisor
+    /// fails with `DivideByZero` and `DIV_REM(i64::MIN, -1)` overflows.
+    pub fn div_rem(&self, other: &Evaluated<'a>) -> Result<(Evaluated<'a>, Evaluated<'a>)> {
+        let name = "DIV_REM";
+
+        match (self.integer_operand(name)?, other.integer_operand(name)?) {
+            (Some(_), Some(0)) => Err(data::ValueError::DivideByZero.into()),
+            (Some(a), Some(b)) => {
+                let overflow = || data::ValueError::NumericOverflow;
+                let quotient = a.checked_div(b).ok_or_else(overflow)?;
+                let remainder = a.checked_rem(b).ok_or_else(overflow)?;
+
+                Ok((
+                    Evaluated::Value(data::Value::I64(quotient)),
+                    Evaluated::Value(data::Value::I64(remainder)),
+                ))
+            }
+            _ => Ok((
+                Evaluated::Literal(AstValue::Null),
+                Evaluated::Literal(AstValue::Null),
+            )),
+        }
+    }
+
+    fn integer_pair(
+        &self,
+        other: &Evaluated<'a>,
+        name: &str,
+        f: impl FnOnce(i64, i64) -> Result<i64>,
+    ) -> Result<Evaluated<'a>> {
+        match (self.integer_operand(name)?, other.integer_operand(name)?) {
+            (Some(l), Some(r)) => f(l, r).map(|v| Evaluated::Value(data::Value::I64(v))),
+            _ => Ok(Evaluated::Literal(AstValue::Null)),
+        }
+    }
+
+    /// Square root, negative operands fail with `EvaluateError::InvalidMathArgument`.
+    pub fn sqrt(&self) -> Result<Evaluated<'a>> {
+        self.math_function("sqrt", |v| v >= 0.0, f64::sqrt)
+    }
+
+    /// Natural logarithm, non-positive operands fail with `EvaluateError::InvalidMathArgument`.
+    pub fn ln(&self) -> Result<Evaluated<'a>> {
+        self.math_function("ln", |v| v > 0.0, f64::ln)
+    }
+
+    /// Base 10 logarithm, non-positive operands fail with `EvaluateError::InvalidMathArgument`.
+    pub fn log10(&self) -> Result<Evaluated<'a>> {
+        self.math_function("log10", |v| v > 0.0, f64::log10)
+    }
+
+    pub fn exp(&self) -> Result<Evaluated<'a>> {
+        self.math_function("exp", |_| true, f64::exp)
+    }
+
+    /// Coerces the operand into `f64`, NULL is passed through.
+    fn math_function(
+        &self,
+        name: &str,
+        valid: impl Fn(f64) -> bool,
+        f: impl Fn(f64) -> f64,
+    ) -> Result<Evaluated<'a>> {
+        use data::Value::*;
+
+        if self.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        let (v, nullable) = match self.to_value()? {
+            I64(v) => (v as f64, false),
+            OptI64(Some(v)) => (v as f64, true),
+            F64(v) => (v, false),
+            OptF64(Some(v)) => (v, true),
+            Decimal(v) => (v.to_f64().unwrap_or(f64::NAN), false),
+            OptDecimal(Some(v)) => (v.to_f64().unwrap_or(f64::NAN), true),
+            _ => return Err(EvaluateError::MathOnNonNumeric(name.to_owned()).into()),
+        };
+
+        if !valid(v) {
+            return Err(EvaluateError::InvalidMathArgument {
+                function: name.to_owned(),
+                value: self.to_string(),
+            }
+            .into());
+        }
+
+        let v = f(v);
+
+        Ok(Evaluated::Value(if nullable {
+            OptF64(Some(v))
+        } else {
+            F64(v)
+        }))
+    }
+
+    /// `f` on floats and `decimal` on decimals, where `None` is an overflow. Integers are passed
+    /// through unchanged.
+    fn map_float(
+        &self,
+        f: impl Fn(f64) -> f64,
+        decimal: impl Fn(rust_decimal::Decimal) -> Option<rust_decimal::Decimal>,
+        error: EvaluateError,
+    ) -> Result<Evaluated<'a>> {
+        use data::Value::*;
+
+        let decimal = |v| decimal(v).ok_or(data::ValueError::NumericOverflow);
+
+        let literal = |v: &AstValue| match v {
+            AstValue::Number(n) => match parse_number(n)? {
+                ParsedNumber::Int(_) | ParsedNumber::UInt(_) => Ok(Evaluated::Literal(v.clone())),
+                ParsedNumber::Float(n) => {
+                    Ok(Evaluated::Literal(ParsedNumber::Float(f(n)).into_literal()))
+                }
+            },
+            AstValue::Null => Ok(Evaluated::Literal(AstValue::Null)),
+            _ => Err(error.clone().into()),
+        };
+
+        let value = |v: &data::Value| match v {
+            I64(_) | OptI64(_) => Ok(Evaluated::Value(v.clone())),
+            F64(v) => Ok(Evaluated::Value(F64(f(*v)))),
+            OptF64(v) => Ok(Evaluated::Value(OptF64(v.map(&f)))),
+            Decimal(v) => Ok(Evaluated::Value(Decimal(decimal(*v)?))),
+            OptDecimal(v) => Ok(Evaluated::Value(OptDecimal(v.map(decimal).transpose()?))),
+            _ => Err(error.clone().into()),
+        };
+
+        match self {
+            Evaluated::LiteralRef(v) => literal(v),
+            Evaluated::Literal(v) => literal(v),
+            Evaluated::ValueRef(v) => value(v),
+            Evaluated::Value(v) => value(v),
+            Evaluated::StringRef(_) => Err(error.into()),
+        }
+    }
+
+    /// SQL `||`. Numbers are joined in their string form, and NULL on either side yields NULL.
+    pub fn concat(&self, other: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        if self.is_null() || other.is_null() {
+            return Ok(Evaluated::Literal(AstValue::Null));
+        }
+
+        match (self.to_concat_string(), other.to_concat_string()) {
+            (Some(l), Some(r)) => Ok(Evaluated::Value(data::Value::Str(l + &r))),
+            _ => Err(EvaluateError::UnsupportedConcat.into()),
+        }
+    }
+
+    /// MySQL `CONCAT_WS`, joins `items` with `sep` in the same string form as `concat`.
+    /// NULL items are skipped, so only NULLs yield an empty string, while a NULL `sep` yields
+    /// NULL.
+    pub fn concat_ws(sep: &Evaluated<'a>, items: &[Evaluated<'a>]) -> Result<Evaluated<'a>> {
+        let sep = match sep.string_operand("CONCAT_WS")? {
+            Some(sep) => sep,
+            None => return Ok(Evaluated::Literal(AstValue::Null)),
+        };
+
+        let items = items
+            .iter()
+            .filter(|item| !item.is_null())
+            .map(|item| {
+                item.to_concat_string()
+                    .ok_or_else(|| EvaluateError::UnsupportedConcat.into())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Evaluated::Value(data::Value::Str(items.join(sep))))
+    }
+
+    /// Unicode aware, uses `str::to_uppercase`.
+    pub fn upper(&self) -> Result<Evaluated<'a>> {
+        self.map_string("UPPER", |v| Ok(data::Value::Str(v.to_uppercase())))
+    }
+
+    /// Unicode aware, uses `str::to_lowercase`.
+    pub fn lower(&self) -> Result<Evaluated<'a>> {
+        self.map_string("LOWER", |v| Ok(data::Value::Str(v.to_lowercase())))
+    }
+
+    /// Title case, a word is a maximal run of alphanumeric characters as in
+    /// `char::is_alphanumeric`, and everything else, such as spaces, punctuation and `_`,
+    /// separates words. The first character of each word is uppercased and the rest are
+    /// lowercased, so `INITCAP('hello wORLD-o''neil')` is `Hello World-O'Neil`. Digits count as
+    /// word characters, so `'3rd'` stays `3rd`.
+    pub fn initcap(&self) -> Result<Evaluated<'a>> {
+        self.map_string("INITCAP", |v| {
+            let mut in_word = false;
+            let v = v
+                .chars()
+                .flat_map(|c| {
+                    let first = !in_word;
+                    in_word = c.is_alphanumeric();
+
+                    let mapped: Vec<char> = if in_word && first {
+                        c.to_uppercase().collect()
+                    } else {
+                        c.to_lowercase().collect()
+                    };
+
+                    mapped
+                })
+                .collect();
+
+            Ok(data::Value::Str(v))
+        })
+    }
+
+    /// Number of characters, so `LENGTH('héllo')` is `5`.
+    pub fn length(&self) -> Result<Evaluated<'a>> {
+        self.map_string("LENGTH", |v| Ok(data::Value::I64(v.chars().count() as i64)))
+    }
+
+    /// Number of bytes in UTF-8, so `OCTET_LENGTH('héllo')` is `6`.
+    pub fn octet_length(&self) -> Result<Evaluated<'a>> {
+        self.map_string("OCTET_LENGTH", |v| Ok(data::Value::I64(v.len() as i64)))
+    }
+
+    /// `SUBSTRING(self FROM start FOR len)` with 1-based character positions.
+    /// Like SQL, the window `[start, start + len)` is clamped to the string, so a start before
+    /// the first character shortens the result and a negative `len` yields an empty string.
+    pub fn substring(&self, start: i64, len: Option<i64>) -> Result<Evaluated<'a>> {
+        self.map_string("SUBSTRING", |v| {
+            let end = len.map_or(i64::MAX, |len| start.saturating_add(len.max(0)));
+            let start = start.max(1);
+
+            let v = if start < end {
+                v.chars()
+                    .skip((start - 1) as usize)
+                    .take((end - start) as usize)
+                    .collect()
+            } else {
+                String::new()
+            };
+
+            Ok(data::Value::Str(v))
+        })
+    }
+
+    /// Strips whitespace from both ends, or any of the characters in `chars` when given, so
+    /// `TRIM(BOTH 'x' FROM col)` is `col.trim(Some('x'))`.
+    pub fn trim(&self, chars: Option<&Evaluated<'a>>) -> Result<Evaluated<'a>> {
+        self.trim_with("TRIM", chars, |v, f| v.trim_matches(f))
+    }
+
+    /// Same as `trim`, but only from the start.
+    pub fn ltrim(&self, chars: Option<&Evaluated<'a>>) -> Result<Evaluated<'a>> {
+        self.trim_with("LTRIM", chars, |v, f| v.trim_start_matches(f))
+    }
+
+    /// Same as `trim`, but only from the end.
+    pub fn rtrim(&self, chars: Option<&Evaluated<'a>>) -> Result<Evaluated<'a>> {
+        self.trim_with("RTRIM", chars, |v, f| v.trim_end_matches(f))
+    }
+
+    fn trim_with(
+        &self,
+        name: &str,
+        chars: Option<&Evaluated<'a>>,
+        trim: impl for<'b> Fn(&'b str, &dyn Fn(char) -> bool) -> &'b str,
+    ) -> Result<Evaluated<'a>> {
+        let chars = match chars.map(|chars| chars.string_operand(name)).transpose()? {
+            Some(None) => return Ok(Evaluated::Literal(AstValue::Null)),
+            Some(Some(chars)) => Some(chars),
+            None => None,
+        };
+
+        self.map_string(name, |v| {
+            let v = match chars {
+                Some(chars) => trim(v, &|c| chars.contains(c)),
+                None => trim(v, &char::is_whitespace),
+            };
+
+            Ok(data::Value::Str(v.to_owned()))
+        })
+    }
+
+    /// Replaces every non-overlapping occurrence of `from` with `to`.
+    /// An empty `from` is a no-op, which matches PostgreSQL.
+    pub fn replace(&self, from: &Evaluated<'a>, to: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        let name = "REPLACE";
+        let (from, to) = match (from.string_operand(name)?, to.string_operand(name)?) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return Ok(Evaluated::Literal(AstValue::Null)),
+        };
+
+        self.map_string(name, |v| {
+            let v = if from.is_empty() {
+                v.to_owned()
+            } else {
+                v.replace(from, to)
+            };
+
+            Ok(data::Value::Str(v))
+        })
+    }
+
+    /// 1-based character position of the first `needle` in `self`, or `0` when not found.
+    /// An empty `needle` is found at `1`, which matches PostgreSQL.
+    pub fn position(&self, needle: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        let needle = match needle.string_operand("POSITION")? {
+            Some(needle) => needle,
+            None => return Ok(Evaluated::Literal(AstValue::Null)),
+        };
+
+        self.map_string("POSITION", |v| {
+            let position = v.find(needle).map_or(0, |i| v[..i].chars().count() + 1);
+
+            Ok(data::Value::I64(position as i64))
+        })
+    }
+
+    /// 1-based `index`th field of the string split on `delimiter`, or an empty string when
+    /// there are fewer fields, so `SPLIT_PART('a/b/c', '/', 2)` is `b`. An empty delimiter
+    /// fails with `EmptyDelimiter` and an index below `1` with `FieldPositionNotPositive`.
+    pub fn split_part(&self, delimiter: &Evaluated<'a>, index: i64) -> Result<Evaluated<'a>> {
+        let name = "SPLIT_PART";
+        let delimiter = match delimiter.string_operand(name)? {
+            Some("") => return Err(EvaluateError::EmptyDelimiter(name.to_owned()).into()),
+            Some(delimiter) => delimiter,
+            None => return Ok(Evaluated::Literal(AstValue::Null)),
+        };
+
+        if index < 1 {
+            return Err(EvaluateError::FieldPositionNotPositive(index).into());
+        }
+
+        self.map_string(name, |v| {
+            let field = v.split(delimiter).nth((index - 1) as usize).unwrap_or("");
+
+            Ok(data::Value::Str(field.to_owned()))
+        })
+    }
+
+    /// `FORMAT(self, args..)`, where each `%s` is replaced by the next argument and `%%` by a
+    /// single `%`, while `%` followed by anything else is kept as is. Strings are substituted
+    /// without quotes, numbers as they are written, booleans as `TRUE` or `FALSE` and NULL as an
+    /// empty string. The number of `%s` must match the number of arguments, or it fails with
+    /// `FormatArgMismatch`.
+    pub fn format(&self, args: &[Evaluated<'a>]) -> Result<Evaluated<'a>> {
+        self.map_string("FORMAT", |v| {
+            let expected = v.replace("%%", "").matches("%s").count();
+
+            if expected != args.len() {
+                return Err(EvaluateError::FormatArgMismatch {
+                    expected,
+                    found: args.len(),
+                }
+                .into());
+            }
+
+            let mut args = args.iter();
+            let mut formatted = String::with_capacity(v.len());
+            let mut chars = v.chars();
+
+            while let Some(c) = chars.next() {
+                if c != '%' {
+                    formatted.push(c);
+                    continue;
+                }
+
+                match chars.clone().next() {
+                    Some('%') => {
+                        chars.next();
+                        formatted.push('%');
+                    }
+                    Some('s') => {
+                        chars.next();
+
+                        let arg = args.next().ok_or(EvaluateError::UnreachableFormatArg)?;
+                        formatted.push_str(&arg.to_format_string()?);
+                    }
+                    _ => formatted.push('%'),
+                }
+            }
+
+            Ok(data::Value::Str(formatted))
+        })
+    }
+
+    fn to_format_string(&self) -> Result<String> {
+        if self.is_null() {
+            return Ok(String::new());
+        }
+
+        if let Some(v) = self.to_concat_string() {
+            return Ok(v);
+        }
+
+        match self.to_ast_value()? {
+            AstValue::Boolean(true) => Ok("TRUE".to_owned()),
+            AstValue::Boolean(false) => Ok("FALSE".to_owned()),
+            AstValue::SingleQuotedString(v) => Ok(v),
+            v => Ok(v.to_string()),
+        }
+    }
+
+    /// First `n` characters, a negative `n` yields an empty string.
+    pub fn left(&self, n: i64) -> Result<Evaluated<'a>> {
+        self.map_string("LEFT", |v| {
+            let v = v.chars().take(n.max(0) as usize).collect();
+
+            Ok(data::Value::Str(v))
+        })
+    }
+
+    /// Last `n` characters, a negative `n` yields an empty string.
+    pub fn right(&self, n: i64) -> Result<Evaluated<'a>> {
+        self.map_string("RIGHT", |v| {
+            let skip = v.chars().count().saturating_sub(n.max(0) as usize);
+            let v = v.chars().skip(skip).collect();
+
+            Ok(data::Value::Str(v))
+        })
+    }
+
+    /// Pads on the left with `fill`, a space by default, up to `length` characters, or truncates
+    /// to the first `length` characters when longer, so `LPAD('7', 3, '0')` is `007`. A
+    /// multi-character `fill` is repeated and cut off where the length is reached, an empty
+    /// `fill` only truncates, and a zero or negative `length` yields an empty string.
+    pub fn lpad(
+        &self,
+        length: &Evaluated<'a>,
+        fill: Option<&Evaluated<'a>>,
+    ) -> Result<Evaluated<'a>> {
+        self.pad("LPAD", length, fill, |v, padding| padding + v)
+    }
+
+    /// Same as `lpad`, but pads on the right, so `RPAD('ab', 5, 'xy')` is `abxyx`.
+    pub fn rpad(
+        &self,
+        length: &Evaluated<'a>,
+        fill: Option<&Evaluated<'a>>,
+    ) -> Result<Evaluated<'a>> {
+        self.pad("RPAD", length, fill, |v, padding| v.to_owned() + &padding)
+    }
+
+    fn pad(
+        &self,
+        name: &str,
+        length: &Evaluated<'a>,
+        fill: Option<&Evaluated<'a>>,
+        join: impl FnOnce(&str, String) -> String,
+    ) -> Result<Evaluated<'a>> {
+        let length = match length.integer_operand(name)? {
+            Some(length) => length.max(0) as usize,
+            None => return Ok(Evaluated::Literal(AstValue::Null)),
+        };
+        let fill = match fill.map(|fill| fill.string_operand(name)).transpose()? {
+            Some(Some(fill)) => fill,
+            Some(None) => return Ok(Evaluated::Literal(AstValue::Null)),
+            None => " ",
+        };
+
+        if length > MAX_STRING_LENGTH {
+            return Err(EvaluateError::StringTooLong(MAX_STRING_LENGTH).into());
+        }
+
+        self.map_string(name, |v| {
+            let count = v.chars().count();
+
+            let v = if count >= length || fill.is_empty() {
+                v.chars().take(length).collect()
+            } else {
+                join(v, fill.chars().cycle().take(length - count).collect())
+            };
+
+            match v.len() {
+                len if len <= MAX_STRING_LENGTH => Ok(data::Value::Str(v)),
+                _ => Err(EvaluateError::StringTooLong(MAX_STRING_LENGTH).into()),
+            }
+        })
+    }
+
+    /// Repeats the string `count` times, a zero or negative `count` yields an empty string.
+    /// Results longer than `MAX_STRING_LENGTH` bytes fail with `EvaluateError::StringTooLong`.
+    pub fn repeat(&self, count: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        let count = match count.integer_operand("REPEAT")? {
+            Some(count) => count.max(0) as usize,
+            None => return Ok(Evaluated::Literal(AstValue::Null)),
+        };
+
+        self.map_string("REPEAT", |v| match v.len().checked_mul(count) {
+            Some(len) if len <= MAX_STRING_LENGTH => Ok(data::Value::Str(v.repeat(count))),
+            _ => Err(EvaluateError::StringTooLong(MAX_STRING_LENGTH).into()),
+        })
+    }
+
+    /// `None` for NULL, and an error for anything else which is not an integer.
+    fn integer_operand(&self, name: &str) -> Result<Option<i64>> {
+        use data::Value::*;
+
+        if self.is_null() {
+            return Ok(None);
+        }
+
+        let integer = match self {
+            Evaluated::LiteralRef(AstValue::Number(v))
+            | Evaluated::Literal(AstValue::Number(v)) => match parse_number(v) {
+                Ok(ParsedNumber::Int(v)) => Some(v),
+                _ => None,
+            },
+            Evaluated::ValueRef(I64(v) | OptI64(Some(v)))
+            | Evaluated::Value(I64(v) | OptI64(Some(v))) => Some(*v),
+            _ => None,
+        };
+
+        integer
+            .map(Some)
+            .ok_or_else(|| EvaluateError::IntegerTypeRequired(name.to_owned()).into())
+    }
+
+    /// Reverses by Unicode scalar values, so the result is always valid UTF-8.
+    /// Grapheme clusters made of several scalars, such as `e` followed by a combining accent,
+    /// are split and come out in reverse order too.
+    pub fn reverse(&self) -> Result<Evaluated<'a>> {
+        self.map_string("REVERSE", |v| {
+            Ok(data::Value::Str(v.chars().rev().collect()))
+        })
+    }
+
+    /// Unicode code point of the first character, so `ASCII('é')` is `233`. An empty string
+    /// has no first character and yields `0`, which matches PostgreSQL.
+    pub fn ascii(&self) -> Result<Evaluated<'a>> {
+        self.map_string("ASCII", |v| {
+            let code = v.chars().next().map_or(0, |c| c as i64);
+
+            Ok(data::Value::I64(code))
+        })
+    }
+
+    /// Single character string of a Unicode code point. Surrogates and values beyond `0x10FFFF`
+    /// fail with `EvaluateError::InvalidCodePoint`, NULL is passed through.
+    pub fn chr(&self) -> Result<Evaluated<'a>> {
+        let code = match self.integer_operand("CHR")? {
+            Some(code) => code,
+            None => return Ok(Evaluated::Literal(AstValue::Null)),
+        };
+
+        u32::try_from(code)
+            .ok()
+            .and_then(char::from_u32)
+            .map(|c| Evaluated::Value(data::Value::Str(c.to_string())))
+            .ok_or_else(|| EvaluateError::InvalidCodePoint(code).into())
+    }
+
+    /// SQL `ENCODE(data, format)`, bytes or a string rendered as text in `format`, which is
+    /// `'base64'` or `'hex'`. Any other format fails with `UnsupportedEncoding`.
+    pub fn encode(&self, format: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        match encoding(format)?.as_str() {
+            "base64" => self.encode_base64(),
+            "hex" => self.to_hex(),
+            other => Err(EvaluateError::UnsupportedEncoding(other.to_owned()).into()),
+        }
+    }
+
+    /// SQL `DECODE(text, format)`, the inverse of `encode`.
+    pub fn decode(&self, format: &Evaluated<'a>) -> Result<Evaluated<'a>> {
+        match encoding(format)?.as_str() {
+            "base64" => self.decode_base64(),
+            "hex" => self.from_hex(),
+            other => Err(EvaluateError::UnsupportedEncoding(other.to_owned()).into()),
+        }
+    }
+
+    /// Standard base64 with `=` padding of bytes, or of the UTF-8 bytes of a string.
+    /// NULL is passed through.
+    pub fn encode_base64(&self) -> Result<Evaluated<'a>> {
+        match self.bytes_operand("ENCODE")? {
+            Some(v) => Ok(Evaluated::Value(data::Value::Str(base64_encode(&v)))),
+            None => Ok(Evaluated::Literal(AstValue::Null)),
+        }
+    }
+
+    /// Bytes of a standard base64 string. Whitespace, such as line breaks every 76 characters,
+    /// is ignored, while missing padding or any other character fails with `InvalidBase64`.
+    /// NULL is passed through.
+    pub fn decode_base64(&self) -> Result<Evaluated<'a>> {
+        self.map_string("DECODE", |v| {
+            base64_decode(v)
+                .map(data::Value::Bytes)
+                .ok_or_else(|| EvaluateError::InvalidBase64(v.to_owned()).into())
+        })
+    }
+
+    /// Lowercase hex digits of an integer, negative ones in two's complement, so `TO_HEX(255)`
+    /// is `'ff'` and `TO_HEX(-1)` is `'ffffffffffffffff'`. Bytes, hex literals and the UTF-8
+    /// bytes of strings give two digits per byte. NULL is passed through.
+    pub fn to_hex(&self) -> Result<Evaluated<'a>> {
+        if let Ok(Some(v)) = self.integer_operand("TO_HEX") {
+            return Ok(Evaluated::Value(data::Value::Str(format!("{:x}", v))));
+        }
+
+        match self.bytes_operand("TO_HEX")? {
+            Some(v) => Ok(Evaluated::Value(data::Value::Str(
+                upper_hex(&v).to_lowercase(),
+            ))),
+            None => Ok(Evaluated::Literal(AstValue::Null)),
+        }
+    }
+
+    /// Bytes of a string of hex digits in either case, the inverse of `to_hex` on bytes. An odd
+    /// number of digits or any other character fails with `InvalidHexLiteral`, NULL is passed
+    /// through.
+    pub fn from_hex(&self) -> Result<Evaluated<'a>> {
+        self.map_string("FROM_HEX", |v| {
+            data::literal_bytes(&AstValue::HexStringLiteral(v.to_owned()))
+                .map(|v| data::Value::Bytes(v.into_owned()))
+                .ok_or_else(|| EvaluateError::InvalidHexLiteral(v.to_owned()).into())
+        })
+    }
+
+    /// Value at `path` in a JSON document stored as a string, as for `->>`. The path starts at
+    /// the root `$` and steps into object fields with `.field` and into arrays with `[index]`,
+    /// e.g. `$.tags[0]`. Strings, numbers and booleans become the matching values, while nested
+    /// objects and arrays are returned as JSON text. A missing field or index and JSON `null`
+    /// yield NULL, malformed JSON fails with `InvalidJson` and a malformed path with
+    /// `InvalidJsonPath`.
+    pub fn json_extract(&self, path: &str) -> Result<Evaluated<'a>> {
+        use serde_json::Value as Json;
+
+        let steps =
+            json_path(path).ok_or_else(|| EvaluateError::InvalidJsonPath(path.to_owned()))?;
+        let document = match self.string_operand("JSON_EXTRACT")? {
+            Some(v) => serde_json::from_str::<Json>(v)
+                .map_err(|e| EvaluateError::InvalidJson(e.to_string()))?,
+            None => return Ok(Evaluated::Literal(AstValue::Null)),
+        };
+
+        let found = steps.iter().try_fold(&document, |json, step| match step {
+            JsonStep::Field(name) => json.get(name),
+            JsonStep::Index(index) => json.get(index),
+        });
+
+        let v = match found {
+            None | Some(Json::Null) => return Ok(Evaluated::Literal(AstValue::Null)),
+            Some(Json::Bool(v)) => data::Value::Bool(*v),
+            Some(Json::Number(v)) => return json_number(v),
+            Some(Json::String(v)) => data::Value::Str(v.clone()),
+            Some(v) => data::Value::Str(v.to_string()),
+        };
+
+        Ok(Evaluated::Value(v))
+    }
+
+    /// SQL `NORMALIZE(text, form)`, so that strings which render the same compare equal after
+    /// normalizing both into the same form. NULL is passed through.
+    pub fn normalize(&self, form: NormalizationForm) -> Result<Evaluated<'a>> {
+        self.map_string("NORMALIZE", |v| {
+            let v = match form {
+                NormalizationForm::Nfc => v.nfc().collect(),
+                NormalizationForm::Nfd => v.nfd().collect(),
+                NormalizationForm::Nfkc => v.nfkc().collect(),
+                NormalizationForm::Nfkd => v.nfkd().collect(),
+            };
+
+            Ok(data::Value::Str(v))
+        })
+    }
+
+    /// Applies `f` on the string operand, NULL is passed through.
+    fn map_string(
+        &self,
+        name: &str,
+        f: impl FnOnce(&str) -> Result<data::Value>,
+    ) -> Result<Evaluated<'a>> {
+        match self.string_operand(name)? {
+            Some(v) => f(v).map(Evaluated::Value),
+            None => Ok(Evaluated::Literal(AstValue::Null)),
+        }
+    }
+
+    /// `None` for NULL, and an error for anything else which is not a string.
+    fn string_operand(&self, name: &str) -> Result<Option<&str>> {
+        if self.is_null() {
+            return Ok(None);
+        }
+
+        self.as_str()
+            .map(Some)
+            .ok_or_else(|| EvaluateError::StringFunctionOnNonString(name.to_owned()).into())
+    }
+
+    /// Bytes values and hex literals as they are, and strings as their UTF-8 bytes. `None` for
+    /// NULL, and an error for anything else.
+    fn bytes_operand(&self, name: &str) -> Result<Option<Cow<'_, [u8]>>> {
+        use Evaluated::*;
+
+        if self.is_null() {
+            return Ok(None);
+        }
+
+        let bytes = match self {
+            ValueRef(data::Value::Bytes(v) | data::Value::OptBytes(Some(v)))
+            | Value(data::Value::Bytes(v) | data::Value::OptBytes(Some(v))) => {
+                Some(Cow::Borrowed(v.as_slice()))
+            }
+            LiteralRef(v @ AstValue::HexStringLiteral(_)) => data::literal_bytes(v),
+            Literal(v @ AstValue::HexStringLiteral(_)) => data::literal_bytes(v),
+            _ => self.as_str().map(|v| Cow::Borrowed(v.as_bytes())),
+        };
+
+        bytes
+            .map(Some)
+            .ok_or_else(|| EvaluateError::StringFunctionOnNonString(name.to_owned()).into())
+    }
+
+    /// SQL `LIKE`, `%` matches any sequence and `_` matches a single character.
+    /// The `escape` character makes the following wildcard literal, so with `\` as the escape
+    /// `100\%` matches `100%` only. Followed by any other character, including itself, the escape
+    /// is dropped and that character is matched as is, while an escape at the end of the pattern
+    /// fails with `LikePatternEndsWithEscape`. NULL on either side never matches.
+    pub fn like(
+        &self,
+        pattern: &Evaluated<'a>,
+        case_sensitive: bool,
+        escape: Option<char>,
+    ) -> Result<bool> {
+        if self.is_null() || pattern.is_null() {
+            return Ok(false);
+        }
+
+        match (self.as_str(), pattern.as_str()) {
+            (Some(target), Some(pattern)) => {
+                let tokens = like_tokens(pattern, escape, case_sensitive)?;
+
+                Ok(if case_sensitive {
+                    like_match(target, &tokens)
+                } else {
+                    like_match(&target.to_lowercase(), &tokens)
+                })
+            }
+            _ => Err(EvaluateError::LikeOnNonString.into()),
+        }
+    }
+
+    /// POSIX-style `~` and `~*`, true when `pattern` matches anywhere in the string, so anchors
+    /// are needed for a whole-string match. Patterns compiled on the current thread are cached.
+    /// NULL on either side never matches, and a pattern which does not compile fails with
+    /// `InvalidRegex`.
+    pub fn regex_match(&self, pattern: &Evaluated<'a>, case_insensitive: bool) -> Result<bool> {
+        if self.is_null() || pattern.is_null() {
+            return Ok(false);
+        }
+
+        let (target, pattern) = match (self.as_str(), pattern.as_str()) {
+            (Some(target), Some(pattern)) => (target, pattern),
+            _ => return Err(EvaluateError::StringFunctionOnNonString("REGEXP".to_owned()).into()),
+        };
+
+        REGEX_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let key = (pattern.to_owned(), case_insensitive);
+
+            if let Some(regex) = cache.get(&key) {
+                return Ok(regex.is_match(target));
+            }
+
+            let regex = RegexBuilder::new(pattern)
+                .case_insensitive(case_insensitive)
+                .build()
+                .map_err(|e| EvaluateError::InvalidRegex(e.to_string()))?;
+            let found = regex.is_match(target);
+
+            if cache.len() >= REGEX_CACHE_SIZE {
+                cache.clear();
+            }
+
+            cache.insert(key, regex);
+
+            Ok(found)
+        })
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        use Evaluated::*;
+
+        let value = |v: &'a data::Value| match v {
+            data::Value::Str(v) | data::Value::OptStr(Some(v)) => Some(v.as_str()),
+            _ => None,
+        };
+
+        match self {
+            LiteralRef(AstValue::SingleQuotedString(v))
+            | Literal(AstValue::SingleQuotedString(v)) => Some(v.as_str()),
+            StringRef(v) => Some(v),
+            ValueRef(v) => value(v),
+            Value(data::Value::Str(v)) | Value(data::Value::OptStr(Some(v))) => Some(v.as_str()),
+            _ => None,
+        }
+    }
+
+    /// String form used by `||`, numbers are rendered as they are written.
+    fn to_concat_string(&self) -> Option<String> {
+        use Evaluated::*;
+
+        let literal = |v: &AstValue| match v {
+            AstValue::SingleQuotedString(v) | AstValue::Number(v) => Some(v.to_owned()),
+            _ => None,
+        };
+
+        let value = |v: &data::Value| match v {
+            data::Value::Str(v) | data::Value::OptStr(Some(v)) => Some(v.to_owned()),
+            data::Value::I64(v) | data::Value::OptI64(Some(v)) => Some(v.to_string()),
+            data::Value::F64(v) | data::Value::OptF64(Some(v)) => Some(float_to_string(*v)),
+            data::Value::Decimal(v) | data::Value::OptDecimal(Some(v)) => Some(v.to_string()),
+            _ => None,
+        };
+
+        match self {
+            LiteralRef(v) => literal(v),
+            Literal(v) => literal(v),
+            StringRef(v) => Some((*v).to_owned()),
+            ValueRef(v) => value(v),
+            Value(v) => value(v),
+        }
+    }
+}
+
+enum JsonStep {
+    Field(String),
+    Index(usize),
+}
+
+/// A JSON number as a value. Integers become `I64`, except those beyond `i64` which are kept
+/// exact as number literals, and other numbers become `F64`.
+fn json_number(v: &serde_json::Number) -> Result<Evaluated<'static>> {
+    match (v.as_i64(), v.as_u64(), v.as_f64()) {
+        (Some(v), _, _) => Ok(Evaluated::Value(data::Value::I64(v))),
+        (None, Some(v), _) => Ok(Evaluated::Literal(AstValue::Number(v.to_string()))),
+        (None, None, Some(v)) => Ok(Evaluated::Value(data::Value::F64(v))),
+        (None, None, None) => Err(EvaluateError::UnrepresentableJson(v.to_string()).into()),
+    }
+}
+
+/// Steps of a `$.field[index]` path, `None` when it is malformed. Field names run up to the
+/// next `.` or `[`, so they cannot contain either.
+fn json_path(path: &str) -> Option<Vec<JsonStep>> {
+    let mut rest = path.trim().strip_prefix('$')?;
+    let mut steps = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(field) = rest.strip_prefix('.') {
+            let end = field.find(['.', '[']).unwrap_or(field.len());
+
+            if end == 0 {
+                return None;
+            }
+
+            steps.push(JsonStep::Field(field[..end].to_owned()));
+            rest = &field[end..];
+        } else {
+            let (index, tail) = rest.strip_prefix('[')?.split_once(']')?;
+
+            steps.push(JsonStep::Index(index.trim().parse().ok()?));
+            rest = tail;
+        }
+    }
+
+    Some(steps)
+}
+
+/// Name of an `ENCODE` or `DECODE` format, case-insensitive.
+fn encoding(format: &Evaluated<'_>) -> Result<String> {
+    format
+        .as_str()
+        .map(str::to_lowercase)
+        .ok_or_else(|| EvaluateError::StringFunctionOnNonString("ENCODE".to_owned()).into())
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(v: &[u8]) -> String {
+    let mut encoded = String::with_capacity(v.len().div_ceil(3) * 4);
+
+    for chunk in v.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0_u32, |bits, (i, byte)| {
+            bits | (*byte as u32) << (16 - 8 * i)
+        });
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (bits >> (18 - 6 * i)) & 0x3f;
+
+                encoded.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+fn base64_decode(v: &str) -> Option<Vec<u8>> {
+    let v = v
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect::<Vec<_>>();
+
+    if v.len() % 4 != 0 {
+        return None;
+    }
+
+    let padding = v.iter().rev().take_while(|c| **c == b'=').count();
+
+    if padding > 2 {
+        return None;
+    }
+
+    let mut decoded = Vec::with_capacity(v.len() / 4 * 3);
+
+    for (n, chunk) in v.chunks(4).enumerate() {
+        let is_last = (n + 1) * 4 == v.len();
+        let digits = if is_last { 4 - padding } else { 4 };
+
+        let bits = chunk[..digits]
+            .iter()
+            .enumerate()
+            .try_fold(0_u32, |bits, (i, c)| {
+                let index = BASE64_ALPHABET.iter().position(|a| a == c)?;
+
+                Some(bits | (index as u32) << (18 - 6 * i))
+            })?;
+
+        decoded.extend((0..digits - 1).map(|i| (bits >> (16 - 8 * i)) as u8));
+    }
+
+    Some(decoded)
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+
+    a
+}
+
+fn shift_amount(v: i64) -> Result<u32> {
+    if (0..64).contains(&v) {
+        Ok(v as u32)
+    } else {
+        Err(EvaluateError::ShiftOutOfRange(v).into())
+    }
+}
+
+/// Upper bound in bytes for strings built by functions such as `repeat`.
+const MAX_STRING_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Number of compiled patterns kept by `regex_match` on each thread before the cache is reset.
+const REGEX_CACHE_SIZE: usize = 64;
+
+thread_local! {
+    static REGEX_CACHE: RefCell<HashMap<(String, bool), Regex>> = RefCell::new(HashMap::new());
+}
+
+enum CastValue<'a> {
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    Decimal(rust_decimal::Decimal),
+    Str(&'a str),
+}
+
+enum LikeToken {
+    Any,
+    One,
+    Char(char),
+}
+
+/// Escapes are resolved before lowercasing, so an uppercase escape character still works in a
+/// case-insensitive match.
+fn like_tokens(
+    pattern: &str,
+    escape: Option<char>,
+    case_sensitive: bool,
+) -> Result<Vec<LikeToken>> {
+    let mut tokens = vec![];
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        let c = match c {
+            c if Some(c) == escape => chars
+                .next()
+                .ok_or(EvaluateError::LikePatternEndsWithEscape)?,
+            '%' => {
+                tokens.push(LikeToken::Any);
+                continue;
+            }
+            '_' => {
+                tokens.push(LikeToken::One);
+                continue;
+            }
+            c => c,
+        };
+
+        if case_sensitive {
+            tokens.push(LikeToken::Char(c));
+        } else {
+            tokens.extend(c.to_lowercase().map(LikeToken::Char));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// On a mismatch only the last `%` seen is retried one character further, as an earlier `%`
+/// could not match anything the last one cannot. So matching takes at most about
+/// `target * tokens` steps however many `%` the pattern has.
+fn like_match(target: &str, tokens: &[LikeToken]) -> bool {
+    let target = target.chars().collect::<Vec<_>>();
+    let (mut t, mut p) = (0, 0);
+    let mut last_any = None;
+
+    while t < target.len() {
+        match tokens.get(p) {
+            Some(LikeToken::Any) => {
+                last_any = Some((p, t));
+                p += 1;
+            }
+            Some(LikeToken::One) => {
+                p += 1;
+                t += 1;
+            }
+            Some(LikeToken::Char(c)) if *c == target[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match last_any {
+                Some((any, start)) => {
+                    last_any = Some((any, start + 1));
+                    p = any + 1;
+                    t = start + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    tokens[p..].iter().all(|v| matches!(v, LikeToken::Any))
+}
+
+/// Integer literals are combined as `i64`. When either side only fits `u64`, e.g. IDs above
+/// `i64::MAX`, `wide_op` is tried next in `i128`, so `-1 + 9223372036854775808` is exact. Overflow fails with `NumericOverflow` in both, as does
+/// a wide result outside `i64::MIN..=u64::MAX`. Anything else which parses as `f64` falls back
+/// to `float_op`.
+fn literal_number_binop(
+    a: &AstValue,
+    b: &AstValue,
+    non_numeric: EvaluateError,
+    op: impl Fn(i64, i64) -> Option<i64>,
+    wide_op: fn(i128, i128) -> Option<i128>,
+    float_op: impl Fn(f64, f64) -> f64,
+) -> Result<AstValue> {
+    let (a, b) = match (a, b) {
+        (AstValue::Number(a), AstValue::Number(b)) => (a, b),
+        _ => return Err(non_numeric.into()),
+    };
+
+    let overflow = || data::ValueError::NumericOverflow.into();
+    let (a, b) = (parse_number(a)?, parse_number(b)?);
+
+    if let (ParsedNumber::Int(a), ParsedNumber::Int(b)) = (a, b) {
+        return op(a, b)
+            .map(|v| ParsedNumber::Int(v).into_literal())
+            .ok_or_else(overflow);
+    }
+
+    if let (Some(a), Some(b)) = (a.to_i128(), b.to_i128()) {
+        return wide_op(a, b)
+            .and_then(ParsedNumber::from_i128)
+            .map(ParsedNumber::into_literal)
+            .ok_or_else(overflow);
+    }
+
+    Ok(ParsedNumber::Float(float_op(a.to_f64(), b.to_f64())).into_literal())
+}
+
+fn literal_add(a: &AstValue, b: &AstValue) -> Result<AstValue> {
+    literal_number_binop(
+        a,
+        b,
+        EvaluateError::LiteralAddOnNonNumeric,
+        i64::checked_add,
+        i128::checked_add,
+        |a, b| a + b,
+    )
+}
+
+fn literal_subtract(a: &AstValue, b: &AstValue) -> Result<AstValue> {
+    literal_number_binop(
+        a,
+        b,
+        EvaluateError::LiteralSubtractOnNonNumeric,
+        i64::checked_sub,
+        i128::checked_sub,
+        |a, b| a - b,
+    )
+}
+
+fn literal_multiply(a: &AstValue, b: &AstValue) -> Result<AstValue> {
+    literal_number_binop(
+        a,
+        b,
+        EvaluateError::LiteralMultiplyOnNonNumeric,
+        i64::checked_mul,
+        i128::checked_mul,
+        |a, b| a * b,
+    )
+}
+
+fn literal_divide(a: &AstValue, b: &AstValue) -> Result<AstValue> {
+    check_literal_divisor(a, b)?;
+
+    literal_number_binop(
+        a,
+        b,
+        EvaluateError::LiteralDivideOnNonNumeric,
+        i64::checked_div,
+        i128::checked_div,
+        |a, b| a / b,
+    )
+}
+
+/// `i64::MIN % -1` overflows in `checked_rem` although the remainder is simply `0`.
+fn literal_modulo(a: &AstValue, b: &AstValue) -> Result<AstValue> {
+    check_literal_divisor(a, b)?;
+
+    literal_number_binop(
+        a,
+        b,
+        EvaluateError::LiteralModuloOnNonNumeric,
+        |a, b| if b == -1 { Some(0) } else { a.checked_rem(b) },
+        i128::checked_rem,
+        |a, b| a % b,
+    )
+}
+
+fn check_literal_divisor(a: &AstValue, b: &AstValue) -> Result<()> {
+    match (a, b) {
+        (AstValue::Number(_), AstValue::Number(v)) if v.parse::<f64>() == Ok(0.0) => {
+            Err(data::ValueError::DivideByZero.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// `-9223372036854775808` reaches here as the minus of a literal which does not fit `i64`,
+/// so a `u64` literal is negated into `i64` when it fits before falling back to `f64`.
+/// The other way around, negating `i64::MIN` gives a `u64` literal.
+fn literal_unary_minus(v: &AstValue) -> Result<AstValue> {
+    use ParsedNumber::*;
+
+    match v {
+        AstValue::Number(v) => Ok(match parse_number(v)? {
+            Int(v) => v.checked_neg().map_or(UInt(v.unsigned_abs()), Int),
+            UInt(v) => 0_i64
+                .checked_sub_unsigned(v)
+                .map_or(Float(-(v as f64)), Int),
+            Float(v) => Float(-v),
+        }
+        .into_literal()),
+        AstValue::Null => Ok(AstValue::Null),
+        _ => Err(data::ValueError::UnaryMinusOnNonNumeric.into()),
+    }
+}
+
+fn literal_abs(v: &AstValue) -> Result<AstValue> {
+    use ParsedNumber::*;
+
+    match v {
+        AstValue::Number(v) => Ok(match parse_number(v)? {
+            Int(v) => v.checked_abs().map_or(UInt(v.unsigned_abs()), Int),
+            UInt(v) => UInt(v),
+            Float(v) => Float(v.abs()),
+        }
+        .into_literal()),
+        AstValue::Null => Ok(AstValue::Null),
+        _ => Err(data::ValueError::AbsOnNonNumeric.into()),
+    }
+}
+
+/// Converts `literal` into the type of `value`, except that a float literal meeting an integer
+/// value stays a float so that the arithmetic promotes to float instead of failing.
+fn cast_literal(value: &Value, literal: &AstValue) -> Result<Value> {
+    match (value, literal) {
+        (Value::I64(_), AstValue::Number(v)) | (Value::OptI64(_), AstValue::Number(v))
+            if v.parse::<i64>().is_err() =>
+        {
+            Value::try_from(literal)
+        }
+        (_, AstValue::Number(_)) if value.is_temporal() => Value::try_from(literal),
+        _ => value.clone_by(literal),
+    }
+}
+
+/// Decimal places for `round_with` and `trunc`, `0` when omitted. Every `f64` is within 400
+/// digits of the point either way, so larger counts are clamped without changing the result.
+fn rounding_places(places: Option<i64>) -> i32 {
+    places.unwrap_or(0).clamp(-400, 400) as i32
+}
+
+/// Rounds `v` by `mode` at `places` decimal digits, negative `places` round left of the point.
+/// The digits are those of the shortest form of `v` as it is printed rather than of its binary
+/// value, so `0.29` truncated at two places stays `0.29` and `1.005` rounds up to `1.01`.
+/// Values or places beyond the range of `Decimal` fall back to scaling the float.
+fn round_float(v: f64, places: i32, mode: RoundingMode) -> f64 {
+    use rust_decimal::Decimal;
+
+    let decimal = || {
+        let v = Decimal::from_str_exact(&v.to_string()).ok()?;
+
+        round_decimal(v, places, mode)?.to_f64()
+    };
+
+    if !v.is_finite() {
+        return v;
+    }
+
+    decimal().unwrap_or_else(|| {
+        let scale = 10_f64.powi(places);
+
+        if scale == 0.0 {
+            return v * 0.0;
+        }
+
+        let scaled = v * scale;
+        let rounded = match mode {
+            RoundingMode::HalfUp => scaled.round(),
+            RoundingMode::HalfEven => scaled.round_ties_even(),
+            RoundingMode::TowardZero => scaled.trunc(),
+            RoundingMode::AwayFromZero if scaled < 0.0 => scaled.floor(),
+            RoundingMode::AwayFromZero => scaled.ceil(),
+        } / scale;
+
+        if rounded.is_finite() {
+            rounded
+        } else {
+            v
+        }
+    })
+}
+
+/// Rounds `v` by `mode` at `places` decimal digits, negative `places` round left of the point.
+/// `None` when rounding left of the point overflows `Decimal`.
+fn round_decimal(
+    v: rust_decimal::Decimal,
+    places: i32,
+    mode: RoundingMode,
+) -> Option<rust_decimal::Decimal> {
+    use rust_decimal::{Decimal, RoundingStrategy};
+
+    let strategy = match mode {
+        RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+        RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+        RoundingMode::TowardZero => RoundingStrategy::ToZero,
+        RoundingMode::AwayFromZero => RoundingStrategy::AwayFromZero,
+    };
+
+    match u32::try_from(places) {
+        Ok(places) => Some(v.round_dp_with_strategy(places, strategy)),
+        Err(_) => {
+            let scale = Decimal::from_scientific(&format!("1e{}", -places)).ok()?;
+
+            v.checked_div(scale)?
+                .round_dp_with_strategy(0, strategy)
+                .checked_mul(scale)
+        }
+    }
+}
+
+/// `Debug` keeps the fractional part (`5.0` rather than `5`), so that the result is parsed back
+/// as a float and `2.5 + 2.5` does not silently turn into an integer literal.
+fn float_to_string(v: f64) -> String {
+    format!("{:?}", v)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::ast::Value as AstValue;
+    use std::cmp::Ordering;
+
+    use super::{
+        Collation, ComparisonMode, DivisionMode, Evaluated, NormalizationForm, OverflowMode,
+        RoundingMode,
+    };
+    use crate::data::{Value, ValueError};
+    use crate::executor::EvaluateError;
+
+    fn number(v: &str) -> AstValue {
+        AstValue::Number(v.to_owned())
+    }
+
+    #[test]
+    fn eq_and_partial_cmp_on_every_variant() {
+        use std::cmp::Ordering;
+
+        let one = number("1");
+        let value_one = Value::I64(1);
+        let numbers = || {
+            vec![
+                Evaluated::LiteralRef(&one),
+                Evaluated::Literal(one.clone()),
+                Evaluated::ValueRef(&value_one),
+                Evaluated::Value(value_one.clone()),
+            ]
+        };
+
+        for l in numbers() {
+            for r in numbers() {
+                assert!(l == r);
+                assert_eq!(l.partial_cmp(&r), Some(Ordering::Equal));
+            }
+        }
+
+        let glue = AstValue::SingleQuotedString("Glue".to_owned());
+        let value_glue = Value::Str("Glue".to_owned());
+        let strings = || {
+            vec![
+                Evaluated::LiteralRef(&glue),
+                Evaluated::Literal(glue.clone()),
+                Evaluated::StringRef("Glue"),
+                Evaluated::ValueRef(&value_glue),
+                Evaluated::Value(value_glue.clone()),
+            ]
+        };
+
+        for l in strings() {
+            for r in strings() {
+                assert!(l == r);
+            }
+        }
+
+        let two = number("2");
+        assert_eq!(
+            Evaluated::Literal(one.clone()).partial_cmp(&Evaluated::LiteralRef(&two)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Evaluated::LiteralRef(&two).partial_cmp(&Evaluated::Literal(one.clone())),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn negative_literals() {
+        let minus = |v: &str| Evaluated::Literal(number(v)).unary_minus().unwrap();
+
+        assert!(minus("5") < minus("3"));
+        assert!(minus("5") == minus("5"));
+        assert!(minus("0") == Evaluated::Literal(number("0")));
+        assert!(minus("1.5") < minus("1"));
+        assert!(minus("9223372036854775808") < minus("9223372036854775807"));
+        assert_eq!(
+            minus("9223372036854775808").to_value(),
+            Ok(Value::I64(i64::MIN))
+        );
+
+        let stored = Value::I64(2);
+        assert!(minus("3") < Evaluated::ValueRef(&stored));
+        assert!(Evaluated::ValueRef(&stored) > minus("3"));
+
+        let stored = Value::OptI64(Some(-3));
+        assert!(minus("3") == Evaluated::ValueRef(&stored));
+        assert!(Evaluated::ValueRef(&stored) < minus("1"));
+    }
+
+    #[test]
+    fn partial_cmp_float_literals() {
+        use std::cmp::Ordering;
+
+        let cmp = |l: &str, r: &str| {
+            let (l, r) = (number(l), number(r));
+
+            Evaluated::LiteralRef(&l).partial_cmp(&Evaluated::LiteralRef(&r))
+        };
+
+        assert_eq!(cmp("3", "3.5"), Some(Ordering::Less));
+        assert_eq!(cmp("3.5", "3"), Some(Ordering::Greater));
+        assert_eq!(cmp("2.5", "2.50"), Some(Ordering::Equal));
+        assert_eq!(cmp("NaN", "1.0"), None);
+    }
+
+    #[test]
+    fn concat() {
+        let glue = AstValue::SingleQuotedString("Glue".to_owned());
+        let one = number("1");
+        let sql = Value::Str("SQL".to_owned());
+
+        let found = Evaluated::LiteralRef(&glue).concat(&Evaluated::ValueRef(&sql));
+        assert_eq!(found, Ok(Evaluated::StringRef("GlueSQL")));
+
+        let found = Evaluated::StringRef("Glue").concat(&Evaluated::Value(sql.clone()));
+        assert_eq!(found, Ok(Evaluated::StringRef("GlueSQL")));
+
+        let found = Evaluated::StringRef("Glue").concat(&Evaluated::LiteralRef(&one));
+        assert_eq!(found, Ok(Evaluated::StringRef("Glue1")));
+
+        let found = Evaluated::Value(Value::F64(1.5)).concat(&Evaluated::StringRef("%"));
+        assert_eq!(found, Ok(Evaluated::StringRef("1.5%")));
+
+        let found = Evaluated::Value(Value::F64(3.0)).concat(&Evaluated::StringRef("x"));
+        assert_eq!(found, Ok(Evaluated::StringRef("3.0x")));
+
+        let found = Evaluated::Value(Value::Bool(true)).concat(&Evaluated::StringRef("Glue"));
+        assert_eq!(found, Err(EvaluateError::UnsupportedConcat.into()));
+
+        let null = AstValue::Null;
+        let found = Evaluated::StringRef("Glue").concat(&Evaluated::LiteralRef(&null));
+        assert!(found.unwrap().is_null());
+
+        let found = Evaluated::Value(Value::OptStr(None)).concat(&Evaluated::ValueRef(&sql));
+        assert!(found.unwrap().is_null());
+    }
+
+    #[test]
+    fn eq_float_value() {
+        let one = number("1");
+        let ratio = Value::OptF64(Some(1.0));
+
+        assert!(Evaluated::ValueRef(&ratio) == Evaluated::LiteralRef(&one));
+        assert!(Evaluated::LiteralRef(&one) == Evaluated::ValueRef(&ratio));
+        assert!(Evaluated::Value(Value::F64(2.0)) == Evaluated::Literal(number("2")));
+        assert!(Evaluated::Value(Value::F64(1.5)) != Evaluated::LiteralRef(&one));
+        assert!(Evaluated::Literal(number("2")) != Evaluated::ValueRef(&ratio));
+        assert_eq!(
+            Evaluated::ValueRef(&ratio).try_eq(&Evaluated::Literal(number("1.0"))),
+            Ok(true)
+        );
+
+        let null = Value::OptF64(None);
+        assert!(Evaluated::ValueRef(&null) != Evaluated::LiteralRef(&one));
+        assert!(Evaluated::ValueRef(&ratio) != Evaluated::Literal(AstValue::Null));
+    }
+
+    #[test]
+    fn compare() {
+        let incomparable = |left: &str, right: &str| {
+            Err(EvaluateError::IncomparableValues {
+                left: left.to_owned(),
+                right: right.to_owned(),
+            }
+            .into())
+        };
+        let one = number("1");
+        let value = Value::F64(1.5);
+
+        assert_eq!(
+            Evaluated::LiteralRef(&one).compare(&Evaluated::ValueRef(&value)),
+            Ok(Ordering::Less)
+        );
+        assert_eq!(
+            Evaluated::StringRef("b").compare(&Evaluated::Value(Value::Str("a".to_owned()))),
+            Ok(Ordering::Greater)
+        );
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).compare(&Evaluated::LiteralRef(&one)),
+            Ok(Ordering::Equal)
+        );
+
+        assert_eq!(
+            Evaluated::StringRef("a").compare(&Evaluated::LiteralRef(&one)),
+            incomparable("'a'", "1")
+        );
+        assert_eq!(
+            Evaluated::ValueRef(&value).compare(&Evaluated::StringRef("1.5")),
+            Ok(Ordering::Equal)
+        );
+
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let nine = Evaluated::Literal(number("9"));
+        assert_eq!(text("10").partial_cmp(&nine), None);
+        assert_eq!(text("10").compare(&nine), Ok(Ordering::Greater));
+        assert_eq!(nine.compare(&text(" 10 ")), Ok(Ordering::Less));
+        assert_eq!(
+            Evaluated::Value(Value::I64(9)).compare(&text("9.0")),
+            Ok(Ordering::Equal)
+        );
+        assert_eq!(text("abc").partial_cmp(&nine), None);
+        assert_eq!(text("abc").compare(&nine), incomparable("'abc'", "9"));
+        assert_eq!(nine.compare(&text("9a")), incomparable("9", "'9a'"));
+        assert_eq!(
+            Evaluated::LiteralRef(&one).compare(&Evaluated::Literal(AstValue::Null)),
+            incomparable("1", "NULL")
+        );
+        assert_eq!(
+            Evaluated::Value(Value::OptI64(None)).compare(&Evaluated::Value(Value::OptI64(None))),
+            incomparable("NULL", "NULL")
+        );
+        assert_eq!(
+            Evaluated::Value(Value::F64(f64::NAN)).compare(&Evaluated::ValueRef(&value)),
+            incomparable("NaN", "1.5")
+        );
+
+        let mut items = [
+            Evaluated::Value(Value::I64(3)),
+            Evaluated::LiteralRef(&one),
+            Evaluated::ValueRef(&value),
+        ];
+        let mut error = None;
+        items.sort_by(|a, b| {
+            a.compare(b).unwrap_or_else(|e| {
+                error = Some(e);
+                Ordering::Equal
+            })
+        });
+        assert_eq!(error, None);
+        assert_eq!(
+            items.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["1", "1.5", "3"]
+        );
+    }
+
+    #[test]
+    fn order_cmp() {
+        let two = number("2");
+        let null = Value::OptI64(None);
+        let sorted = |nulls_first: bool| {
+            let mut items = [
+                Evaluated::ValueRef(&null),
+                Evaluated::Value(Value::I64(3)),
+                Evaluated::Literal(AstValue::Null),
+                Evaluated::LiteralRef(&two),
+                Evaluated::Value(Value::OptF64(Some(-1.5))),
+            ];
+            items.sort_by(|a, b| a.order_cmp(b, nulls_first));
+
+            items.iter().map(ToString::to_string).collect::<Vec<_>>()
+        };
+
+        assert_eq!(sorted(true), vec!["NULL", "NULL", "-1.5", "2", "3"]);
+        assert_eq!(sorted(false), vec!["-1.5", "2", "3", "NULL", "NULL"]);
+
+        let null = Evaluated::Literal(AstValue::Null);
+        let two = Evaluated::LiteralRef(&two);
+        assert_eq!(null.order_cmp(&two, true), Ordering::Less);
+        assert_eq!(null.order_cmp(&two, false), Ordering::Greater);
+        assert_eq!(two.order_cmp(&null, true), Ordering::Greater);
+        assert_eq!(two.order_cmp(&null, false), Ordering::Less);
+        assert_eq!(null.order_cmp(&null, false), Ordering::Equal);
+        assert_eq!(
+            two.order_cmp(&Evaluated::Value(Value::F64(2.0)), true),
+            Ordering::Equal
+        );
+
+        let nan = Evaluated::Value(Value::F64(f64::NAN));
+        let text = Evaluated::StringRef("a");
+        let boolean = Evaluated::Value(Value::Bool(true));
+        assert_eq!(nan.order_cmp(&two, true), Ordering::Greater);
+        assert_eq!(nan.order_cmp(&nan, true), Ordering::Equal);
+        assert_eq!(text.order_cmp(&nan, true), Ordering::Greater);
+        assert_eq!(text.order_cmp(&two, false), Ordering::Greater);
+        assert_eq!(boolean.order_cmp(&two, false), Ordering::Less);
+        assert_eq!(null.order_cmp(&text, false), Ordering::Greater);
+    }
+
+    #[test]
+    fn concat_ws() {
+        let concat_ws = |sep: Evaluated<'_>, items: Vec<Evaluated<'_>>| {
+            Evaluated::concat_ws(&sep, &items).and_then(|v| v.to_value())
+        };
+        let null = || Evaluated::Literal(AstValue::Null);
+        let text = |v: &str| Value::Str(v.to_owned());
+        let sql = Value::OptStr(Some("SQL".to_owned()));
+
+        let found = concat_ws(
+            Evaluated::StringRef(", "),
+            vec![
+                Evaluated::StringRef("Glue"),
+                Evaluated::ValueRef(&sql),
+                Evaluated::Literal(number("1")),
+                Evaluated::Value(Value::F64(1.5)),
+                Evaluated::Value(Value::Decimal("0.10".parse().unwrap())),
+            ],
+        );
+        assert_eq!(found, Ok(text("Glue, SQL, 1, 1.5, 0.10")));
+
+        let found = concat_ws(
+            Evaluated::StringRef("-"),
+            vec![
+                null(),
+                Evaluated::StringRef("a"),
+                Evaluated::Value(Value::OptI64(None)),
+                Evaluated::StringRef("b"),
+                null(),
+            ],
+        );
+        assert_eq!(found, Ok(text("a-b")));
+
+        let found = concat_ws(Evaluated::StringRef("-"), vec![null(), null()]);
+        assert_eq!(found, Ok(text("")));
+        assert_eq!(concat_ws(Evaluated::StringRef("-"), vec![]), Ok(text("")));
+
+        let found = concat_ws(null(), vec![Evaluated::StringRef("a")]);
+        assert_eq!(found, Ok(Value::Empty));
+
+        assert_eq!(
+            concat_ws(
+                Evaluated::Literal(number("1")),
+                vec![Evaluated::StringRef("a")]
+            ),
+            Err(EvaluateError::StringFunctionOnNonString("CONCAT_WS".to_owned()).into())
+        );
+        assert_eq!(
+            concat_ws(
+                Evaluated::StringRef("-"),
+                vec![Evaluated::Value(Value::Bool(true))]
+            ),
+            Err(EvaluateError::UnsupportedConcat.into())
+        );
+    }
+
+    #[test]
+    fn unary_minus() {
+        let five = number("5");
+        let found = Evaluated::LiteralRef(&five).unary_minus();
+        assert_eq!(found, Ok(Evaluated::Literal(number("-5"))));
+
+        let found = Evaluated::Literal(number("-1.5")).unary_minus();
+        assert_eq!(found, Ok(Evaluated::Literal(number("1.5"))));
+
+        let found = Evaluated::ValueRef(&Value::OptI64(Some(3))).unary_minus();
+        assert_eq!(found, Ok(Evaluated::Value(Value::OptI64(Some(-3)))));
+
+        let found = Evaluated::Value(Value::F64(2.5)).unary_minus();
+        assert_eq!(found, Ok(Evaluated::Value(Value::F64(-2.5))));
+
+        let found = Evaluated::StringRef("Glue").unary_minus();
+        assert_eq!(found, Err(ValueError::UnaryMinusOnNonNumeric.into()));
+
+        let overflow = Err(ValueError::NumericOverflow.into());
+        assert_eq!(
+            Evaluated::Value(Value::I64(i64::MIN)).unary_minus(),
+            overflow
+        );
+        assert_eq!(
+            Evaluated::ValueRef(&Value::OptI64(Some(i64::MIN))).unary_minus(),
+            overflow
+        );
+        assert_eq!(Evaluated::Value(Value::I64(i64::MIN)).abs(), overflow);
+
+        let found = Evaluated::Value(Value::I64(i64::MIN + 1)).unary_minus();
+        assert_eq!(found, Ok(Evaluated::Value(Value::I64(i64::MAX))));
+
+        let found = Evaluated::Value(Value::I64(-7)).unary_minus();
+        assert_eq!(found, Ok(Evaluated::Value(Value::I64(7))));
+
+        let found = Evaluated::Value(Value::OptI64(None)).unary_minus();
+        assert!(matches!(found, Ok(Evaluated::Value(Value::OptI64(None)))));
+
+        let found = Evaluated::Literal(number("-9223372036854775808")).unary_minus();
+        assert_eq!(found, Ok(Evaluated::Literal(number("9223372036854775808"))));
+    }
+
+    #[test]
+    fn unary_plus_and_abs() {
+        let five = number("-5");
+        let found = Evaluated::LiteralRef(&five).unary_plus();
+        assert_eq!(found, Ok(Evaluated::LiteralRef(&five)));
+
+        let found = Evaluated::LiteralRef(&five).abs();
+        assert_eq!(found, Ok(Evaluated::Literal(number("5"))));
+
+        let found = Evaluated::Literal(number("-2.5")).abs();
+        assert_eq!(found, Ok(Evaluated::Literal(number("2.5"))));
+
+        let found = Evaluated::ValueRef(&Value::OptI64(Some(-3))).abs();
+        assert_eq!(found, Ok(Evaluated::Value(Value::OptI64(Some(3)))));
+
+        let found = Evaluated::Value(Value::F64(-1.5)).abs();
+        assert_eq!(found, Ok(Evaluated::Value(Value::F64(1.5))));
+
+        let found = Evaluated::StringRef("Glue").unary_plus();
+        assert_eq!(found, Err(EvaluateError::UnaryPlusOnNonNumeric.into()));
+
+        let glue = Value::Str("Glue".to_owned());
+        let found = Evaluated::ValueRef(&glue).unary_plus();
+        assert_eq!(found, Err(EvaluateError::UnaryPlusOnNonNumeric.into()));
+
+        let found = Evaluated::StringRef("Glue").abs();
+        assert_eq!(found, Err(ValueError::AbsOnNonNumeric.into()));
+
+        let found = Evaluated::ValueRef(&glue).abs();
+        assert_eq!(found, Err(ValueError::AbsOnNonNumeric.into()));
+    }
+
+    #[test]
+    fn round_floor_ceil() {
+        let float = |v: f64| Evaluated::Value(Value::F64(v));
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+
+        assert_eq!(to_value(float(1.005).round(Some(2))), Ok(Value::F64(1.01)));
+        assert_eq!(
+            to_value(float(-1.005).round(Some(2))),
+            Ok(Value::F64(-1.01))
+        );
+        assert_eq!(to_value(float(2.675).round(Some(2))), Ok(Value::F64(2.68)));
+        assert_eq!(
+            to_value(float(0.5).round(Some(i64::MAX))),
+            Ok(Value::F64(0.5))
+        );
+        assert_eq!(
+            to_value(float(1e300).round(Some(400))),
+            Ok(Value::F64(1e300))
+        );
+        assert_eq!(
+            to_value(float(123.0).round(Some(i64::MIN))),
+            Ok(Value::F64(0.0))
+        );
+        assert_eq!(
+            to_value(float(1e300).round(Some(-400))),
+            Ok(Value::F64(0.0))
+        );
+
+        assert_eq!(to_value(float(2.5).round(None)), Ok(Value::F64(3.0)));
+        assert_eq!(to_value(float(-2.5).round(None)), Ok(Value::F64(-3.0)));
+        assert_eq!(to_value(float(2.4).round(None)), Ok(Value::F64(2.0)));
+        assert_eq!(to_value(float(1.2345).round(Some(2))), Ok(Value::F64(1.23)));
+        assert_eq!(
+            to_value(float(1255.0).round(Some(-1))),
+            Ok(Value::F64(1260.0))
+        );
+        assert_eq!(
+            to_value(Evaluated::Literal(number("-1.5")).round(None)),
+            Ok(Value::F64(-2.0))
+        );
+
+        assert_eq!(to_value(float(-1.5).floor()), Ok(Value::F64(-2.0)));
+        assert_eq!(to_value(float(1.5).floor()), Ok(Value::F64(1.0)));
+        assert_eq!(to_value(float(-1.5).ceil()), Ok(Value::F64(-1.0)));
+        assert_eq!(to_value(float(1.1).ceil()), Ok(Value::F64(2.0)));
+
+        let nullable = Value::OptF64(Some(0.5));
+        assert_eq!(
+            to_value(Evaluated::ValueRef(&nullable).ceil()),
+            Ok(Value::OptF64(Some(1.0)))
+        );
+
+        assert_eq!(
+            to_value(Evaluated::Value(Value::I64(-7)).round(Some(-1))),
+            Ok(Value::I64(-7))
+        );
+        assert_eq!(
+            to_value(Evaluated::Literal(number("7")).floor()),
+            Ok(Value::I64(7))
+        );
+        assert_eq!(
+            to_value(Evaluated::Value(Value::OptI64(Some(7))).ceil()),
+            Ok(Value::OptI64(Some(7)))
+        );
+
+        assert_eq!(
+            Evaluated::StringRef("Glue").round(None),
+            Err(EvaluateError::MathOnNonNumeric("round".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::Value(Value::Bool(true)).floor(),
+            Err(EvaluateError::MathOnNonNumeric("floor".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::Literal(AstValue::Boolean(true)).ceil(),
+            Err(EvaluateError::MathOnNonNumeric("ceil".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn trunc() {
+        let float = |v: f64| Evaluated::Value(Value::F64(v));
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+
+        assert_eq!(to_value(float(-2.7).trunc(None)), Ok(Value::F64(-2.0)));
+        assert_eq!(to_value(float(-2.7).floor()), Ok(Value::F64(-3.0)));
+        assert_eq!(to_value(float(2.7).trunc(None)), Ok(Value::F64(2.0)));
+        assert_eq!(
+            to_value(float(-1.239).trunc(Some(2))),
+            Ok(Value::F64(-1.23))
+        );
+        assert_eq!(to_value(float(1.239).trunc(Some(2))), Ok(Value::F64(1.23)));
+        assert_eq!(
+            to_value(float(-1259.0).trunc(Some(-1))),
+            Ok(Value::F64(-1250.0))
+        );
+        assert_eq!(to_value(float(-1259.0).floor()), Ok(Value::F64(-1259.0)));
+        assert_eq!(
+            to_value(Evaluated::Literal(number("-0.5")).trunc(None)),
+            Ok(Value::F64(-0.0))
+        );
+        assert_eq!(
+            to_value(Evaluated::Value(Value::OptF64(Some(9.99))).trunc(Some(1))),
+            Ok(Value::OptF64(Some(9.9)))
+        );
+
+        assert_eq!(
+            to_value(float(1e300).trunc(Some(100))),
+            Ok(Value::F64(1e300))
+        );
+        assert_eq!(
+            to_value(float(123.0).trunc(Some(-1000))),
+            Ok(Value::F64(0.0))
+        );
+        assert_eq!(
+            to_value(float(0.5).trunc(Some(i64::MAX))),
+            Ok(Value::F64(0.5))
+        );
+
+        assert_eq!(
+            to_value(Evaluated::Value(Value::I64(-27)).trunc(Some(-1))),
+            Ok(Value::I64(-27))
+        );
+        assert_eq!(
+            Evaluated::StringRef("1.5").trunc(None),
+            Err(EvaluateError::MathOnNonNumeric("trunc".to_owned()).into())
+        );
+
+        assert_eq!(to_value(float(0.29).trunc(Some(2))), Ok(Value::F64(0.29)));
+        assert_eq!(to_value(float(-0.29).trunc(Some(2))), Ok(Value::F64(-0.29)));
+        assert_eq!(to_value(float(4.35).trunc(Some(2))), Ok(Value::F64(4.35)));
+        assert_eq!(to_value(float(1.005).trunc(Some(2))), Ok(Value::F64(1.0)));
+        assert_eq!(
+            to_value(float(0.1 + 0.2).trunc(Some(2))),
+            Ok(Value::F64(0.3))
+        );
+        assert_eq!(
+            to_value(float(-1259.99).trunc(Some(-2))),
+            Ok(Value::F64(-1200.0))
+        );
+    }
+
+    #[test]
+    fn cast() {
+        use sqlparser::ast::DataType;
+
+        let cast = |v: Evaluated<'_>, target| v.cast(target).and_then(|v| v.to_value());
+        let failed = |value: &str, target: &str| {
+            Err(EvaluateError::CastFailed {
+                value: value.to_owned(),
+                target: target.to_owned(),
+            }
+            .into())
+        };
+
+        let float = |v| Evaluated::Value(Value::F64(v));
+        assert_eq!(cast(float(1.7), DataType::Int), Ok(Value::I64(1)));
+        assert_eq!(cast(float(-1.7), DataType::Int), Ok(Value::I64(-1)));
+        assert_eq!(cast(float(1e20), DataType::Int), failed("1e20", "INT"));
+        assert_eq!(cast(float(f64::NAN), DataType::Int), failed("NaN", "INT"));
+        assert_eq!(
+            cast(Evaluated::Literal(number("3")), DataType::Float(None)),
+            Ok(Value::F64(3.0))
+        );
+
+        assert_eq!(
+            cast(Evaluated::StringRef(" 42 "), DataType::Int),
+            Ok(Value::I64(42))
+        );
+        assert_eq!(
+            cast(Evaluated::StringRef("2.5"), DataType::Float(None)),
+            Ok(Value::F64(2.5))
+        );
+        assert_eq!(
+            cast(Evaluated::StringRef("abc"), DataType::Int),
+            failed("'abc'", "INT")
+        );
+        assert_eq!(
+            cast(Evaluated::StringRef("TRUE"), DataType::Boolean),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(
+            cast(Evaluated::Value(Value::I64(2)), DataType::Boolean),
+            failed("2", "BOOLEAN")
+        );
+
+        assert_eq!(
+            cast(Evaluated::Value(Value::I64(7)), DataType::Text),
+            Ok(Value::Str("7".to_owned()))
+        );
+        assert_eq!(
+            cast(float(2.0), DataType::Text),
+            Ok(Value::Str("2.0".to_owned()))
+        );
+        assert_eq!(
+            cast(Evaluated::Literal(AstValue::Boolean(false)), DataType::Text),
+            Ok(Value::Str("FALSE".to_owned()))
+        );
+
+        let text = cast(float(0.25), DataType::Text).unwrap();
+        let round_trip = cast(Evaluated::Value(text), DataType::Float(None));
+        assert_eq!(round_trip, Ok(Value::F64(0.25)));
+
+        let text = cast(Evaluated::Literal(number("-12")), DataType::Text).unwrap();
+        let round_trip = cast(Evaluated::Value(text), DataType::Int);
+        assert_eq!(round_trip, Ok(Value::I64(-12)));
+
+        assert_eq!(
+            cast(
+                Evaluated::Value(Value::OptI64(Some(1))),
+                DataType::Float(None)
+            ),
+            Ok(Value::OptF64(Some(1.0)))
+        );
+        assert!(Evaluated::Literal(AstValue::Null)
+            .cast(DataType::Int)
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            cast(Evaluated::Value(Value::I64(1)), DataType::Date),
+            failed("1", "DATE")
+        );
+    }
+
+    #[test]
+    fn gcd_lcm() {
+        let int = |v: i64| Evaluated::Value(Value::I64(v));
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+        let gcd = |a: i64, b: i64| to_value(int(a).gcd(&int(b)));
+        let lcm = |a: i64, b: i64| to_value(int(a).lcm(&int(b)));
+
+        assert_eq!(gcd(8, 15), Ok(Value::I64(1)));
+        assert_eq!(lcm(8, 15), Ok(Value::I64(120)));
+        assert_eq!(gcd(12, 18), Ok(Value::I64(6)));
+        assert_eq!(lcm(12, 18), Ok(Value::I64(36)));
+        assert_eq!(gcd(-12, 18), Ok(Value::I64(6)));
+        assert_eq!(lcm(12, -18), Ok(Value::I64(36)));
+        assert_eq!(gcd(0, 0), Ok(Value::I64(0)));
+        assert_eq!(gcd(0, -5), Ok(Value::I64(5)));
+        assert_eq!(lcm(0, 0), Ok(Value::I64(0)));
+        assert_eq!(lcm(7, 0), Ok(Value::I64(0)));
+        assert_eq!(gcd(i64::MIN, 6), Ok(Value::I64(2)));
+        assert_eq!(lcm(i64::MAX, 1), Ok(Value::I64(i64::MAX)));
+        assert_eq!(lcm(1 << 62, 1 << 61), Ok(Value::I64(1 << 62)));
+
+        let overflow = Err(ValueError::NumericOverflow.into());
+        assert_eq!(gcd(i64::MIN, 0), overflow);
+        assert_eq!(lcm(i64::MAX, i64::MAX - 1), overflow);
+        assert_eq!(lcm(i64::MIN, 1), overflow);
+
+        assert_eq!(
+            to_value(Evaluated::Literal(number("21")).gcd(&Evaluated::Literal(number("14")))),
+            Ok(Value::I64(7))
+        );
+        assert!(int(4)
+            .lcm(&Evaluated::Value(Value::OptI64(None)))
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            int(4).gcd(&Evaluated::Literal(number("2.5"))),
+            Err(EvaluateError::IntegerTypeRequired("GCD".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::StringRef("4").lcm(&int(2)),
+            Err(EvaluateError::IntegerTypeRequired("LCM".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn bitwise() {
+        let flags = Value::I64(0b0110);
+        let flags = || Evaluated::ValueRef(&flags);
+        let int = |v: &str| Evaluated::Literal(number(v));
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+
+        assert_eq!(to_value(flags().bit_and(&int("4"))), Ok(Value::I64(4)));
+        assert_eq!(to_value(flags().bit_and(&int("1"))), Ok(Value::I64(0)));
+        assert_eq!(to_value(flags().bit_or(&int("1"))), Ok(Value::I64(0b0111)));
+        assert_eq!(to_value(flags().bit_xor(&int("2"))), Ok(Value::I64(0b0100)));
+        assert_eq!(to_value(int("1").shift_left(&int("3"))), Ok(Value::I64(8)));
+        assert_eq!(
+            to_value(int("1").shift_left(&int("63"))),
+            Ok(Value::I64(i64::MIN))
+        );
+        assert_eq!(
+            to_value(flags().shift_right(&int("1"))),
+            Ok(Value::I64(0b0011))
+        );
+        assert_eq!(
+            to_value(int("-8").shift_right(&int("1"))),
+            Ok(Value::I64(-4))
+        );
+
+        assert_eq!(
+            int("1").shift_left(&int("64")),
+            Err(EvaluateError::ShiftOutOfRange(64).into())
+        );
+        assert_eq!(
+            int("1").shift_right(&int("-1")),
+            Err(EvaluateError::ShiftOutOfRange(-1).into())
+        );
+        assert_eq!(
+            flags().bit_and(&int("1.5")),
+            Err(EvaluateError::BitwiseOnNonInteger("&".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::StringRef("1").bit_or(&int("1")),
+            Err(EvaluateError::BitwiseOnNonInteger("|".to_owned()).into())
+        );
+        assert!(flags()
+            .bit_and(&Evaluated::Literal(AstValue::Null))
+            .unwrap()
+            .is_null());
+    }
+
+    #[test]
+    fn sign() {
+        let sign = |v: Evaluated<'_>| v.sign().and_then(|v| v.to_value());
+
+        assert_eq!(sign(Evaluated::Literal(number("42"))), Ok(Value::I64(1)));
+        assert_eq!(sign(Evaluated::Literal(number("-0.5"))), Ok(Value::I64(-1)));
+        assert_eq!(sign(Evaluated::Literal(number("0"))), Ok(Value::I64(0)));
+        assert_eq!(
+            sign(Evaluated::Value(Value::I64(i64::MIN))),
+            Ok(Value::I64(-1))
+        );
+        assert_eq!(sign(Evaluated::Value(Value::F64(2.5))), Ok(Value::I64(1)));
+        assert_eq!(sign(Evaluated::Value(Value::F64(-0.0))), Ok(Value::I64(0)));
+        assert_eq!(
+            sign(Evaluated::Value(Value::F64(f64::NEG_INFINITY))),
+            Ok(Value::I64(-1))
+        );
+        assert_eq!(
+            sign(Evaluated::Value(Value::OptF64(Some(-3.0)))),
+            Ok(Value::OptI64(Some(-1)))
+        );
+        assert_eq!(
+            sign(Evaluated::Value(Value::Decimal("-0.00".parse().unwrap()))),
+            Ok(Value::I64(0))
+        );
+        assert_eq!(
+            sign(Evaluated::Value(Value::OptDecimal(Some(
+                "0.01".parse().unwrap()
+            )))),
+            Ok(Value::OptI64(Some(1)))
+        );
+        assert!(Evaluated::Value(Value::OptI64(None))
+            .sign()
+            .unwrap()
+            .is_null());
+
+        assert_eq!(
+            Evaluated::Value(Value::F64(f64::NAN)).sign(),
+            Err(EvaluateError::InvalidMathArgument {
+                function: "sign".to_owned(),
+                value: "NaN".to_owned(),
+            }
+            .into())
+        );
+        assert_eq!(
+            Evaluated::StringRef("1").sign(),
+            Err(EvaluateError::MathOnNonNumeric("sign".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn math_functions() {
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+        let invalid = |function: &str, value: &str| {
+            Err(EvaluateError::InvalidMathArgument {
+                function: function.to_owned(),
+                value: value.to_owned(),
+            }
+            .into())
+        };
+
+        assert_eq!(
+            to_value(Evaluated::Literal(number("16")).sqrt()),
+            Ok(Value::F64(4.0))
+        );
+        assert_eq!(
+            to_value(Evaluated::Value(Value::F64(2.25)).sqrt()),
+            Ok(Value::F64(1.5))
+        );
+        assert_eq!(
+            to_value(Evaluated::Value(Value::I64(1)).ln()),
+            Ok(Value::F64(0.0))
+        );
+        assert_eq!(
+            to_value(Evaluated::Literal(number("1000")).log10()),
+            Ok(Value::F64(3.0))
+        );
+        assert_eq!(
+            to_value(Evaluated::Value(Value::I64(0)).exp()),
+            Ok(Value::F64(1.0))
+        );
+        assert_eq!(
+            to_value(Evaluated::Value(Value::OptI64(Some(9))).sqrt()),
+            Ok(Value::OptF64(Some(3.0)))
+        );
+        assert!(Evaluated::Literal(AstValue::Null).sqrt().unwrap().is_null());
+
+        assert_eq!(
+            Evaluated::Literal(number("-1")).sqrt(),
+            invalid("sqrt", "-1")
+        );
+        assert_eq!(
+            Evaluated::Value(Value::F64(-0.5)).ln(),
+            invalid("ln", "-0.5")
+        );
+        assert_eq!(
+            Evaluated::Value(Value::I64(0)).log10(),
+            invalid("log10", "0")
+        );
+        assert_eq!(
+            Evaluated::StringRef("Glue").exp(),
+            Err(EvaluateError::MathOnNonNumeric("exp".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn boolean() {
+        use std::cmp::Ordering;
+
+        let t = AstValue::Boolean(true);
+        let f = AstValue::Boolean(false);
+        let value_true = Value::Bool(true);
+
+        assert_eq!(Evaluated::LiteralRef(&t), Evaluated::Literal(t.clone()));
+        assert_eq!(Evaluated::LiteralRef(&t), Evaluated::ValueRef(&value_true));
+        assert_ne!(Evaluated::LiteralRef(&f), Evaluated::ValueRef(&value_true));
+
+        let found = Evaluated::LiteralRef(&f).partial_cmp(&Evaluated::LiteralRef(&t));
+        assert_eq!(found, Some(Ordering::Less));
+
+        let found = Evaluated::ValueRef(&value_true).partial_cmp(&Evaluated::LiteralRef(&f));
+        assert_eq!(found, Some(Ordering::Greater));
+
+        let one = number("1");
+        let found = Evaluated::LiteralRef(&t).add(&Evaluated::LiteralRef(&one));
+        assert_eq!(found, Err(EvaluateError::LiteralAddOnNonNumeric.into()));
+
+        let found = Evaluated::ValueRef(&value_true).add(&Evaluated::LiteralRef(&one));
+        assert!(found.is_err());
+    }
+
+    #[test]
+    fn null() {
+        let null = AstValue::Null;
+        let one = number("1");
+        let value_null = Value::OptI64(None);
+        let nulls = || {
+            vec![
+                Evaluated::LiteralRef(&null),
+                Evaluated::Literal(AstValue::Null),
+                Evaluated::ValueRef(&value_null),
+                Evaluated::Value(Value::Empty),
+            ]
+        };
+
+        for l in nulls() {
+            let one = Evaluated::LiteralRef(&one);
+
+            for r in nulls() {
+                assert_ne!(l, r);
+                assert_eq!(l.partial_cmp(&r), None);
+            }
+
+            assert_ne!(l, one);
+            assert_ne!(one, l);
+            assert_eq!(l.partial_cmp(&one), None);
+            assert_eq!(one.partial_cmp(&l), None);
+        }
+    }
+
+    #[test]
+    fn is_null() {
+        let null = AstValue::Null;
+        let text = AstValue::SingleQuotedString("".to_owned());
+        let none = Value::OptI64(None);
+        let empty = Value::Str("".to_owned());
+
+        assert!(Evaluated::LiteralRef(&null).is_null());
+        assert!(Evaluated::Literal(AstValue::Null).is_null());
+        assert!(Evaluated::ValueRef(&none).is_null());
+        assert!(Evaluated::Value(Value::OptStr(None)).is_null());
+        assert!(Evaluated::Value(Value::OptBool(None)).is_null());
+        assert!(Evaluated::Value(Value::OptF64(None)).is_null());
+        assert!(Evaluated::Value(Value::Empty).is_null());
+
+        assert!(!Evaluated::LiteralRef(&text).is_null());
+        assert!(!Evaluated::Literal(number("0")).is_null());
+        assert!(!Evaluated::StringRef("").is_null());
+        assert!(!Evaluated::ValueRef(&empty).is_null());
+        assert!(!Evaluated::Value(Value::OptStr(Some("".to_owned()))).is_null());
+        assert!(!Evaluated::Value(Value::Bool(false)).is_null());
+    }
+
+    #[test]
+    fn to_value() {
+        let to_value = |v: AstValue| Evaluated::Literal(v).to_value();
+
+        assert_eq!(to_value(number("1")), Ok(Value::I64(1)));
+        assert_eq!(to_value(number("1.5")), Ok(Value::F64(1.5)));
+        assert_eq!(to_value(AstValue::Boolean(true)), Ok(Value::Bool(true)));
+        assert_eq!(
+            to_value(AstValue::SingleQuotedString("Glue".to_owned())),
+            Ok(Value::Str("Glue".to_owned()))
+        );
+        assert_eq!(to_value(AstValue::Null), Ok(Value::Empty));
+
+        let found = Evaluated::StringRef("Glue").to_value();
+        assert_eq!(found, Ok(Value::Str("Glue".to_owned())));
+
+        let found = Evaluated::ValueRef(&Value::OptI64(None)).to_value();
+        assert_eq!(found, Ok(Value::OptI64(None)));
+
+        let found = Evaluated::Value(Value::F64(2.5)).to_value();
+        assert_eq!(found, Ok(Value::F64(2.5)));
+    }
+
+    #[test]
+    fn data_type() {
+        use sqlparser::ast::DataType;
+
+        let one = number("1");
+        let text = AstValue::SingleQuotedString("Glue".to_owned());
+        let value = Value::OptStr(None);
+
+        assert_eq!(Evaluated::LiteralRef(&one).data_type(), Some(DataType::Int));
+        assert_eq!(
+            Evaluated::Literal(number("1.5")).data_type(),
+            Some(DataType::Float(None))
+        );
+        assert_eq!(
+            Evaluated::LiteralRef(&text).data_type(),
+            Some(DataType::Text)
+        );
+        assert_eq!(
+            Evaluated::Literal(AstValue::Boolean(true)).data_type(),
+            Some(DataType::Boolean)
+        );
+        assert_eq!(Evaluated::Literal(AstValue::Null).data_type(), None);
+        assert_eq!(
+            Evaluated::StringRef("Glue").data_type(),
+            Some(DataType::Text)
+        );
+        assert_eq!(
+            Evaluated::ValueRef(&value).data_type(),
+            Some(DataType::Text)
+        );
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).data_type(),
+            Some(DataType::Int)
+        );
+        assert_eq!(
+            Evaluated::Value(Value::OptF64(Some(1.0))).data_type(),
+            Some(DataType::Float(None))
+        );
+        assert_eq!(
+            Evaluated::Value(Value::Bool(false)).data_type(),
+            Some(DataType::Boolean)
+        );
+        assert_eq!(Evaluated::Value(Value::Empty).data_type(), None);
+    }
+
+    #[test]
+    fn into_value() {
+        let text = "Glue".to_owned();
+        let ptr = text.as_ptr();
+
+        match Evaluated::Value(Value::Str(text)).into_value() {
+            Ok(Value::Str(v)) => assert_eq!(v.as_ptr(), ptr),
+            v => panic!("unexpected {:?}", v),
+        }
+
+        let text = "SQL".to_owned();
+        let ptr = text.as_ptr();
+
+        match Evaluated::Literal(AstValue::SingleQuotedString(text)).into_value() {
+            Ok(Value::Str(v)) => assert_eq!(v.as_ptr(), ptr),
+            v => panic!("unexpected {:?}", v),
+        }
+
+        let one = number("1");
+        let value = Value::F64(1.5);
+        let cases = vec![
+            Evaluated::LiteralRef(&one),
+            Evaluated::Literal(number("2.5")),
+            Evaluated::Literal(AstValue::Null),
+            Evaluated::StringRef("Glue"),
+            Evaluated::ValueRef(&value),
+            Evaluated::Value(Value::OptI64(None)),
+        ];
+
+        for evaluated in cases.into_iter() {
+            assert_eq!(evaluated.to_value(), evaluated.into_value());
+        }
+    }
+
+    #[test]
+    fn numeric_overflow() {
+        let max = number(&i64::MAX.to_string());
+        let min = number(&i64::MIN.to_string());
+        let one = number("1");
+        let minus_one = number("-1");
+        let two = number("2");
+        let overflow = Err(ValueError::NumericOverflow.into());
+
+        let max = Evaluated::LiteralRef(&max);
+        let min = Evaluated::LiteralRef(&min);
+
+        assert_eq!(max.add(&Evaluated::LiteralRef(&one)), overflow);
+        assert_eq!(min.subtract(&Evaluated::LiteralRef(&one)), overflow);
+        assert_eq!(max.multiply(&Evaluated::LiteralRef(&two)), overflow);
+        assert_eq!(min.divide(&Evaluated::LiteralRef(&minus_one)), overflow);
+
+        let value_max = Value::I64(i64::MAX);
+        let overflow = Err(ValueError::NumericOverflow.into());
+        let found = Evaluated::ValueRef(&value_max).add(&Evaluated::LiteralRef(&one));
+        assert_eq!(found, overflow);
+
+        let found = Evaluated::Value(Value::OptI64(Some(i64::MIN)))
+            .subtract(&Evaluated::Value(Value::I64(1)));
+        assert_eq!(found, overflow);
+
+        let found = Evaluated::LiteralRef(&two).multiply(&Evaluated::ValueRef(&value_max));
+        assert_eq!(found, overflow);
+
+        let found = Evaluated::LiteralRef(&one).add(&Evaluated::LiteralRef(&two));
+        assert_eq!(found, Ok(Evaluated::Literal(number("3"))));
+    }
+
+    #[test]
+    fn float_promotion() {
+        use std::cmp::Ordering;
+
+        let two = number("2");
+        let two_and_half = number("2.5");
+        let value_two = Value::I64(2);
+        let value_two_and_half = Value::OptF64(Some(2.5));
+
+        let found = Evaluated::LiteralRef(&two).add(&Evaluated::LiteralRef(&two_and_half));
+        assert_eq!(found, Ok(Evaluated::Literal(number("4.5"))));
+
+        let found = Evaluated::ValueRef(&value_two).add(&Evaluated::LiteralRef(&two_and_half));
+        assert_eq!(found, Ok(Evaluated::Value(Value::F64(4.5))));
+
+        let found = Evaluated::LiteralRef(&two_and_half).multiply(&Evaluated::ValueRef(&value_two));
+        assert_eq!(found, Ok(Evaluated::Value(Value::F64(5.0))));
+
+        let found =
+            Evaluated::ValueRef(&value_two).subtract(&Evaluated::ValueRef(&value_two_and_half));
+        assert_eq!(found, Ok(Evaluated::Value(Value::OptF64(Some(-0.5)))));
+
+        let found = Evaluated::Value(Value::F64(5.0)).divide(&Evaluated::ValueRef(&value_two));
+        assert_eq!(found, Ok(Evaluated::Value(Value::F64(2.5))));
+
+        let less = Some(Ordering::Less);
+        let found = Evaluated::LiteralRef(&two).partial_cmp(&Evaluated::LiteralRef(&two_and_half));
+        assert_eq!(found, less);
+
+        let found =
+            Evaluated::ValueRef(&value_two).partial_cmp(&Evaluated::LiteralRef(&two_and_half));
+        assert_eq!(found, less);
+
+        let found =
+            Evaluated::ValueRef(&value_two).partial_cmp(&Evaluated::ValueRef(&value_two_and_half));
+        assert_eq!(found, less);
+
+        assert_eq!(
+            Evaluated::ValueRef(&value_two),
+            Evaluated::Value(Value::F64(2.0))
+        );
+        assert_eq!(
+            Evaluated::ValueRef(&value_two),
+            Evaluated::Literal(number("2.0"))
+        );
+    }
+
+    #[test]
+    fn type_mismatch() {
+        use std::cmp::Ordering;
+
+        let number = number("42");
+        let name = Value::Str("Glue".to_owned());
+        let mismatch = Err(EvaluateError::TypeMismatch.into());
+
+        let found = Evaluated::StringRef("Glue").try_eq(&Evaluated::LiteralRef(&number));
+        assert_eq!(found, mismatch);
+
+        let found = Evaluated::Value(Value::I64(1)).try_eq(&Evaluated::StringRef("1"));
+        assert_eq!(found, mismatch);
+
+        let found = Evaluated::LiteralRef(&number).try_partial_cmp(&Evaluated::ValueRef(&name));
+        assert_eq!(found, Err(EvaluateError::TypeMismatch.into()));
+
+        let found = Evaluated::StringRef("Glue").try_eq(&Evaluated::ValueRef(&name));
+        assert_eq!(found, Ok(true));
+
+        let found = Evaluated::StringRef("A").try_partial_cmp(&Evaluated::ValueRef(&name));
+        assert_eq!(found, Ok(Some(Ordering::Less)));
+
+        let found = Evaluated::StringRef("Glue").try_eq(&Evaluated::Literal(AstValue::Null));
+        assert_eq!(found, Ok(false));
+    }
+
+    #[test]
+    fn eq_case_insensitive() {
+        let alice = Value::Str("alice".to_owned());
+        let literal = AstValue::SingleQuotedString("Alice".to_owned());
+
+        assert!(Evaluated::StringRef("ALICE").eq_case_insensitive(&Evaluated::ValueRef(&alice)));
+        assert!(Evaluated::LiteralRef(&literal).eq_case_insensitive(&Evaluated::StringRef("aLiCe")));
+        assert!(
+            Evaluated::Value(alice.clone()).eq_case_insensitive(&Evaluated::LiteralRef(&literal))
+        );
+        assert!(!Evaluated::StringRef("Bob").eq_case_insensitive(&Evaluated::LiteralRef(&literal)));
+
+        assert!(Evaluated::StringRef("ÄPFEL").eq_case_insensitive(&Evaluated::StringRef("äpfel")));
+        assert!(
+            !Evaluated::StringRef("STRASSE").eq_case_insensitive(&Evaluated::StringRef("straße"))
+        );
+
+        let one = number("1");
+        assert!(Evaluated::Value(Value::I64(1)).eq_case_insensitive(&Evaluated::LiteralRef(&one)));
+        assert!(!Evaluated::StringRef("1").eq_case_insensitive(&Evaluated::LiteralRef(&one)));
+
+        let null = Evaluated::Literal(AstValue::Null);
+        assert!(!null.eq_case_insensitive(&Evaluated::Literal(AstValue::Null)));
+    }
+
+    #[test]
+    fn coalesce() {
+        let null = || Evaluated::Literal(AstValue::Null);
+
+        let found = Evaluated::coalesce(&[null(), Evaluated::Value(Value::OptStr(None)), null()]);
+        assert!(found.unwrap().is_null());
+        assert!(Evaluated::coalesce(&[]).unwrap().is_null());
+
+        let nickname = Value::OptStr(None);
+        let name = Value::Str("Glue".to_owned());
+        let anonymous = AstValue::SingleQuotedString("anonymous".to_owned());
+        let found = Evaluated::coalesce(&[
+            Evaluated::ValueRef(&nickname),
+            Evaluated::ValueRef(&name),
+            Evaluated::LiteralRef(&anonymous),
+        ]);
+        assert!(matches!(found, Ok(Evaluated::ValueRef(Value::Str(v))) if v == "Glue"));
+
+        let found = Evaluated::coalesce(&[null(), Evaluated::LiteralRef(&anonymous)]);
+        assert!(matches!(found, Ok(Evaluated::LiteralRef(v)) if v == &anonymous));
+
+        let found = Evaluated::coalesce(&[Evaluated::StringRef("Glue")]);
+        assert!(matches!(found, Ok(Evaluated::StringRef("Glue"))));
+    }
+
+    #[test]
+    fn greatest_and_least() {
+        let null = || Evaluated::Literal(AstValue::Null);
+        let three = Value::I64(3);
+        let items = vec![
+            Evaluated::Literal(number("1")),
+            null(),
+            Evaluated::ValueRef(&three),
+            Evaluated::Value(Value::F64(2.5)),
+            Evaluated::Value(Value::OptI64(None)),
+        ];
+
+        let found = Evaluated::greatest(&items).and_then(|v| v.to_value());
+        assert_eq!(found, Ok(Value::I64(3)));
+        let found = Evaluated::least(&items).and_then(|v| v.to_value());
+        assert_eq!(found, Ok(Value::I64(1)));
+
+        let items = vec![Evaluated::StringRef("b"), Evaluated::StringRef("a")];
+        let found = Evaluated::least(&items).and_then(|v| v.to_value());
+        assert_eq!(found, Ok(Value::Str("a".to_owned())));
+
+        assert!(Evaluated::greatest(&[null(), null()]).unwrap().is_null());
+        assert!(Evaluated::least(&[]).unwrap().is_null());
+
+        let items = vec![Evaluated::Literal(number("1")), Evaluated::StringRef("a")];
+        assert_eq!(
+            Evaluated::greatest(&items),
+            Err(EvaluateError::IncomparableValues {
+                left: "1".to_owned(),
+                right: "'a'".to_owned(),
+            }
+            .into())
+        );
+    }
+
+    #[test]
+    fn nullif() {
+        let one = number("1");
+        let found = Evaluated::Value(Value::I64(1)).nullif(&Evaluated::LiteralRef(&one));
+        assert!(found.unwrap().is_null());
+
+        let found = Evaluated::Value(Value::I64(2)).nullif(&Evaluated::LiteralRef(&one));
+        assert_eq!(found.and_then(|v| v.to_value()), Ok(Value::I64(2)));
+
+        let found = Evaluated::StringRef("Glue").nullif(&Evaluated::StringRef("SQL"));
+        assert_eq!(
+            found.and_then(|v| v.to_value()),
+            Ok(Value::Str("Glue".to_owned()))
+        );
+
+        let found = Evaluated::StringRef("Glue").nullif(&Evaluated::StringRef("Glue"));
+        assert!(found.unwrap().is_null());
+
+        let null = Evaluated::Literal(AstValue::Null);
+        assert!(null.nullif(&Evaluated::LiteralRef(&one)).unwrap().is_null());
+        assert!(null.nullif(&null).unwrap().is_null());
+
+        let found = Evaluated::Value(Value::I64(1)).nullif(&null);
+        assert_eq!(found.and_then(|v| v.to_value()), Ok(Value::I64(1)));
+    }
+
+    #[test]
+    fn is_in() {
+        let one = number("1");
+        let two = number("2");
+        let numbers = vec![Evaluated::LiteralRef(&one), Evaluated::LiteralRef(&two)];
+
+        assert_eq!(Evaluated::Value(Value::I64(2)).is_in(&numbers), Ok(true));
+        assert_eq!(Evaluated::Value(Value::I64(3)).is_in(&numbers), Ok(false));
+
+        let strings = vec![
+            Evaluated::Literal(AstValue::SingleQuotedString("active".to_owned())),
+            Evaluated::Literal(AstValue::SingleQuotedString("pending".to_owned())),
+        ];
+
+        assert_eq!(Evaluated::StringRef("pending").is_in(&strings), Ok(true));
+        assert_eq!(Evaluated::StringRef("closed").is_in(&strings), Ok(false));
+
+        let with_null = vec![
+            Evaluated::LiteralRef(&one),
+            Evaluated::Literal(AstValue::Null),
+        ];
+
+        assert_eq!(Evaluated::Value(Value::I64(1)).is_in(&with_null), Ok(true));
+        assert_eq!(Evaluated::Value(Value::I64(3)).is_in(&with_null), Ok(false));
+        assert_eq!(
+            Evaluated::Literal(AstValue::Null).is_in(&with_null),
+            Ok(false)
+        );
+
+        let mixed = vec![
+            Evaluated::Literal(AstValue::Boolean(true)),
+            Evaluated::LiteralRef(&one),
+        ];
+
+        assert_eq!(Evaluated::Value(Value::I64(1)).is_in(&mixed), Ok(true));
+        assert_eq!(
+            Evaluated::StringRef("1").is_in(&mixed),
+            Err(EvaluateError::TypeMismatch.into())
+        );
+
+        let text = Evaluated::Literal(AstValue::SingleQuotedString("a".to_owned()));
+        let one = Evaluated::Value(Value::I64(1));
+
+        for list in [
+            vec![one.clone(), text.clone()],
+            vec![text.clone(), one.clone()],
+        ] {
+            assert_eq!(one.is_in(&list), Err(EvaluateError::TypeMismatch.into()));
+        }
+    }
+
+    #[test]
+    fn between() {
+        let low = number("18");
+        let high = number("65");
+        let between = |v: i64, negated| {
+            Evaluated::Value(Value::I64(v)).between(
+                &Evaluated::LiteralRef(&low),
+                &Evaluated::LiteralRef(&high),
+                negated,
+                false,
+            )
+        };
+
+        assert_eq!(between(18, false), Ok(true));
+        assert_eq!(between(40, false), Ok(true));
+        assert_eq!(between(65, false), Ok(true));
+        assert_eq!(between(66, false), Ok(false));
+        assert_eq!(between(17, true), Ok(true));
+        assert_eq!(between(40, true), Ok(false));
+
+        let reversed = Evaluated::Value(Value::I64(40)).between(
+            &Evaluated::LiteralRef(&high),
+            &Evaluated::LiteralRef(&low),
+            false,
+            false,
+        );
+        assert_eq!(reversed, Ok(false));
+
+        let null = Evaluated::Literal(AstValue::Null);
+        let found = null.between(
+            &Evaluated::LiteralRef(&low),
+            &Evaluated::LiteralRef(&high),
+            true,
+            false,
+        );
+        assert_eq!(found, Ok(false));
+
+        let boolean = Evaluated::Literal(AstValue::Boolean(true));
+        let found = boolean.between(
+            &Evaluated::LiteralRef(&low),
+            &Evaluated::LiteralRef(&high),
+            true,
+            false,
+        );
+        assert_eq!(found, Ok(false));
+
+        let found = Evaluated::StringRef("Glue").between(
+            &Evaluated::LiteralRef(&low),
+            &Evaluated::LiteralRef(&high),
+            false,
+            false,
+        );
+        assert_eq!(found, Err(EvaluateError::TypeMismatch.into()));
+    }
+
+    #[test]
+    fn between_symmetric() {
+        let (five, one) = (number("5"), number("1"));
+        let between = |v: i64, negated, symmetric| {
+            Evaluated::Value(Value::I64(v)).between(
+                &Evaluated::LiteralRef(&five),
+                &Evaluated::LiteralRef(&one),
+                negated,
+                symmetric,
+            )
+        };
+
+        assert_eq!(between(3, false, false), Ok(false));
+        assert_eq!(between(3, false, true), Ok(true));
+        assert_eq!(between(1, false, true), Ok(true));
+        assert_eq!(between(5, false, true), Ok(true));
+        assert_eq!(between(6, false, true), Ok(false));
+        assert_eq!(between(3, true, true), Ok(false));
+        assert_eq!(between(0, true, true), Ok(true));
+
+        let in_order = Evaluated::Value(Value::I64(3)).between(
+            &Evaluated::LiteralRef(&one),
+            &Evaluated::LiteralRef(&five),
+            false,
+            true,
+        );
+        assert_eq!(in_order, Ok(true));
+
+        let null = Evaluated::Literal(AstValue::Null);
+        let found = Evaluated::Value(Value::I64(3)).between(
+            &null,
+            &Evaluated::LiteralRef(&one),
+            false,
+            true,
+        );
+        assert_eq!(found, Ok(false));
+    }
+
+    #[test]
+    fn upper_and_lower() {
+        let text = |v: &str| Ok(Value::Str(v.to_owned()));
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+
+        let literal = AstValue::SingleQuotedString("Glue".to_owned());
+        let value = Value::Str("Glue".to_owned());
+
+        assert_eq!(to_value(Evaluated::StringRef("Glue").upper()), text("GLUE"));
+        assert_eq!(
+            to_value(Evaluated::LiteralRef(&literal).upper()),
+            text("GLUE")
+        );
+        assert_eq!(
+            to_value(Evaluated::Literal(literal.clone()).lower()),
+            text("glue")
+        );
+        assert_eq!(to_value(Evaluated::ValueRef(&value).lower()), text("glue"));
+        assert_eq!(
+            to_value(Evaluated::Value(Value::OptStr(Some("Glue".to_owned()))).upper()),
+            text("GLUE")
+        );
+
+        assert_eq!(
+            to_value(Evaluated::StringRef("straße").upper()),
+            text("STRASSE")
+        );
+        assert_eq!(
+            to_value(Evaluated::StringRef("ÄPFEL").lower()),
+            text("äpfel")
+        );
+
+        assert!(Evaluated::Value(Value::OptStr(None))
+            .upper()
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            Evaluated::Literal(number("1")).upper(),
+            Err(EvaluateError::StringFunctionOnNonString("UPPER".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).lower(),
+            Err(EvaluateError::StringFunctionOnNonString("LOWER".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn length() {
+        let literal = AstValue::SingleQuotedString("héllo".to_owned());
+        let value = Value::Str("héllo".to_owned());
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+
+        assert_eq!(
+            to_value(Evaluated::StringRef("héllo").length()),
+            Ok(Value::I64(5))
+        );
+        assert_eq!(
+            to_value(Evaluated::LiteralRef(&literal).length()),
+            Ok(Value::I64(5))
+        );
+        assert_eq!(
+            to_value(Evaluated::ValueRef(&value).length()),
+            Ok(Value::I64(5))
+        );
+        assert_eq!(
+            to_value(Evaluated::StringRef("").length()),
+            Ok(Value::I64(0))
+        );
+        assert_eq!(
+            to_value(Evaluated::ValueRef(&value).octet_length()),
+            Ok(Value::I64(6))
+        );
+        assert_eq!(
+            to_value(Evaluated::StringRef("hello").octet_length()),
+            Ok(Value::I64(5))
+        );
+
+        assert!(Evaluated::Literal(AstValue::Null)
+            .length()
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            Evaluated::Value(Value::I64(10)).length(),
+            Err(EvaluateError::StringFunctionOnNonString("LENGTH".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn substring() {
+        let substring = |v: &str, start, len| {
+            Evaluated::StringRef(v)
+                .substring(start, len)
+                .and_then(|v| v.to_value())
+        };
+        let text = |v: &str| Ok(Value::Str(v.to_owned()));
+
+        assert_eq!(substring("Hello", 2, Some(3)), text("ell"));
+        assert_eq!(substring("Hello", 2, None), text("ello"));
+        assert_eq!(substring("Hello", 1, Some(100)), text("Hello"));
+        assert_eq!(substring("Hello", 6, None), text(""));
+        assert_eq!(substring("Hello", 100, Some(2)), text(""));
+        assert_eq!(substring("Hello", 0, Some(3)), text("He"));
+        assert_eq!(substring("Hello", -3, Some(5)), text("H"));
+        assert_eq!(substring("Hello", -3, None), text("Hello"));
+        assert_eq!(substring("Hello", 2, Some(-1)), text(""));
+        assert_eq!(substring("héllo wörld", 2, Some(4)), text("éllo"));
+        assert_eq!(substring("Hello", i64::MAX, Some(i64::MAX)), text(""));
+
+        let value = Value::Str("GlueSQL".to_owned());
+        let found = Evaluated::ValueRef(&value).substring(5, None);
+        assert_eq!(found.and_then(|v| v.to_value()), text("SQL"));
+
+        assert_eq!(
+            Evaluated::Value(Value::F64(1.0)).substring(1, None),
+            Err(EvaluateError::StringFunctionOnNonString("SUBSTRING".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn trim() {
+        let text = |v: &str| Ok(Value::Str(v.to_owned()));
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+
+        let padded = Evaluated::StringRef("  Glue \t\n");
+        assert_eq!(to_value(padded.trim(None)), text("Glue"));
+        assert_eq!(to_value(padded.ltrim(None)), text("Glue \t\n"));
+        assert_eq!(to_value(padded.rtrim(None)), text("  Glue"));
+        assert_eq!(to_value(Evaluated::StringRef(" \t ").trim(None)), text(""));
+
+        let literal = AstValue::SingleQuotedString("xxGluexyx".to_owned());
+        let chars = Evaluated::StringRef("xy");
+        assert_eq!(
+            to_value(Evaluated::LiteralRef(&literal).trim(Some(&chars))),
+            text("Glue")
+        );
+        assert_eq!(
+            to_value(Evaluated::LiteralRef(&literal).ltrim(Some(&chars))),
+            text("Gluexyx")
+        );
+
+        let value = Value::Str("xxGluexyx".to_owned());
+        assert_eq!(
+            to_value(Evaluated::ValueRef(&value).rtrim(Some(&chars))),
+            text("xxGlue")
+        );
+
+        let null = Evaluated::Literal(AstValue::Null);
+        assert!(Evaluated::ValueRef(&value)
+            .trim(Some(&null))
+            .unwrap()
+            .is_null());
+        assert!(null.trim(None).unwrap().is_null());
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).trim(None),
+            Err(EvaluateError::StringFunctionOnNonString("TRIM".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::StringRef("1").ltrim(Some(&Evaluated::Value(Value::I64(1)))),
+            Err(EvaluateError::StringFunctionOnNonString("LTRIM".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn replace() {
+        let text = |v: &str| Ok(Value::Str(v.to_owned()));
+        let replace = |v: &str, from: &str, to: &str| {
+            Evaluated::StringRef(v)
+                .replace(&Evaluated::StringRef(from), &Evaluated::StringRef(to))
+                .and_then(|v| v.to_value())
+        };
+
+        assert_eq!(replace("a-b-c", "-", "+"), text("a+b+c"));
+        assert_eq!(replace("aaaa", "aa", "b"), text("bb"));
+        assert_eq!(replace("aaa", "aa", "b"), text("ba"));
+        assert_eq!(replace("Glue", "SQL", "DB"), text("Glue"));
+        assert_eq!(replace("Glue", "", "x"), text("Glue"));
+        assert_eq!(replace("héllo", "é", "e"), text("hello"));
+
+        let value = Value::Str("GlueSQL".to_owned());
+        let from = AstValue::SingleQuotedString("SQL".to_owned());
+        let found = Evaluated::ValueRef(&value)
+            .replace(&Evaluated::LiteralRef(&from), &Evaluated::StringRef("DB"));
+        assert_eq!(found.and_then(|v| v.to_value()), text("GlueDB"));
+
+        let null = Evaluated::Literal(AstValue::Null);
+        let found = Evaluated::ValueRef(&value).replace(&null, &Evaluated::StringRef("DB"));
+        assert!(found.unwrap().is_null());
+
+        let found = Evaluated::ValueRef(&value).replace(
+            &Evaluated::StringRef("SQL"),
+            &Evaluated::Value(Value::I64(1)),
+        );
+        assert_eq!(
+            found,
+            Err(EvaluateError::StringFunctionOnNonString("REPLACE".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn position() {
+        let position = |v: &str, needle: &str| {
+            Evaluated::StringRef(v)
+                .position(&Evaluated::StringRef(needle))
+                .and_then(|v| v.to_value())
+        };
+
+        assert_eq!(position("GlueSQL", "Glue"), Ok(Value::I64(1)));
+        assert_eq!(position("GlueSQL", "SQL"), Ok(Value::I64(5)));
+        assert_eq!(position("GlueSQL", "DB"), Ok(Value::I64(0)));
+        assert_eq!(position("GlueSQL", ""), Ok(Value::I64(1)));
+        assert_eq!(position("", ""), Ok(Value::I64(1)));
+        assert_eq!(position("héllo", "llo"), Ok(Value::I64(3)));
+
+        let value = Value::OptStr(Some("a,b".to_owned()));
+        let needle = AstValue::SingleQuotedString(",".to_owned());
+        let found = Evaluated::ValueRef(&value).position(&Evaluated::LiteralRef(&needle));
+        assert_eq!(found.and_then(|v| v.to_value()), Ok(Value::I64(2)));
+
+        let null = Evaluated::Literal(AstValue::Null);
+        assert!(Evaluated::StringRef("Glue")
+            .position(&null)
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            Evaluated::StringRef("Glue").position(&Evaluated::Value(Value::I64(1))),
+            Err(EvaluateError::StringFunctionOnNonString("POSITION".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn left_and_right() {
+        let text = |v: &str| Ok(Value::Str(v.to_owned()));
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+        let glue = Evaluated::StringRef("Glue");
+
+        assert_eq!(to_value(glue.left(3)), text("Glu"));
+        assert_eq!(to_value(glue.right(2)), text("ue"));
+        assert_eq!(to_value(glue.left(0)), text(""));
+        assert_eq!(to_value(glue.right(0)), text(""));
+        assert_eq!(to_value(glue.left(10)), text("Glue"));
+        assert_eq!(to_value(glue.right(i64::MAX)), text("Glue"));
+        assert_eq!(to_value(glue.left(-1)), text(""));
+        assert_eq!(to_value(glue.right(-1)), text(""));
+
+        let value = Value::Str("héllo".to_owned());
+        assert_eq!(to_value(Evaluated::ValueRef(&value).left(2)), text("hé"));
+        assert_eq!(to_value(Evaluated::ValueRef(&value).right(4)), text("éllo"));
+
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).right(1),
+            Err(EvaluateError::StringFunctionOnNonString("RIGHT".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn repeat() {
+        let text = |v: &str| Ok(Value::Str(v.to_owned()));
+        let repeat = |count: Evaluated<'_>| {
+            Evaluated::StringRef("ab")
+                .repeat(&count)
+                .and_then(|v| v.to_value())
+        };
+
+        assert_eq!(repeat(Evaluated::Literal(number("3"))), text("ababab"));
+        assert_eq!(repeat(Evaluated::Value(Value::I64(0))), text(""));
+        assert_eq!(repeat(Evaluated::Value(Value::OptI64(Some(-2)))), text(""));
+        assert_eq!(
+            repeat(Evaluated::Value(Value::I64(i64::MAX))),
+            Err(EvaluateError::StringTooLong(16 * 1024 * 1024).into())
+        );
+        assert_eq!(
+            repeat(Evaluated::Literal(number("1.5"))),
+            Err(EvaluateError::IntegerTypeRequired("REPEAT".to_owned()).into())
+        );
+        assert_eq!(
+            repeat(Evaluated::StringRef("3")),
+            Err(EvaluateError::IntegerTypeRequired("REPEAT".to_owned()).into())
+        );
+
+        let found = Evaluated::StringRef("ab").repeat(&Evaluated::Literal(AstValue::Null));
+        assert!(found.unwrap().is_null());
+    }
+
+    #[test]
+    fn reverse() {
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+
+        assert_eq!(
+            to_value(Evaluated::StringRef("Glue").reverse()),
+            Ok(Value::Str("eulG".to_owned()))
+        );
+
+        let value = Value::Str("héllo wörld 한글".to_owned());
+        let found = to_value(Evaluated::ValueRef(&value).reverse()).unwrap();
+        assert_eq!(found, Value::Str("글한 dlröw olléh".to_owned()));
+
+        if let Value::Str(found) = found {
+            assert!(std::str::from_utf8(found.as_bytes()).is_ok());
+        }
+
+        assert_eq!(
+            to_value(Evaluated::StringRef("e\u{301}").reverse()),
+            Ok(Value::Str("\u{301}e".to_owned()))
+        );
+        assert_eq!(
+            Evaluated::Literal(number("12")).reverse(),
+            Err(EvaluateError::StringFunctionOnNonString("REVERSE".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn regex_match() {
+        let matches = |target: &str, pattern: &str| {
+            Evaluated::StringRef(target).regex_match(
+                &Evaluated::Literal(AstValue::SingleQuotedString(pattern.to_owned())),
+                false,
+            )
+        };
+
+        assert_eq!(matches("A123", "^A[0-9]+$"), Ok(true));
+        assert_eq!(matches("A123B", "^A[0-9]+$"), Ok(false));
+        assert_eq!(matches("xA1", "A[0-9]"), Ok(true));
+        assert_eq!(matches("xA1", "^A"), Ok(false));
+        assert_eq!(matches("glue-sql", r"^\w+-\w+$"), Ok(true));
+        assert_eq!(matches("ab12", "^[[:alpha:]]+[[:digit:]]+$"), Ok(true));
+        assert_eq!(matches("한글", "^.{2}$"), Ok(true));
+        assert_eq!(matches("a123", "^A[0-9]+$"), Ok(false));
+
+        let code = Value::OptStr(Some("a123".to_owned()));
+        assert_eq!(
+            Evaluated::ValueRef(&code).regex_match(&Evaluated::StringRef("^A[0-9]+$"), true),
+            Ok(true)
+        );
+
+        let null = Evaluated::Literal(AstValue::Null);
+        assert_eq!(
+            Evaluated::StringRef("A1").regex_match(&null, false),
+            Ok(false)
+        );
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).regex_match(&Evaluated::StringRef("1"), false),
+            Err(EvaluateError::StringFunctionOnNonString("REGEXP".to_owned()).into())
+        );
+
+        match matches("A1", "^A[0-9") {
+            Err(crate::result::Error::Evaluate(EvaluateError::InvalidRegex(message))) => {
+                assert!(message.contains("unclosed character class"), "{}", message)
+            }
+            found => panic!("unexpected {:?}", found),
+        }
+    }
+
+    #[test]
+    fn like() {
+        let like = |target: &str, pattern: &str| {
+            Evaluated::StringRef(target).like(
+                &Evaluated::Literal(AstValue::SingleQuotedString(pattern.to_owned())),
+                true,
+                Some('\\'),
+            )
+        };
+
+        assert_eq!(like("glue@gmail.com", "%@gmail.com"), Ok(true));
+        assert_eq!(like("glue@gmail.com", "glue%"), Ok(true));
+        assert_eq!(like("glue@gmail.com", "%@naver.com"), Ok(false));
+        assert_eq!(like("glue", "gl_e"), Ok(true));
+        assert_eq!(like("glue", "g_e"), Ok(false));
+        assert_eq!(like("100%", "100\\%"), Ok(true));
+        assert_eq!(like("1000", "100\\%"), Ok(false));
+        assert_eq!(like("a_c", "a\\_c"), Ok(true));
+        assert_eq!(like("abc", "a\\_c"), Ok(false));
+        assert_eq!(like("", "%"), Ok(true));
+        assert_eq!(like("", "_"), Ok(false));
+        assert_eq!(like("abcbd", "%b_"), Ok(true));
+        assert_eq!(like("abcbd", "%b%c"), Ok(false));
+        assert_eq!(like("mississippi", "%iss%ppi"), Ok(true));
+        assert_eq!(like("mississippi", "m%ss%%s_p%i"), Ok(true));
+
+        let long = "a".repeat(5000);
+        let pathological = "%a%a%a%a%a%a%a%a%a%a%b";
+        assert_eq!(like(&long, pathological), Ok(false));
+        assert_eq!(like(&format!("{}b", long), pathological), Ok(true));
+
+        let escaped = |target: &str, pattern: &str, escape: char| {
+            Evaluated::StringRef(target).like(&Evaluated::StringRef(pattern), false, Some(escape))
+        };
+
+        assert_eq!(escaped("100%", "100!%", '!'), Ok(true));
+        assert_eq!(escaped("100% off", "100!%%", '!'), Ok(true));
+        assert_eq!(escaped("1000", "100!%", '!'), Ok(false));
+        assert_eq!(escaped("file_1", "FILE#_1", '#'), Ok(true));
+        assert_eq!(escaped("file-1", "file#_1", '#'), Ok(false));
+        assert_eq!(escaped("a#b", "a##b", '#'), Ok(true));
+        assert_eq!(escaped("ab", "a#b", '#'), Ok(true));
+        assert_eq!(escaped("100%", "100X%", 'X'), Ok(true));
+        assert_eq!(
+            escaped("100", "100!", '!'),
+            Err(EvaluateError::LikePatternEndsWithEscape.into())
+        );
+        assert_eq!(
+            like("C:\\", "C:\\"),
+            Err(EvaluateError::LikePatternEndsWithEscape.into())
+        );
+        assert_eq!(like("C:\\", "C:\\\\"), Ok(true));
+        assert_eq!(
+            Evaluated::StringRef("C:\\").like(&Evaluated::StringRef("C:\\"), true, None),
+            Ok(true)
+        );
+
+        let glue = Value::Str("Glue".to_owned());
+        let pattern = AstValue::SingleQuotedString("g%".to_owned());
+        assert_eq!(
+            Evaluated::ValueRef(&glue).like(&Evaluated::LiteralRef(&pattern), true, None),
+            Ok(false)
+        );
+        assert_eq!(
+            Evaluated::Value(glue.clone()).like(&Evaluated::LiteralRef(&pattern), false, None),
+            Ok(true)
+        );
+        assert_eq!(
+            Evaluated::LiteralRef(&pattern).like(&Evaluated::StringRef("g_"), true, None),
+            Ok(true)
+        );
+
+        let null = Evaluated::Literal(AstValue::Null);
+        assert_eq!(
+            Evaluated::StringRef("Glue").like(&null, true, None),
+            Ok(false)
+        );
+        assert_eq!(
+            Evaluated::StringRef("Glue").like(&Evaluated::Literal(number("1")), true, None),
+            Err(EvaluateError::LikeOnNonString.into())
+        );
+    }
+
+    #[test]
+    fn string_literals() {
+        let hex = |v: &str| Evaluated::Literal(AstValue::HexStringLiteral(v.to_owned()));
+        let national = |v: &str| Evaluated::Literal(AstValue::NationalStringLiteral(v.to_owned()));
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let stored = Value::OptStr(Some("Glue".to_owned()));
+
+        assert!(hex("4a") == hex("4A"));
+        assert!(hex("4a") != hex("4b"));
+        assert!(hex("41") < hex("4100"));
+        assert!(hex("ff") > hex("7f"));
+        assert!(hex("41") == text("A"));
+        assert!(hex("41") == Evaluated::StringRef("A"));
+        assert!(national("Glue") == text("Glue"));
+        assert!(national("héllo") == Evaluated::StringRef("héllo"));
+        assert!(national("b") > text("a"));
+
+        assert!(hex("476c7565") == Evaluated::ValueRef(&stored));
+        assert!(Evaluated::ValueRef(&stored) == hex("476C7565"));
+        assert!(Evaluated::ValueRef(&stored) == national("Glue"));
+        assert!(Evaluated::ValueRef(&stored) < hex("48"));
+        assert!(Evaluated::Value(Value::Str("A".to_owned())) > hex("40"));
+
+        assert!(hex("zz") != text("zz"));
+        assert_eq!(hex("4").partial_cmp(&hex("41")), None);
+        assert_eq!(
+            hex("31").try_eq(&Evaluated::Literal(number("1"))),
+            Err(EvaluateError::TypeMismatch.into())
+        );
+    }
+
+    #[test]
+    fn to_ast_value() {
+        let text = |v: &str| AstValue::SingleQuotedString(v.to_owned());
+        let literal = number("1.50");
+        let value = Value::OptStr(Some("Glue".to_owned()));
+
+        let cases = vec![
+            (Evaluated::LiteralRef(&literal), Ok(number("1.50"))),
+            (Evaluated::Literal(AstValue::Null), Ok(AstValue::Null)),
+            (Evaluated::StringRef("it's"), Ok(text("it's"))),
+            (Evaluated::ValueRef(&value), Ok(text("Glue"))),
+            (
+                Evaluated::Value(Value::Bool(true)),
+                Ok(AstValue::Boolean(true)),
+            ),
+            (Evaluated::Value(Value::OptI64(Some(-3))), Ok(number("-3"))),
+            (Evaluated::Value(Value::F64(5.0)), Ok(number("5.0"))),
+            (Evaluated::Value(Value::F64(0.25)), Ok(number("0.25"))),
+            (
+                Evaluated::Value(Value::Decimal("0.10".parse().unwrap())),
+                Ok(number("0.10")),
+            ),
+            (Evaluated::Value(Value::OptF64(None)), Ok(AstValue::Null)),
+            (Evaluated::Value(Value::Empty), Ok(AstValue::Null)),
+            (
+                Evaluated::Value(Value::F64(f64::NAN)),
+                Err(EvaluateError::UnrepresentableLiteral("NaN".to_owned()).into()),
+            ),
+            (
+                Evaluated::Value(Value::F64(f64::NEG_INFINITY)),
+                Err(EvaluateError::UnrepresentableLiteral("-inf".to_owned()).into()),
+            ),
+        ];
+
+        for (evaluated, expected) in cases {
+            assert_eq!(evaluated.to_ast_value(), expected);
+        }
+
+        let round_trip = |v: Value| {
+            let literal = Evaluated::Value(v).to_ast_value().unwrap();
+            Evaluated::Literal(literal).to_value().unwrap()
+        };
+
+        assert_eq!(round_trip(Value::I64(42)), Value::I64(42));
+        assert_eq!(round_trip(Value::F64(2.0)), Value::F64(2.0));
+        assert_eq!(round_trip(Value::F64(1e20)), Value::F64(1e20));
+        assert_eq!(
+            round_trip(Value::Str("Glue".to_owned())),
+            Value::Str("Glue".to_owned())
+        );
+    }
+
+    #[test]
+    fn parse_number() {
+        use super::{parse_number, ParsedNumber::*};
+
+        assert_eq!(parse_number("42"), Ok(Int(42)));
+        assert_eq!(parse_number("-7"), Ok(Int(-7)));
+        assert_eq!(parse_number("-9223372036854775808"), Ok(Int(i64::MIN)));
+        assert_eq!(parse_number("9223372036854775808"), Ok(UInt(1 << 63)));
+        assert_eq!(parse_number("18446744073709551615"), Ok(UInt(u64::MAX)));
+        assert_eq!(
+            parse_number("18446744073709551616"),
+            Ok(Float(1.8446744073709552e19))
+        );
+        assert_eq!(parse_number("1.5"), Ok(Float(1.5)));
+        assert_eq!(parse_number("1.0"), Ok(Float(1.0)));
+        assert_eq!(parse_number("-0.25"), Ok(Float(-0.25)));
+        assert_eq!(parse_number("1e3"), Ok(Float(1000.0)));
+        assert_eq!(
+            parse_number("1.2.3"),
+            Err(EvaluateError::InvalidNumberLiteral("1.2.3".to_owned()).into())
+        );
+
+        let literal = |v: &str| Evaluated::Literal(number(v));
+        assert_eq!(
+            literal("-9223372036854775808").abs(),
+            Ok(literal("9223372036854775808"))
+        );
+        assert_eq!(
+            literal("-9223372036854775808").unary_minus(),
+            Ok(literal("9223372036854775808"))
+        );
+        assert_eq!(
+            literal("9223372036854775808").unary_minus(),
+            Ok(literal("-9223372036854775808"))
+        );
+        assert_eq!(
+            literal("18446744073709551615").unary_minus(),
+            Ok(literal("-1.8446744073709552e19"))
+        );
+        assert_eq!(
+            literal("18446744073709551615").floor(),
+            Ok(literal("18446744073709551615"))
+        );
+    }
+
+    #[test]
+    fn literal_arithmetic() {
+        let literal = |v: &str| Evaluated::Literal(number(v));
+        let text = || Evaluated::Literal(AstValue::SingleQuotedString("a".to_owned()));
+
+        assert_eq!(literal("7").add(&literal("2")), Ok(literal("9")));
+        assert_eq!(literal("7").subtract(&literal("9")), Ok(literal("-2")));
+        assert_eq!(literal("7").multiply(&literal("-2")), Ok(literal("-14")));
+        assert_eq!(literal("7").divide(&literal("2")), Ok(literal("3")));
+        assert_eq!(literal("7").divide(&literal("-2")), Ok(literal("-3")));
+        assert_eq!(literal("1.5").add(&literal("1")), Ok(literal("2.5")));
+        assert_eq!(literal("3").divide(&literal("1.5")), Ok(literal("2.0")));
+
+        let overflow = Err(ValueError::NumericOverflow.into());
+        let min = || literal("-9223372036854775808");
+        assert_eq!(literal("9223372036854775807").add(&literal("1")), overflow);
+        assert_eq!(min().subtract(&literal("1")), overflow);
+        assert_eq!(min().divide(&literal("-1")), overflow);
+
+        let non_numeric = |left: &str, right: &str| {
+            Err(EvaluateError::ArithmeticOnNonNumeric {
+                left: left.to_owned(),
+                right: right.to_owned(),
+            }
+            .into())
+        };
+
+        assert_eq!(text().add(&literal("1")), non_numeric("'a'", "1"));
+        assert_eq!(literal("1").subtract(&text()), non_numeric("1", "'a'"));
+        assert_eq!(text().multiply(&text()), non_numeric("'a'", "'a'"));
+        assert_eq!(literal("1").divide(&text()), non_numeric("1", "'a'"));
+
+        let invalid = |v: &str| Err(EvaluateError::InvalidNumberLiteral(v.to_owned()).into());
+        assert_eq!(literal("1x").add(&literal("1")), invalid("1x"));
+        assert_eq!(literal("1").subtract(&literal("one")), invalid("one"));
+        assert_eq!(literal("").multiply(&literal("2")), invalid(""));
+        assert_eq!(literal("1..0").divide(&literal("2")), invalid("1..0"));
+    }
+
+    #[test]
+    fn unsigned_literals() {
+        let large = number("18446744073709551615");
+        let above_max = number("9223372036854775808");
+        let max = number("9223372036854775807");
+        let negative = number("-1");
+
+        assert!(
+            Evaluated::LiteralRef(&large) == Evaluated::Literal(number("18446744073709551615"))
+        );
+        assert!(Evaluated::LiteralRef(&large) != Evaluated::LiteralRef(&above_max));
+        assert!(Evaluated::LiteralRef(&above_max) > Evaluated::LiteralRef(&max));
+        assert!(Evaluated::LiteralRef(&max) < Evaluated::LiteralRef(&above_max));
+        assert!(Evaluated::LiteralRef(&negative) < Evaluated::LiteralRef(&large));
+        assert!(Evaluated::LiteralRef(&above_max) < Evaluated::LiteralRef(&large));
+
+        let found = Evaluated::LiteralRef(&above_max).add(&Evaluated::Literal(number("1")));
+        assert_eq!(found, Ok(Evaluated::Literal(number("9223372036854775809"))));
+
+        let found = Evaluated::LiteralRef(&above_max).multiply(&Evaluated::Literal(number("2")));
+        assert_eq!(found, Err(ValueError::NumericOverflow.into()));
+
+        let found = Evaluated::LiteralRef(&max).multiply(&Evaluated::Literal(number("2")));
+        assert_eq!(found, Err(ValueError::NumericOverflow.into()));
+
+        let found = Evaluated::LiteralRef(&above_max).add(&Evaluated::LiteralRef(&negative));
+        assert_eq!(
+            found,
+            Ok(Evaluated::Literal(number("9.223372036854776e18")))
+        );
+    }
+
+    #[test]
+    fn unsigned_subtract() {
+        let literal = |v| Evaluated::Literal(number(v));
+        let subtract = |a, b| literal(a).subtract(&literal(b));
+
+        assert_eq!(
+            subtract("18446744073709551615", "1"),
+            Ok(literal("18446744073709551614"))
+        );
+        assert_eq!(
+            subtract("18446744073709551615", "9223372036854775808"),
+            Ok(literal("9223372036854775807"))
+        );
+        assert_eq!(
+            subtract("18446744073709551615", "18446744073709551615"),
+            Ok(literal("0"))
+        );
+        assert_eq!(
+            subtract("9223372036854775808", "9223372036854775809"),
+            Ok(literal("-1"))
+        );
+        assert_eq!(
+            subtract("0", "9223372036854775808"),
+            Ok(literal("-9223372036854775808"))
+        );
+        assert_eq!(
+            subtract("1", "18446744073709551615"),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            subtract("0", "9223372036854775809"),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            subtract("-1", "9223372036854775808"),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            subtract("-1", "9223372036854775807"),
+            Ok(literal("-9223372036854775808"))
+        );
+        assert_eq!(
+            literal("-1").add(&literal("9223372036854775808")),
+            Ok(literal("9223372036854775807"))
+        );
+        assert_eq!(
+            literal("-1").multiply(&literal("9223372036854775808")),
+            Ok(literal("-9223372036854775808"))
+        );
+        assert_eq!(
+            literal("-2").multiply(&literal("9223372036854775808")),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            literal("-7").modulo(&literal("18446744073709551615")),
+            Ok(literal("-7"))
+        );
+        assert_eq!(
+            literal("9223372036854775808").divide(&literal("2")),
+            Ok(literal("4611686018427387904"))
+        );
+        assert_eq!(
+            literal("18446744073709551615").divide(&literal("-1")),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            literal("18446744073709551615").divide(&literal("0")),
+            Err(ValueError::DivideByZero.into())
+        );
+    }
+
+    #[test]
+    fn hash_hex() {
+        let hash = |v: Evaluated<'_>| v.hash_hex().and_then(|v| v.to_value()).unwrap();
+
+        let one = number("1");
+        let text = AstValue::SingleQuotedString("1".to_owned());
+        let value = Value::OptI64(Some(1));
+
+        assert_eq!(
+            hash(Evaluated::LiteralRef(&one)),
+            hash(Evaluated::Value(Value::I64(1)))
+        );
+        assert_eq!(
+            hash(Evaluated::ValueRef(&value)),
+            hash(Evaluated::Literal(number("1")))
+        );
+        assert_eq!(
+            hash(Evaluated::StringRef("1")),
+            hash(Evaluated::LiteralRef(&text))
+        );
+        assert_ne!(
+            hash(Evaluated::LiteralRef(&one)),
+            hash(Evaluated::LiteralRef(&text))
+        );
+        assert_ne!(
+            hash(Evaluated::LiteralRef(&one)),
+            hash(Evaluated::Value(Value::F64(1.0)))
+        );
+        assert_ne!(
+            hash(Evaluated::Value(Value::I64(1))),
+            hash(Evaluated::Value(Value::I64(2)))
+        );
+
+        assert_eq!(
+            hash(Evaluated::StringRef("")),
+            Value::Str("08d90407b578d9c8".to_owned())
+        );
+        assert!(Evaluated::Literal(AstValue::Null)
+            .hash_hex()
+            .unwrap()
+            .is_null());
+    }
+
+    #[test]
+    fn three_valued_logic() {
+        let t = || Evaluated::Literal(AstValue::Boolean(true));
+        let f = || Evaluated::Value(Value::Bool(false));
+        let n = || Evaluated::Value(Value::OptBool(None));
+        let to_bool = |v: crate::result::Result<Evaluated<'_>>| {
+            let v = v.unwrap();
+
+            (!v.is_null()).then(|| v.try_into_bool().unwrap())
+        };
+
+        let truth_table = [
+            (t(), t(), Some(true), Some(true)),
+            (t(), f(), Some(false), Some(true)),
+            (t(), n(), None, Some(true)),
+            (f(), t(), Some(false), Some(true)),
+            (f(), f(), Some(false), Some(false)),
+            (f(), n(), Some(false), None),
+            (n(), t(), None, Some(true)),
+            (n(), f(), Some(false), None),
+            (n(), n(), None, None),
+        ];
+
+        for (l, r, and, or) in truth_table.iter() {
+            assert_eq!(to_bool(l.and(r)), *and, "{} AND {}", l, r);
+            assert_eq!(to_bool(l.or(r)), *or, "{} OR {}", l, r);
+        }
+
+        assert_eq!(to_bool(t().logical_not()), Some(false));
+        assert_eq!(to_bool(f().logical_not()), Some(true));
+        assert_eq!(to_bool(n().logical_not()), None);
+
+        let required = |v: &str| Err(EvaluateError::BooleanTypeRequired(v.to_owned()).into());
+        assert_eq!(t().and(&Evaluated::Literal(number("1"))), required("1"));
+        assert_eq!(Evaluated::StringRef("true").or(&t()), required("'true'"));
+        assert_eq!(Evaluated::Value(Value::I64(0)).logical_not(), required("0"));
+    }
+
+    #[test]
+    fn try_into_bool() {
+        let text = AstValue::SingleQuotedString("true".to_owned());
+        let required = |v: &str| Err(EvaluateError::BooleanTypeRequired(v.to_owned()).into());
+
+        assert_eq!(
+            Evaluated::Literal(AstValue::Boolean(true)).try_into_bool(),
+            Ok(true)
+        );
+        assert_eq!(
+            Evaluated::Value(Value::Bool(false)).try_into_bool(),
+            Ok(false)
+        );
+        assert_eq!(
+            Evaluated::Value(Value::OptBool(Some(true))).try_into_bool(),
+            Ok(true)
+        );
+        assert_eq!(Evaluated::Literal(number("0")).try_into_bool(), Ok(false));
+        assert_eq!(Evaluated::Literal(number("0.0")).try_into_bool(), Ok(false));
+        assert_eq!(Evaluated::Literal(number("-2")).try_into_bool(), Ok(true));
+        assert_eq!(Evaluated::Value(Value::I64(0)).try_into_bool(), Ok(false));
+        assert_eq!(Evaluated::Value(Value::F64(0.5)).try_into_bool(), Ok(true));
+        assert_eq!(
+            Evaluated::Literal(AstValue::Null).try_into_bool(),
+            Ok(false)
+        );
+        assert_eq!(
+            Evaluated::Value(Value::OptBool(None)).try_into_bool(),
+            Ok(false)
+        );
+        assert_eq!(Evaluated::Value(Value::Empty).try_into_bool(), Ok(false));
+        assert_eq!(
+            Evaluated::LiteralRef(&text).try_into_bool(),
+            required("'true'")
+        );
+        assert_eq!(
+            Evaluated::StringRef("Glue").try_into_bool(),
+            required("'Glue'")
+        );
+
+        let name = Value::Str("Glue".to_owned());
+        assert_eq!(
+            Evaluated::ValueRef(&name).try_into_bool(),
+            required("'Glue'")
+        );
+    }
+
+    #[test]
+    fn display() {
+        let number = number("1.50");
+        assert_eq!(Evaluated::LiteralRef(&number).to_string(), "1.50");
+        assert_eq!(
+            Evaluated::Literal(AstValue::Number("007".to_owned())).to_string(),
+            "007"
+        );
+
+        let text = AstValue::SingleQuotedString("Glue".to_owned());
+        assert_eq!(Evaluated::LiteralRef(&text).to_string(), "'Glue'");
+        assert_eq!(
+            Evaluated::Literal(AstValue::Boolean(true)).to_string(),
+            "TRUE"
+        );
+        assert_eq!(Evaluated::Literal(AstValue::Null).to_string(), "NULL");
+        assert_eq!(Evaluated::StringRef("it's").to_string(), "'it''s'");
+
+        let value = Value::Str("SQL".to_owned());
+        assert_eq!(Evaluated::ValueRef(&value).to_string(), "'SQL'");
+        assert_eq!(Evaluated::Value(Value::I64(-3)).to_string(), "-3");
+        assert_eq!(Evaluated::Value(Value::F64(2.0)).to_string(), "2.0");
+        assert_eq!(
+            Evaluated::Value(Value::OptF64(Some(0.5))).to_string(),
+            "0.5"
+        );
+        assert_eq!(Evaluated::Value(Value::Bool(false)).to_string(), "FALSE");
+        assert_eq!(Evaluated::Value(Value::OptI64(None)).to_string(), "NULL");
+        assert_eq!(Evaluated::Value(Value::Empty).to_string(), "NULL");
+    }
+
+    #[test]
+    fn arithmetic_on_non_numeric() {
+        let one = number("1");
+        let error = |left: &str, right: &str| {
+            Err(EvaluateError::ArithmeticOnNonNumeric {
+                left: left.to_owned(),
+                right: right.to_owned(),
+            }
+            .into())
+        };
+
+        let found = Evaluated::StringRef("abc").add(&Evaluated::LiteralRef(&one));
+        assert_eq!(found, error("'abc'", "1"));
+
+        let found = Evaluated::Value(Value::F64(1.5)).subtract(&Evaluated::StringRef("abc"));
+        assert_eq!(found, error("1.5", "'abc'"));
+
+        let found = Evaluated::LiteralRef(&one).multiply(&Evaluated::StringRef("abc"));
+        assert_eq!(found, error("1", "'abc'"));
+
+        let found = Evaluated::StringRef("a").divide(&Evaluated::StringRef("b"));
+        assert_eq!(found, error("'a'", "'b'"));
+
+        let abc = Value::Str("abc".to_owned());
+        let found = Evaluated::ValueRef(&abc).add(&Evaluated::LiteralRef(&one));
+        assert_eq!(found, error("'abc'", "1"));
+
+        let found = Evaluated::Value(Value::I64(1)).modulo(&Evaluated::ValueRef(&abc));
+        assert_eq!(found, error("1", "'abc'"));
+    }
+
+    #[test]
+    fn power() {
+        let power = |b: &str, e: &str| {
+            Evaluated::Literal(number(b))
+                .power(&Evaluated::Literal(number(e)))
+                .and_then(|v| v.to_value())
+        };
+
+        assert_eq!(power("2", "10"), Ok(Value::I64(1024)));
+        assert_eq!(power("-3", "3"), Ok(Value::I64(-27)));
+        assert_eq!(power("5", "0"), Ok(Value::I64(1)));
+        assert_eq!(power("2", "-1"), Ok(Value::F64(0.5)));
+        assert_eq!(power("4", "0.5"), Ok(Value::F64(2.0)));
+        assert_eq!(power("2.5", "2"), Ok(Value::F64(6.25)));
+        assert_eq!(power("2", "63"), Err(ValueError::NumericOverflow.into()));
+        assert_eq!(
+            power("2", "5000000000"),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(power("0", "-1"), Err(ValueError::DivideByZero.into()));
+        assert_eq!(power("0.0", "-2"), Err(ValueError::DivideByZero.into()));
+
+        let base = Value::OptI64(Some(3));
+        let found = Evaluated::ValueRef(&base).power(&Evaluated::Literal(number("2")));
+        assert_eq!(found.and_then(|v| v.to_value()), Ok(Value::OptI64(Some(9))));
+
+        let found = Evaluated::StringRef("Glue").power(&Evaluated::Literal(number("2")));
+        assert_eq!(found, Err(EvaluateError::PowerOnNonNumeric.into()));
+
+        let found = Evaluated::Literal(AstValue::Null).power(&Evaluated::Literal(number("2")));
+        assert!(found.unwrap().is_null());
+    }
+
+    #[test]
+    fn decimal() {
+        let decimal = |v: &str| Value::Decimal(v.parse().unwrap());
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+
+        let tenth = decimal("0.1");
+        let found = Evaluated::ValueRef(&tenth).add(&Evaluated::Literal(number("0.2")));
+        assert_eq!(to_value(found), Ok(decimal("0.3")));
+
+        let found = Evaluated::Value(decimal("0.1")).add(&Evaluated::Value(decimal("0.2")));
+        assert!(found.unwrap() == Evaluated::Value(decimal("0.3")));
+
+        let found = Evaluated::ValueRef(&tenth).multiply(&Evaluated::Value(Value::I64(3)));
+        assert_eq!(to_value(found), Ok(decimal("0.3")));
+
+        let found = Evaluated::Value(decimal("1")).divide(&Evaluated::Literal(number("4")));
+        assert_eq!(to_value(found), Ok(decimal("0.25")));
+
+        let found = Evaluated::Value(Value::I64(1)).subtract(&Evaluated::ValueRef(&tenth));
+        assert_eq!(to_value(found), Ok(decimal("0.9")));
+
+        let found = Evaluated::ValueRef(&tenth).divide(&Evaluated::Value(decimal("0")));
+        assert_eq!(found, Err(ValueError::DivideByZero.into()));
+
+        let found = Evaluated::ValueRef(&tenth).add(&Evaluated::Value(Value::F64(0.2)));
+        assert_eq!(found, Err(ValueError::DecimalWithFloat.into()));
+
+        assert!(Evaluated::ValueRef(&tenth) == Evaluated::Literal(number("0.10")));
+        assert!(Evaluated::ValueRef(&tenth) < Evaluated::Literal(number("0.2")));
+        assert!(Evaluated::ValueRef(&tenth) < Evaluated::Value(Value::I64(1)));
+        assert!(Evaluated::Value(decimal("2.5")) > Evaluated::Value(decimal("2.49")));
+        assert_eq!(
+            Evaluated::ValueRef(&tenth).partial_cmp(&Evaluated::Value(Value::F64(0.1))),
+            None
+        );
+        assert_eq!(
+            to_value(Evaluated::ValueRef(&tenth).unary_minus()),
+            Ok(decimal("-0.1"))
+        );
+    }
+
+    #[test]
+    fn modulo() {
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+        let int = |v: i64| Evaluated::Value(Value::I64(v));
+        let literal = |v: &str| Evaluated::Literal(number(v));
+
+        assert_eq!(to_value(int(7).r#mod(&int(3))), Ok(Value::I64(1)));
+        assert_eq!(to_value(int(-7).r#mod(&int(3))), Ok(Value::I64(-1)));
+        assert_eq!(to_value(int(7).r#mod(&int(-3))), Ok(Value::I64(1)));
+        assert_eq!(to_value(int(-7).r#mod(&int(-3))), Ok(Value::I64(-1)));
+        assert_eq!(to_value(int(i64::MIN).r#mod(&int(-1))), Ok(Value::I64(0)));
+        assert_eq!(literal("-7").r#mod(&literal("3")), Ok(literal("-1")));
+        assert_eq!(literal("7").modulo(&literal("-3")), Ok(literal("1")));
+        assert_eq!(
+            literal("-9223372036854775808").r#mod(&literal("-1")),
+            Ok(literal("0"))
+        );
+        assert_eq!(
+            literal("18446744073709551615").r#mod(&literal("10")),
+            Ok(literal("5"))
+        );
+        assert_eq!(literal("-7.5").r#mod(&literal("2")), Ok(literal("-1.5")));
+
+        let nullable = Value::OptI64(Some(-7));
+        assert_eq!(
+            to_value(Evaluated::ValueRef(&nullable).r#mod(&literal("4"))),
+            Ok(Value::OptI64(Some(-3)))
+        );
+        assert_eq!(
+            to_value(Evaluated::Value(Value::F64(5.5)).r#mod(&int(-2))),
+            Ok(Value::F64(1.5))
+        );
+        assert_eq!(
+            to_value(Evaluated::Value(Value::Decimal("-5.5".parse().unwrap())).r#mod(&int(2))),
+            Ok(Value::Decimal("-1.5".parse().unwrap()))
+        );
+        assert!(int(1)
+            .r#mod(&Evaluated::Value(Value::OptI64(None)))
+            .unwrap()
+            .is_null());
+
+        let divide_by_zero = Err(ValueError::DivideByZero.into());
+        assert_eq!(int(7).r#mod(&int(0)), divide_by_zero);
+        assert_eq!(literal("7").r#mod(&literal("0")), divide_by_zero);
+        assert_eq!(literal("7").r#mod(&int(0)), divide_by_zero);
+        assert_eq!(int(7).r#mod(&literal("0.0")), divide_by_zero);
+
+        assert_eq!(
+            Evaluated::StringRef("a").r#mod(&int(2)),
+            Err(EvaluateError::ArithmeticOnNonNumeric {
+                left: "'a'".to_owned(),
+                right: "2".to_owned(),
+            }
+            .into())
+        );
+        assert_eq!(
+            Evaluated::Literal(AstValue::Boolean(true)).r#mod(&literal("2")),
+            Err(EvaluateError::LiteralModuloOnNonNumeric.into())
+        );
+    }
+
+    #[test]
+    fn divide_by_zero() {
+        let zero = number("0");
+        let one = number("1");
+        let value_zero = Value::I64(0);
+        let value_one = Value::I64(1);
+        let divide_by_zero = Err(ValueError::DivideByZero.into());
+
+        let dividends = || {
+            vec![
+                Evaluated::LiteralRef(&one),
+                Evaluated::Literal(one.clone()),
+                Evaluated::ValueRef(&value_one),
+                Evaluated::Value(value_one.clone()),
+            ]
+        };
+        let divisors = || {
+            vec![
+                Evaluated::LiteralRef(&zero),
+                Evaluated::Literal(zero.clone()),
+                Evaluated::ValueRef(&value_zero),
+                Evaluated::Value(value_zero.clone()),
+                Evaluated::Value(Value::OptF64(Some(0.0))),
+            ]
+        };
+
+        for l in dividends() {
+            for r in divisors() {
+                assert_eq!(l.divide(&r), divide_by_zero);
+            }
+        }
+
+        let float_zero = number("0.0");
+        assert_eq!(
+            Evaluated::LiteralRef(&one).divide(&Evaluated::LiteralRef(&float_zero)),
+            divide_by_zero
+        );
+    }
+
+    #[test]
+    fn temporal() {
+        use chrono::NaiveDate;
+
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+
+        let created_at = Value::Timestamp(date(2021, 1, 1).and_hms_opt(12, 30, 0).unwrap());
+        let created_at = Evaluated::ValueRef(&created_at);
+
+        assert_eq!(
+            created_at.partial_cmp(&text("2021-01-01")),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            text("2021-01-02").partial_cmp(&created_at),
+            Some(Ordering::Greater)
+        );
+        assert!(created_at == text("2021-01-01 12:30:00"));
+        assert!(created_at == text("2021-01-01T12:30:00.000"));
+        assert!(created_at == text("2021-01-01T21:30:00+09:00"));
+        assert!(created_at == Evaluated::StringRef("2021-01-01 12:30:00"));
+        assert!(created_at != text("2021-01-01"));
+
+        let first = Evaluated::Value(Value::Date(date(2020, 2, 29)));
+        let second = Evaluated::Value(Value::OptDate(Some(date(2021, 1, 1))));
+        assert_eq!(first.partial_cmp(&second), Some(Ordering::Less));
+        assert_eq!(second.try_partial_cmp(&first), Ok(Some(Ordering::Greater)));
+        assert_eq!(second.partial_cmp(&created_at), Some(Ordering::Less));
+        assert!(second == text("2021-01-01"));
+
+        assert_eq!(first.partial_cmp(&text("yesterday")), None);
+        assert_eq!(
+            first.try_partial_cmp(&text("2021-02-30")),
+            Err(EvaluateError::InvalidDateLiteral("2021-02-30".to_owned()).into())
+        );
+        assert_eq!(
+            text("soon").try_eq(&created_at),
+            Err(EvaluateError::InvalidDateLiteral("soon".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn interval_arithmetic() {
+        use chrono::NaiveDate;
+        use sqlparser::ast::DateTimeField;
+
+        let date = |y, m, d| Value::Date(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        let interval = |v: &str, leading_field| {
+            Evaluated::Literal(AstValue::Interval {
+                value: v.to_owned(),
+                leading_field,
+                leading_precision: None,
+                last_field: None,
+                fractional_seconds_precision: None,
+            })
+        };
+
+        let due_date = date(2021, 2, 25);
+        let due_date = Evaluated::ValueRef(&due_date);
+        let week = interval("7 days", None);
+
+        assert_eq!(
+            due_date.add(&Evaluated::Literal(number("7"))),
+            Ok(Evaluated::Value(date(2021, 3, 4)))
+        );
+        assert_eq!(
+            Evaluated::Literal(number("7")).add(&due_date),
+            Ok(Evaluated::Value(date(2021, 3, 4)))
+        );
+        assert_eq!(due_date.add(&week), Ok(Evaluated::Value(date(2021, 3, 4))));
+        assert_eq!(week.add(&due_date), Ok(Evaluated::Value(date(2021, 3, 4))));
+        assert_eq!(
+            due_date.subtract(&interval("1", Some(DateTimeField::Month))),
+            Ok(Evaluated::Value(date(2021, 1, 25)))
+        );
+        assert_eq!(
+            Evaluated::Value(date(2021, 3, 4)).subtract(&due_date),
+            Ok(Evaluated::Value(Value::I64(7)))
+        );
+        assert!(Evaluated::Value(Value::OptDate(None))
+            .add(&week)
+            .unwrap()
+            .is_null());
+
+        assert_eq!(
+            week.subtract(&due_date),
+            Err(EvaluateError::ArithmeticOnNonNumeric {
+                left: "INTERVAL '7 days'".to_owned(),
+                right: "DATE '2021-02-25'".to_owned(),
+            }
+            .into())
+        );
+        assert_eq!(
+            due_date.add(&interval("7 fortnights", None)),
+            Err(ValueError::FailedToParseInterval("INTERVAL '7 fortnights'".to_owned()).into())
+        );
+        assert_eq!(
+            due_date.multiply(&Evaluated::Literal(number("2"))),
+            Err(ValueError::MultiplyOnNonNumeric.into())
+        );
+    }
+
+    #[test]
+    fn extract() {
+        use chrono::NaiveDate;
+        use sqlparser::ast::DateTimeField::*;
+
+        let created_at = NaiveDate::from_ymd_opt(2021, 3, 14)
+            .unwrap()
+            .and_hms_opt(15, 9, 26)
+            .unwrap();
+        let created_at = Value::Timestamp(created_at);
+        let created_at = Evaluated::ValueRef(&created_at);
+        let int = |v| Ok(Evaluated::Value(Value::I64(v)));
+
+        assert_eq!(created_at.extract(Year), int(2021));
+        assert_eq!(created_at.extract(Month), int(3));
+        assert_eq!(created_at.extract(Day), int(14));
+        assert_eq!(created_at.extract(Hour), int(15));
+        assert_eq!(created_at.extract(Minute), int(9));
+        assert_eq!(created_at.extract(Second), int(26));
+
+        let day = Value::OptDate(NaiveDate::from_ymd_opt(2020, 2, 29));
+        assert_eq!(
+            Evaluated::Value(day).extract(Day),
+            Ok(Evaluated::Value(Value::OptI64(Some(29))))
+        );
+        assert_eq!(
+            Evaluated::Value(Value::Date(NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()))
+                .extract(Hour),
+            int(0)
+        );
+        assert_eq!(
+            Evaluated::StringRef("2021-03-14 15:09:26").extract(Minute),
+            int(9)
+        );
+        assert!(Evaluated::Value(Value::OptTimestamp(None))
+            .extract(Year)
+            .unwrap()
+            .is_null());
+
+        let text = Evaluated::Literal(AstValue::SingleQuotedString("Glue".to_owned()));
+        assert_eq!(
+            text.extract(Year),
+            Err(EvaluateError::InvalidDateLiteral("Glue".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::Literal(number("2021")).extract(Year),
+            Err(EvaluateError::ExtractOnNonDate.into())
+        );
+    }
+
+    #[test]
+    fn now() {
+        use chrono::{NaiveDate, NaiveDateTime};
+        use sqlparser::ast::{Expr, Function, Ident, ObjectName};
+
+        use crate::executor::evaluate::evaluate_function;
+
+        let fixed = || -> NaiveDateTime {
+            NaiveDate::from_ymd_opt(2021, 3, 14)
+                .unwrap()
+                .and_hms_opt(15, 9, 26)
+                .unwrap()
+        };
+        let expected = Evaluated::Value(Value::Timestamp(fixed()));
+
+        assert_eq!(Evaluated::now(&fixed), expected);
+
+        let function = |name: &str, args| Function {
+            name: ObjectName(vec![Ident::new(name)]),
+            args,
+            over: None,
+            distinct: false,
+        };
+
+        assert_eq!(
+            evaluate_function(&function("now", vec![]), &fixed),
+            Ok(Some(expected.clone()))
+        );
+        assert_eq!(
+            evaluate_function(&function("CURRENT_TIMESTAMP", vec![]), &fixed),
+            Ok(Some(expected))
+        );
+        assert_eq!(
+            evaluate_function(&function("COUNT", vec![Expr::Wildcard]), &fixed),
+            Ok(None)
+        );
+        assert_eq!(
+            evaluate_function(&function("NOW", vec![Expr::Wildcard]), &fixed),
+            Err(EvaluateError::FunctionArgsLengthNotMatching {
+                name: "NOW".to_owned(),
+                expected: 0,
+                found: 1,
+            }
+            .into())
+        );
+    }
+
+    #[test]
+    fn comparison_mode() {
+        use super::ComparisonMode::{Coercing, Strict};
+
+        let price = Value::I64(12);
+        let price = Evaluated::ValueRef(&price);
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let number_value = |v| Evaluated::Value(Value::F64(v));
+
+        fn mismatch<T>() -> crate::result::Result<T> {
+            Err(EvaluateError::TypeMismatch.into())
+        }
+
+        assert_eq!(ComparisonMode::default(), Strict);
+
+        assert_eq!(price.try_partial_cmp_with(&text("10"), Strict), mismatch());
+        assert_eq!(price.try_eq_with(&text("12"), Strict), mismatch());
+        assert_eq!(
+            price.try_partial_cmp_with(&text("10"), Coercing),
+            Ok(Some(Ordering::Greater))
+        );
+        assert_eq!(
+            text(" 12.5 ").try_partial_cmp_with(&price, Coercing),
+            Ok(Some(Ordering::Greater))
+        );
+        assert_eq!(price.try_eq_with(&text("12"), Coercing), Ok(true));
+        assert_eq!(
+            Evaluated::StringRef("12.0").try_eq_with(&price, Coercing),
+            Ok(true)
+        );
+        let stored = Value::Str("7".to_owned());
+        assert_eq!(
+            Evaluated::ValueRef(&stored)
+                .try_partial_cmp_with(&Evaluated::Literal(number("10")), Coercing),
+            Ok(Some(Ordering::Less))
+        );
+        assert_eq!(price.try_eq_with(&text("twelve"), Coercing), Ok(false));
+        assert_eq!(
+            text("twelve").try_partial_cmp_with(&price, Coercing),
+            Ok(Some(Ordering::Greater))
+        );
+
+        let imported = Value::Str("007".to_owned());
+        let imported = Evaluated::ValueRef(&imported);
+        let seven = Evaluated::Literal(number("7"));
+        assert_eq!(imported.try_eq_with(&seven, Coercing), Ok(true));
+        assert_eq!(seven.try_eq_with(&imported, Coercing), Ok(true));
+        assert_eq!(imported.try_eq_with(&seven, Strict), mismatch());
+        assert_eq!(
+            text("-007.50").try_eq_with(&number_value(-7.5), Coercing),
+            Ok(true)
+        );
+        assert_eq!(text("\t7 ").try_eq_with(&seven, Coercing), Ok(true));
+        assert_eq!(text("7a").try_eq_with(&seven, Coercing), Ok(false));
+        assert_eq!(text("7 7").try_eq_with(&seven, Coercing), Ok(false));
+        assert_eq!(
+            text("7").try_eq_with(&Evaluated::Value(Value::I64(7)), Coercing),
+            Ok(true)
+        );
+
+        assert_eq!(text("a").try_eq_with(&text("a"), Coercing), Ok(true));
+        assert_eq!(
+            price.try_partial_cmp_with(&Evaluated::Literal(number("10")), Strict),
+            Ok(Some(Ordering::Greater))
+        );
+    }
+
+    #[test]
+    fn into_owned() {
+        let literal = number("1.5");
+        let value = Value::OptStr(Some("Glue".to_owned()));
+
+        let owned = |v: Evaluated<'_>| -> Evaluated<'static> { v.into_owned() };
+
+        for evaluated in [
+            Evaluated::LiteralRef(&literal),
+            Evaluated::Literal(number("-3")),
+            Evaluated::StringRef("Glue"),
+            Evaluated::ValueRef(&value),
+            Evaluated::Value(Value::I64(7)),
+        ] {
+            let found = owned(evaluated.clone());
+
+            assert_eq!(found, evaluated);
+            assert_eq!(found.partial_cmp(&evaluated), Some(Ordering::Equal));
+            assert_eq!(found.to_string(), evaluated.to_string());
+        }
+
+        assert!(matches!(
+            Evaluated::LiteralRef(&literal).into_owned(),
+            Evaluated::Literal(_)
+        ));
+        assert!(matches!(
+            Evaluated::StringRef("Glue").into_owned(),
+            Evaluated::Value(Value::Str(_))
+        ));
+        assert!(matches!(
+            Evaluated::ValueRef(&value).into_owned(),
+            Evaluated::Value(Value::OptStr(_))
+        ));
+        assert!(owned(Evaluated::Literal(AstValue::Null)).is_null());
+    }
+
+    #[test]
+    fn saturating() {
+        let int = |v| Evaluated::Value(Value::I64(v));
+        let literal = |v: i64| Evaluated::Literal(number(&v.to_string()));
+
+        let pairs = [
+            (int(i64::MAX).add_saturating(&int(1)), int(i64::MAX)),
+            (int(i64::MIN).add_saturating(&literal(-1)), int(i64::MIN)),
+            (
+                literal(i64::MAX).add_saturating(&literal(i64::MAX)),
+                literal(i64::MAX),
+            ),
+            (int(i64::MIN).subtract_saturating(&int(1)), int(i64::MIN)),
+            (
+                literal(i64::MAX).subtract_saturating(&int(-1)),
+                int(i64::MAX),
+            ),
+            (
+                literal(i64::MIN).subtract_saturating(&literal(1)),
+                literal(i64::MIN),
+            ),
+            (int(i64::MAX).multiply_saturating(&int(2)), int(i64::MAX)),
+            (int(i64::MAX).multiply_saturating(&int(-2)), int(i64::MIN)),
+            (
+                literal(i64::MIN).multiply_saturating(&literal(-1)),
+                literal(i64::MAX),
+            ),
+            (
+                Evaluated::Value(Value::OptI64(Some(i64::MAX))).add_saturating(&int(1)),
+                Evaluated::Value(Value::OptI64(Some(i64::MAX))),
+            ),
+            (int(2).add_saturating(&int(3)), int(5)),
+            (int(2).subtract_saturating(&literal(3)), int(-1)),
+            (literal(2).multiply_saturating(&literal(3)), literal(6)),
+        ];
+
+        for (found, expected) in pairs.iter() {
+            let found = found.as_ref().unwrap();
+
+            assert_eq!(found, expected);
+            assert_eq!(
+                std::mem::discriminant(found),
+                std::mem::discriminant(expected)
+            );
+        }
+
+        assert_eq!(
+            int(i64::MAX).add(&int(1)),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            int(1).add_saturating(&Evaluated::StringRef("a")),
+            Err(EvaluateError::ArithmeticOnNonNumeric {
+                left: "1".to_owned(),
+                right: "'a'".to_owned(),
+            }
+            .into())
+        );
+    }
+
+    #[test]
+    fn multiply_widening() {
+        use super::OverflowMode::{Checked, Widening};
+
+        let int = |v| Evaluated::Value(Value::I64(v));
+        let max = || Evaluated::Literal(number(&i64::MAX.to_string()));
+        let widened = i64::MAX as f64 * 2.0;
+
+        assert_eq!(OverflowMode::default(), Checked);
+
+        assert_eq!(
+            max().multiply_with(&Evaluated::Literal(number("2")), Checked),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            int(i64::MAX).multiply_with(&int(2), Checked),
+            Err(ValueError::NumericOverflow.into())
+        );
+
+        let found = max().multiply_with(&Evaluated::Literal(number("2")), Widening);
+        assert_eq!(
+            found,
+            Ok(Evaluated::Literal(number(&format!("{:?}", widened))))
+        );
+        assert!(matches!(found, Ok(Evaluated::Literal(_))));
+        assert_eq!(
+            int(i64::MAX).multiply_with(&int(-2), Widening),
+            Ok(Evaluated::Value(Value::F64(-widened)))
+        );
+        assert!(matches!(
+            Evaluated::Value(Value::OptI64(Some(i64::MAX))).multiply_with(&int(2), Widening),
+            Ok(Evaluated::Value(Value::OptF64(Some(_))))
+        ));
+
+        let found = Evaluated::Literal(number("3")).multiply_with(&int(4), Widening);
+        assert_eq!(found, Ok(int(12)));
+        assert!(matches!(found, Ok(Evaluated::Value(Value::I64(12)))));
+        assert!(matches!(
+            Evaluated::Literal(number("3")).multiply_with(&Evaluated::Literal(number("4")), Widening),
+            Ok(Evaluated::Literal(AstValue::Number(v))) if v == "12"
+        ));
+    }
+
+    #[test]
+    fn div_rem() {
+        let int = |v| Evaluated::Value(Value::I64(v));
+        let pair = |q, r| Ok((int(q), int(r)));
+
+        assert_eq!(int(7).div_rem(&int(3)), pair(2, 1));
+        assert_eq!(int(-7).div_rem(&int(3)), pair(-2, -1));
+        assert_eq!(int(7).div_rem(&int(-3)), pair(-2, 1));
+        assert_eq!(int(-7).div_rem(&int(-3)), pair(2, -1));
+        assert_eq!(int(6).div_rem(&int(3)), pair(2, 0));
+        assert_eq!(
+            Evaluated::Literal(number("7")).div_rem(&Evaluated::Literal(number("3"))),
+            pair(2, 1)
+        );
+        assert_eq!(int(i64::MIN).div_rem(&int(1)), pair(i64::MIN, 0));
+
+        let (quotient, remainder) = int(7)
+            .div_rem(&Evaluated::Value(Value::OptI64(None)))
+            .unwrap();
+        assert!(quotient.is_null() && remainder.is_null());
+
+        assert_eq!(
+            int(7).div_rem(&int(0)),
+            Err(ValueError::DivideByZero.into())
+        );
+        assert_eq!(
+            int(i64::MIN).div_rem(&int(-1)),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            int(7).div_rem(&Evaluated::Literal(number("2.5"))),
+            Err(EvaluateError::IntegerTypeRequired("DIV_REM".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::StringRef("7").div_rem(&int(2)),
+            Err(EvaluateError::IntegerTypeRequired("DIV_REM".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn ascii_chr() {
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let int = |v| Evaluated::Value(Value::I64(v));
+        let string = |v: &str| Ok(Evaluated::Value(Value::Str(v.to_owned())));
+
+        assert_eq!(text("A").ascii(), Ok(int(65)));
+        assert_eq!(text("glue").ascii(), Ok(int(103)));
+        assert_eq!(text("é").ascii(), Ok(int(233)));
+        assert_eq!(Evaluated::StringRef("😀").ascii(), Ok(int(0x1F600)));
+        assert_eq!(text("").ascii(), Ok(int(0)));
+        assert_eq!(
+            int(65).ascii(),
+            Err(EvaluateError::StringFunctionOnNonString("ASCII".to_owned()).into())
+        );
+
+        assert_eq!(int(65).chr(), string("A"));
+        assert_eq!(Evaluated::Literal(number("122")).chr(), string("z"));
+        assert_eq!(int(233).chr(), string("é"));
+        assert_eq!(int(0x1F600).chr(), string("😀"));
+        assert!(Evaluated::Value(Value::OptI64(None))
+            .chr()
+            .unwrap()
+            .is_null());
+
+        for code in [-1, 0xD800, 0x11_0000, i64::MAX] {
+            assert_eq!(
+                int(code).chr(),
+                Err(EvaluateError::InvalidCodePoint(code).into())
+            );
+        }
+        assert_eq!(
+            text("A").chr(),
+            Err(EvaluateError::IntegerTypeRequired("CHR".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn pad() {
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let int = |v| Evaluated::Value(Value::I64(v));
+        let string = |v: &str| Ok(Evaluated::Value(Value::Str(v.to_owned())));
+
+        assert_eq!(text("7").lpad(&int(3), Some(&text("0"))), string("007"));
+        assert_eq!(text("7").rpad(&int(3), Some(&text("0"))), string("700"));
+        assert_eq!(text("ab").lpad(&int(4), None), string("  ab"));
+        assert_eq!(text("ab").rpad(&int(4), None), string("ab  "));
+        assert_eq!(
+            text("ab").lpad(&int(7), Some(&text("xyz"))),
+            string("xyzxyab")
+        );
+        assert_eq!(text("ab").rpad(&int(5), Some(&text("xy"))), string("abxyx"));
+        assert_eq!(
+            text("héllo").lpad(&int(7), Some(&text("é"))),
+            string("ééhéllo")
+        );
+
+        assert_eq!(text("glue").lpad(&int(2), Some(&text("0"))), string("gl"));
+        assert_eq!(text("glue").rpad(&int(2), None), string("gl"));
+        assert_eq!(text("héllo").rpad(&int(2), None), string("hé"));
+        assert_eq!(text("glue").lpad(&int(4), None), string("glue"));
+        assert_eq!(text("ab").lpad(&int(5), Some(&text(""))), string("ab"));
+        assert_eq!(text("glue").lpad(&int(0), None), string(""));
+        assert_eq!(text("glue").rpad(&int(-3), None), string(""));
+
+        assert!(text("ab")
+            .lpad(&Evaluated::Literal(AstValue::Null), None)
+            .unwrap()
+            .is_null());
+        assert!(text("ab")
+            .rpad(&int(3), Some(&Evaluated::Value(Value::OptStr(None))))
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            int(7).lpad(&int(3), Some(&text("0"))),
+            Err(EvaluateError::StringFunctionOnNonString("LPAD".to_owned()).into())
+        );
+        assert_eq!(
+            text("7").rpad(&text("3"), None),
+            Err(EvaluateError::IntegerTypeRequired("RPAD".to_owned()).into())
+        );
+        assert_eq!(
+            text("7").lpad(&int(i64::MAX), None),
+            Err(EvaluateError::StringTooLong(super::MAX_STRING_LENGTH).into())
+        );
+    }
+
+    #[test]
+    fn initcap() {
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let string = |v: &str| Ok(Evaluated::Value(Value::Str(v.to_owned())));
+
+        assert_eq!(text("hello world").initcap(), string("Hello World"));
+        assert_eq!(text("hELLO   wORLD").initcap(), string("Hello   World"));
+        assert_eq!(text("Hello World").initcap(), string("Hello World"));
+        assert_eq!(text("HELLO").initcap(), string("Hello"));
+        assert_eq!(
+            text("...hello, world!").initcap(),
+            string("...Hello, World!")
+        );
+        assert_eq!(text("o'neil-smith").initcap(), string("O'Neil-Smith"));
+        assert_eq!(text("snake_case_name").initcap(), string("Snake_Case_Name"));
+        assert_eq!(text("3rd street").initcap(), string("3rd Street"));
+        assert_eq!(text("élan ßtraße").initcap(), string("Élan SStraße"));
+        assert_eq!(text("").initcap(), string(""));
+
+        assert!(Evaluated::Value(Value::OptStr(None))
+            .initcap()
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).initcap(),
+            Err(EvaluateError::StringFunctionOnNonString("INITCAP".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn split_part() {
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let string = |v: &str| Ok(Evaluated::Value(Value::Str(v.to_owned())));
+        let path = text("usr/local/bin");
+        let slash = text("/");
+
+        assert_eq!(path.split_part(&slash, 1), string("usr"));
+        assert_eq!(path.split_part(&slash, 2), string("local"));
+        assert_eq!(path.split_part(&slash, 3), string("bin"));
+        assert_eq!(path.split_part(&slash, 4), string(""));
+        assert_eq!(path.split_part(&slash, i64::MAX), string(""));
+        assert_eq!(path.split_part(&text("local"), 2), string("/bin"));
+        assert_eq!(text("a,,b").split_part(&text(","), 2), string(""));
+        assert_eq!(text("a,,b").split_part(&text(","), 3), string("b"));
+        assert_eq!(text("abc").split_part(&text(","), 1), string("abc"));
+
+        assert!(Evaluated::Value(Value::OptStr(None))
+            .split_part(&slash, 1)
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            path.split_part(&text(""), 1),
+            Err(EvaluateError::EmptyDelimiter("SPLIT_PART".to_owned()).into())
+        );
+        assert_eq!(
+            path.split_part(&slash, 0),
+            Err(EvaluateError::FieldPositionNotPositive(0).into())
+        );
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).split_part(&slash, 1),
+            Err(EvaluateError::StringFunctionOnNonString("SPLIT_PART".to_owned()).into())
+        );
+        assert_eq!(
+            path.split_part(&Evaluated::Value(Value::I64(1)), 1),
+            Err(EvaluateError::StringFunctionOnNonString("SPLIT_PART".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn format() {
+        use chrono::NaiveDate;
+
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let string = |v: &str| Ok(Evaluated::Value(Value::Str(v.to_owned())));
+        let date = Value::Date(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
+
+        assert_eq!(
+            text("%s has %s rows").format(&[text("Item"), Evaluated::Literal(number("3"))]),
+            string("Item has 3 rows")
+        );
+        assert_eq!(
+            text("%s, %s, %s, [%s]").format(&[
+                Evaluated::Value(Value::Bool(true)),
+                Evaluated::Value(Value::F64(1.5)),
+                Evaluated::ValueRef(&date),
+                Evaluated::Literal(AstValue::Null),
+            ]),
+            string("TRUE, 1.5, 2021-01-01, []")
+        );
+        assert_eq!(
+            text("%s rows").format(&[Evaluated::Value(Value::OptF64(Some(3.0)))]),
+            string("3.0 rows")
+        );
+        assert_eq!(
+            text("100%% of %s, %d").format(&[Evaluated::StringRef("%s")]),
+            string("100% of %s, %d")
+        );
+        assert_eq!(
+            text("no placeholders").format(&[]),
+            string("no placeholders")
+        );
+        assert_eq!(text("ends with %").format(&[]), string("ends with %"));
+
+        assert_eq!(
+            text("%s and %s").format(&[text("one")]),
+            Err(EvaluateError::FormatArgMismatch {
+                expected: 2,
+                found: 1,
+            }
+            .into())
+        );
+        assert_eq!(
+            text("%%s").format(&[text("one")]),
+            Err(EvaluateError::FormatArgMismatch {
+                expected: 0,
+                found: 1,
+            }
+            .into())
+        );
+        assert!(Evaluated::Literal(AstValue::Null)
+            .format(&[text("one")])
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).format(&[]),
+            Err(EvaluateError::StringFunctionOnNonString("FORMAT".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn collation() {
+        use super::Collation::{Binary, NoCase};
+
+        let sorted = |collation| {
+            let mut items = ["b", "A", "a", "B"]
+                .iter()
+                .map(|v| Evaluated::Value(Value::Str((*v).to_owned())))
+                .collect::<Vec<_>>();
+            items.sort_by(|l, r| l.order_cmp_collated(r, false, collation));
+
+            items.iter().map(|v| v.to_string()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(Collation::default(), Binary);
+        assert_eq!(sorted(Binary), ["'A'", "'B'", "'a'", "'b'"]);
+        assert_eq!(sorted(NoCase), ["'A'", "'a'", "'b'", "'B'"]);
+
+        let upper = Evaluated::StringRef("GLUE");
+        let lower = Evaluated::Literal(AstValue::SingleQuotedString("glue".to_owned()));
+        assert!(!upper.eq_collated(&lower, Binary));
+        assert!(upper.eq_collated(&lower, NoCase));
+        assert!(Evaluated::StringRef("ÉCOLE").eq_collated(&Evaluated::StringRef("école"), NoCase));
+        assert_eq!(
+            Evaluated::StringRef("a").partial_cmp_collated(&Evaluated::StringRef("B"), Binary),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            Evaluated::StringRef("a").partial_cmp_collated(&Evaluated::StringRef("B"), NoCase),
+            Some(Ordering::Less)
+        );
+
+        let one = Evaluated::Value(Value::I64(1));
+        let also_one = Evaluated::Literal(number("1.0"));
+        assert!(one.eq_collated(&also_one, NoCase));
+        assert_eq!(
+            one.partial_cmp_collated(&Evaluated::Literal(number("2")), NoCase),
+            Some(Ordering::Less)
+        );
+        assert_eq!(one.partial_cmp_collated(&lower, NoCase), None);
+        assert!(!Evaluated::Literal(AstValue::Null).eq_collated(&lower, NoCase));
+    }
+
+    #[test]
+    fn is_distinct_from() {
+        let null = || Evaluated::Literal(AstValue::Null);
+        let none = Value::OptI64(None);
+        let one = Value::I64(1);
+        let also_one = Value::OptI64(Some(1));
+        let two = Value::I64(2);
+
+        let cases = vec![
+            (null(), null(), false),
+            (null(), Evaluated::ValueRef(&none), false),
+            (
+                Evaluated::ValueRef(&none),
+                Evaluated::ValueRef(&none),
+                false,
+            ),
+            (null(), Evaluated::ValueRef(&one), true),
+            (Evaluated::ValueRef(&one), Evaluated::ValueRef(&none), true),
+            (Evaluated::StringRef("a"), null(), true),
+            (
+                Evaluated::ValueRef(&one),
+                Evaluated::ValueRef(&also_one),
+                false,
+            ),
+            (
+                Evaluated::ValueRef(&one),
+                Evaluated::Literal(number("1")),
+                false,
+            ),
+            (Evaluated::ValueRef(&one), Evaluated::ValueRef(&two), true),
+            (Evaluated::StringRef("a"), Evaluated::StringRef("a"), false),
+            (Evaluated::StringRef("a"), Evaluated::StringRef("b"), true),
+        ];
+
+        for (l, r, distinct) in cases {
+            assert_eq!(l.is_distinct_from(&r), distinct, "{} and {}", l, r);
+            assert_eq!(r.is_distinct_from(&l), distinct, "{} and {}", r, l);
+            assert_eq!(l.is_not_distinct_from(&r), !distinct, "{} and {}", l, r);
+        }
+    }
+
+    #[test]
+    fn divide_with() {
+        use DivisionMode::{Integer, True};
+
+        let int = |v| Evaluated::Value(Value::I64(v));
+        let literal = |v| Evaluated::Literal(number(v));
+
+        assert_eq!(DivisionMode::default(), Integer);
+        assert_eq!(int(7).divide_with(&int(2), Integer), Ok(int(3)));
+        assert_eq!(
+            int(7).divide_with(&int(2), True),
+            Ok(Evaluated::Value(Value::F64(3.5)))
+        );
+        assert_eq!(int(6).divide_with(&int(2), True), Ok(int(3)));
+        assert_eq!(
+            int(-7).divide_with(&int(2), True),
+            Ok(Evaluated::Value(Value::F64(-3.5)))
+        );
+        assert_eq!(
+            literal("7").divide_with(&literal("2"), Integer),
+            Ok(literal("3"))
+        );
+        assert_eq!(
+            literal("7").divide_with(&literal("2"), True),
+            Ok(literal("3.5"))
+        );
+        assert_eq!(
+            Evaluated::Value(Value::OptI64(Some(7))).divide_with(&literal("2"), True),
+            Ok(Evaluated::Value(Value::OptF64(Some(3.5))))
+        );
+        assert_eq!(
+            literal("7.0").divide_with(&literal("2"), True),
+            Ok(literal("3.5"))
+        );
+        assert_eq!(
+            int(7).divide_with(&int(0), True),
+            Err(ValueError::DivideByZero.into())
+        );
+        for mode in [Integer, True] {
+            assert_eq!(
+                int(i64::MIN).divide_with(&int(-1), mode),
+                Err(ValueError::NumericOverflow.into())
+            );
+            assert_eq!(
+                literal("-9223372036854775808").divide_with(&literal("-1"), mode),
+                Err(ValueError::NumericOverflow.into())
+            );
+            assert_eq!(int(i64::MIN).divide_with(&int(1), mode), Ok(int(i64::MIN)));
+        }
+
+        assert_eq!(int(7).div(&int(2)), Ok(int(3)));
+        assert_eq!(literal("7").div(&literal("2")), Ok(int(3)));
+        assert_eq!(int(-7).div(&int(2)), Ok(int(-3)));
+        assert!(int(7)
+            .div(&Evaluated::Literal(AstValue::Null))
+            .unwrap()
+            .is_null());
+        assert_eq!(int(7).div(&int(0)), Err(ValueError::DivideByZero.into()));
+        assert_eq!(
+            int(i64::MIN).div(&int(-1)),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            literal("7.5").div(&int(2)),
+            Err(EvaluateError::IntegerTypeRequired("DIV".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        let bytes = |v: &[u8]| Evaluated::Value(Value::Bytes(v.to_vec()));
+        let hex = |v: &str| Evaluated::Literal(AstValue::HexStringLiteral(v.to_owned()));
+
+        let digest = Value::OptBytes(Some(vec![0xde, 0xad, 0xbe, 0xef]));
+        let digest = Evaluated::ValueRef(&digest);
+
+        assert!(digest == bytes(&[0xde, 0xad, 0xbe, 0xef]));
+        assert!(digest == hex("deadbeef"));
+        assert!(hex("DEADBEEF") == digest);
+        assert_eq!(digest.try_eq(&hex("deadbeef")), Ok(true));
+        assert!(digest != hex("deadbe"));
+        assert!(digest != Evaluated::StringRef("deadbeef"));
+
+        assert_eq!(
+            bytes(&[0xde, 0xad]).partial_cmp(&digest),
+            Some(Ordering::Less)
+        );
+        assert_eq!(digest.partial_cmp(&hex("df")), Some(Ordering::Less));
+        assert_eq!(digest.partial_cmp(&hex("deadbeef00")), Some(Ordering::Less));
+        assert_eq!(
+            digest.try_partial_cmp(&bytes(&[])),
+            Ok(Some(Ordering::Greater))
+        );
+        assert_eq!(digest.to_string(), "X'DEADBEEF'");
+
+        assert!(digest != hex("deadbee"));
+        assert_eq!(
+            digest.try_eq(&hex("deadbee")),
+            Err(EvaluateError::InvalidHexLiteral("deadbee".to_owned()).into())
+        );
+        assert_eq!(
+            hex("zz").try_partial_cmp(&digest),
+            Err(EvaluateError::InvalidHexLiteral("zz".to_owned()).into())
+        );
+        assert!(!Evaluated::Value(Value::OptBytes(None))
+            .is_distinct_from(&Evaluated::Literal(AstValue::Null)));
+    }
+
+    #[test]
+    fn base64() {
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let bytes = |v: &[u8]| Evaluated::Value(Value::Bytes(v.to_vec()));
+        let encoded = |v: &str| Ok(Evaluated::Value(Value::Str(v.to_owned())));
+
+        assert_eq!(text("").encode_base64(), encoded(""));
+        assert_eq!(text("f").encode_base64(), encoded("Zg=="));
+        assert_eq!(text("fo").encode_base64(), encoded("Zm8="));
+        assert_eq!(text("foo").encode_base64(), encoded("Zm9v"));
+        assert_eq!(text("foobar").encode_base64(), encoded("Zm9vYmFy"));
+        assert_eq!(bytes(&[0xff, 0xfe]).encode_base64(), encoded("//4="));
+        assert_eq!(
+            Evaluated::Literal(AstValue::HexStringLiteral("DEADBEEF".to_owned())).encode_base64(),
+            encoded("3q2+7w==")
+        );
+        assert_eq!(text("héllo").encode(&text("BASE64")), encoded("aMOpbGxv"));
+
+        for v in [&b""[..], b"f", b"fo", b"foo", &[0, 0xff, 0x10, 0x80]] {
+            let found = bytes(v).encode_base64().and_then(|v| v.decode_base64());
+
+            assert_eq!(found, Ok(bytes(v)));
+        }
+
+        assert_eq!(
+            text("Zm9v\nYmFy").decode(&text("base64")),
+            Ok(bytes(b"foobar"))
+        );
+        assert!(Evaluated::Literal(AstValue::Null)
+            .encode_base64()
+            .unwrap()
+            .is_null());
+
+        for v in ["Zm9", "Zm9v!A==", "Z===", "=Zm9", "Zg==Zm9v"] {
+            assert_eq!(
+                text(v).decode_base64(),
+                Err(EvaluateError::InvalidBase64(v.to_owned()).into())
+            );
+        }
+
+        assert_eq!(
+            text("foo").encode(&text("uuencode")),
+            Err(EvaluateError::UnsupportedEncoding("uuencode".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::Literal(number("1")).encode_base64(),
+            Err(EvaluateError::StringFunctionOnNonString("ENCODE".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn hex() {
+        let int = |v| Evaluated::Value(Value::I64(v));
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let bytes = |v: &[u8]| Evaluated::Value(Value::Bytes(v.to_vec()));
+        let hex = |v: &str| Ok(Evaluated::Value(Value::Str(v.to_owned())));
+
+        assert_eq!(int(255).to_hex(), hex("ff"));
+        assert_eq!(int(0).to_hex(), hex("0"));
+        assert_eq!(Evaluated::Literal(number("4096")).to_hex(), hex("1000"));
+        assert_eq!(int(-1).to_hex(), hex("ffffffffffffffff"));
+        assert_eq!(int(i64::MAX).to_hex(), hex("7fffffffffffffff"));
+
+        assert_eq!(bytes(&[0xde, 0xad, 0xbe, 0xef]).to_hex(), hex("deadbeef"));
+        assert_eq!(bytes(&[0, 0x0f]).to_hex(), hex("000f"));
+        assert_eq!(text("AB").to_hex(), hex("4142"));
+        assert_eq!(
+            Evaluated::Literal(AstValue::HexStringLiteral("C0FFEE".to_owned())).to_hex(),
+            hex("c0ffee")
+        );
+        assert!(Evaluated::Literal(AstValue::Null)
+            .to_hex()
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            Evaluated::Literal(number("2.5")).to_hex(),
+            Err(EvaluateError::StringFunctionOnNonString("TO_HEX".to_owned()).into())
+        );
+
+        assert_eq!(
+            text("deadBEEF").from_hex(),
+            Ok(bytes(&[0xde, 0xad, 0xbe, 0xef]))
+        );
+        assert_eq!(text("").from_hex(), Ok(bytes(&[])));
+        assert_eq!(text("000f").decode(&text("hex")), Ok(bytes(&[0, 0x0f])));
+        assert_eq!(bytes(&[0xab]).encode(&text("HEX")), hex("ab"));
+
+        for v in ["abc", "xyz0", "0x12", "é1"] {
+            assert_eq!(
+                text(v).from_hex(),
+                Err(EvaluateError::InvalidHexLiteral(v.to_owned()).into())
+            );
+        }
+    }
+
+    #[test]
+    fn json_extract() {
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let str = |v: &str| Ok(Evaluated::Value(Value::Str(v.to_owned())));
+
+        let document = Value::Str(
+            r#"{"name": "Glue", "stars": 42, "ratio": 0.5, "rust": true, "owner": null,
+                "max": 18446744073709551615,
+                "tags": ["sql", "db"], "meta": {"langs": [{"name": "Rust"}]}}"#
+                .to_owned(),
+        );
+        let document = Evaluated::ValueRef(&document);
+        let extract = |path| document.json_extract(path);
+
+        assert_eq!(extract("$.name"), str("Glue"));
+        assert_eq!(extract("$.stars"), Ok(Evaluated::Value(Value::I64(42))));
+        assert_eq!(extract("$.ratio"), Ok(Evaluated::Value(Value::F64(0.5))));
+        assert_eq!(
+            extract("$.max"),
+            Ok(Evaluated::Literal(number("18446744073709551615")))
+        );
+        assert_eq!(extract("$.rust"), Ok(Evaluated::Value(Value::Bool(true))));
+        assert_eq!(extract("$.tags[1]"), str("db"));
+        assert_eq!(extract("$.meta.langs[0].name"), str("Rust"));
+        assert_eq!(extract("$.tags"), str(r#"["sql","db"]"#));
+        assert_eq!(extract("$.meta.langs[ 0 ]"), str(r#"{"name":"Rust"}"#));
+        assert_eq!(
+            text("[1, [2, 3]]").json_extract("$[1][0]"),
+            Ok(Evaluated::Value(Value::I64(2)))
+        );
+        assert_eq!(text(r#""root""#).json_extract("$"), str("root"));
+
+        for path in [
+            "$.missing",
+            "$.tags[2]",
+            "$.name.first",
+            "$.stars[0]",
+            "$.owner",
+            "$[0]",
+        ] {
+            assert!(extract(path).unwrap().is_null(), "{}", path);
+        }
+        assert!(Evaluated::Literal(AstValue::Null)
+            .json_extract("$.name")
+            .unwrap()
+            .is_null());
+
+        for path in ["name", "$.", "$..name", "$[", "$[-1]", "$[x]", "$name"] {
+            assert_eq!(
+                extract(path),
+                Err(EvaluateError::InvalidJsonPath(path.to_owned()).into())
+            );
+        }
+
+        assert!(matches!(
+            text(r#"{"name": "Glue""#).json_extract("$.name"),
+            Err(crate::result::Error::Evaluate(EvaluateError::InvalidJson(
+                _
+            )))
+        ));
+        assert_eq!(
+            Evaluated::Literal(number("1")).json_extract("$"),
+            Err(EvaluateError::StringFunctionOnNonString("JSON_EXTRACT".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn typeof_name() {
+        use chrono::NaiveDate;
+
+        let literal = |v| Evaluated::Literal(v);
+        let value = |v| Evaluated::Value(v);
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+
+        let cases = vec![
+            (literal(number("1")), "INTEGER"),
+            (literal(number("1.5")), "FLOAT"),
+            (literal(number("18446744073709551615")), "FLOAT"),
+            (
+                literal(AstValue::SingleQuotedString("a".to_owned())),
+                "TEXT",
+            ),
+            (
+                literal(AstValue::NationalStringLiteral("a".to_owned())),
+                "TEXT",
+            ),
+            (
+                literal(AstValue::HexStringLiteral("ab".to_owned())),
+                "BYTEA",
+            ),
+            (literal(AstValue::Boolean(true)), "BOOLEAN"),
+            (literal(AstValue::Null), "NULL"),
+            (Evaluated::StringRef("a"), "TEXT"),
+            (value(Value::I64(1)), "INTEGER"),
+            (value(Value::OptF64(Some(1.0))), "FLOAT"),
+            (value(Value::Str("a".to_owned())), "TEXT"),
+            (value(Value::OptBool(Some(false))), "BOOLEAN"),
+            (value(Value::Decimal(1.into())), "DECIMAL"),
+            (value(Value::Date(date)), "DATE"),
+            (
+                value(Value::Timestamp(date.and_hms_opt(0, 0, 0).unwrap())),
+                "TIMESTAMP",
+            ),
+            (value(Value::Bytes(vec![1])), "BYTEA"),
+            (value(Value::OptI64(None)), "NULL"),
+            (value(Value::Empty), "NULL"),
+        ];
+
+        for (v, name) in cases {
+            assert_eq!(
+                v.typeof_name(),
+                Evaluated::Value(Value::Str(name.to_owned())),
+                "{}",
+                v
+            );
+        }
+
+        let stored = Value::OptStr(Some("a".to_owned()));
+        assert_eq!(
+            Evaluated::ValueRef(&stored).typeof_name(),
+            Evaluated::Value(Value::Str("TEXT".to_owned()))
+        );
+    }
+
+    #[test]
+    fn iif() {
+        let yes = || Evaluated::StringRef("yes");
+        let no = || Evaluated::StringRef("no");
+        let iif = |cond: Evaluated<'_>| Evaluated::iif(&cond, yes(), no());
+
+        assert_eq!(iif(Evaluated::Literal(AstValue::Boolean(true))), Ok(yes()));
+        assert_eq!(iif(Evaluated::Value(Value::OptBool(Some(false)))), Ok(no()));
+        assert_eq!(iif(Evaluated::Literal(AstValue::Null)), Ok(no()));
+        assert_eq!(iif(Evaluated::Value(Value::OptBool(None))), Ok(no()));
+        assert_eq!(
+            iif(Evaluated::Value(Value::I64(2))),
+            Err(EvaluateError::BooleanTypeRequired("2".to_owned()).into())
+        );
+        assert_eq!(
+            iif(Evaluated::Literal(number("0"))),
+            Err(EvaluateError::BooleanTypeRequired("0".to_owned()).into())
+        );
+        assert_eq!(
+            iif(Evaluated::StringRef("true")),
+            Err(EvaluateError::BooleanTypeRequired("'true'".to_owned()).into())
+        );
+
+        let null = Evaluated::iif(
+            &Evaluated::Literal(AstValue::Boolean(true)),
+            Evaluated::Literal(AstValue::Null),
+            no(),
+        );
+        assert!(null.unwrap().is_null());
+    }
+
+    #[test]
+    fn normalize() {
+        use NormalizationForm::*;
+
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+        let str = |v: &str| Ok(Evaluated::Value(Value::Str(v.to_owned())));
+
+        let composed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+
+        assert!(text(composed) != text(decomposed));
+        assert_eq!(text(decomposed).normalize(Nfc), str(composed));
+        assert_eq!(text(composed).normalize(Nfd), str(decomposed));
+        assert_eq!(
+            text(decomposed)
+                .normalize(Nfc)
+                .and_then(|v| v.normalize(Nfd)),
+            str(decomposed)
+        );
+        assert_eq!(text(composed).normalize(Nfc), str(composed));
+
+        assert_eq!(text("\u{fb01}le").normalize(Nfc), str("\u{fb01}le"));
+        assert_eq!(text("\u{fb01}le").normalize(Nfkc), str("file"));
+        assert_eq!(text("\u{fb01}l\u{e9}").normalize(Nfkd), str("file\u{301}"));
+
+        assert!(Evaluated::Literal(AstValue::Null)
+            .normalize(Nfc)
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            Evaluated::Literal(number("1")).normalize(Nfc),
+            Err(EvaluateError::StringFunctionOnNonString("NORMALIZE".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn fit_to() {
+        use sqlparser::ast::DataType;
+
+        let int = DataType::Int;
+        let float = DataType::Float(None);
+        let value = |v| Evaluated::Value(v);
+        let literal = |v| Evaluated::Literal(number(v));
+
+        let num = Value::I64(6);
+        let found = Evaluated::ValueRef(&num).multiply(&literal("1.0")).unwrap();
+        assert_eq!(found, value(Value::F64(6.0)));
+        assert_eq!(found.fit_to(&int), Ok(value(Value::I64(6))));
+
+        let found = Evaluated::ValueRef(&num).add(&literal("0.5")).unwrap();
+        assert_eq!(
+            found.fit_to(&int),
+            Err(EvaluateError::LossyConversion {
+                value: "6.5".to_owned(),
+                target: "INT".to_owned(),
+            }
+            .into())
+        );
+
+        assert_eq!(
+            value(Value::OptF64(Some(-3.0))).fit_to(&int),
+            Ok(value(Value::OptI64(Some(-3))))
+        );
+        assert!(matches!(
+            value(Value::OptF64(None)).fit_to(&int),
+            Ok(Evaluated::Value(Value::OptI64(None)))
+        ));
+        assert_eq!(literal("2.0").fit_to(&int), Ok(value(Value::I64(2))));
+        assert_eq!(
+            value(Value::Decimal("4.00".parse().unwrap())).fit_to(&int),
+            Ok(value(Value::I64(4)))
+        );
+        assert_eq!(
+            value(Value::Decimal("4.25".parse().unwrap())).fit_to(&int),
+            Err(EvaluateError::LossyConversion {
+                value: "4.25".to_owned(),
+                target: "INT".to_owned(),
+            }
+            .into())
+        );
+        assert_eq!(
+            value(Value::F64(1e19)).fit_to(&int),
+            Err(ValueError::NumericOverflow.into())
+        );
+        assert_eq!(
+            value(Value::F64(f64::NAN)).fit_to(&int),
+            Err(EvaluateError::LossyConversion {
+                value: "NaN".to_owned(),
+                target: "INT".to_owned(),
+            }
+            .into())
+        );
+
+        assert_eq!(
+            value(Value::I64(3)).fit_to(&float),
+            Ok(value(Value::F64(3.0)))
+        );
+        assert_eq!(
+            value(Value::OptI64(Some(3))).fit_to(&float),
+            Ok(value(Value::OptF64(Some(3.0))))
+        );
+        assert_eq!(
+            value(Value::I64(i64::MAX)).fit_to(&float),
+            Err(ValueError::NumericOverflow.into())
+        );
+
+        assert_eq!(value(Value::I64(3)).fit_to(&int), Ok(value(Value::I64(3))));
+        assert_eq!(
+            value(Value::Str("a".to_owned())).fit_to(&int),
+            Ok(value(Value::Str("a".to_owned())))
+        );
+        assert_eq!(
+            Evaluated::StringRef("a").fit_to(&int),
+            Ok(Evaluated::StringRef("a"))
+        );
+    }
+
+    #[test]
+    fn comparison_values() {
+        let int = |v| Evaluated::Value(Value::I64(v));
+        let literal = |v| Evaluated::Literal(number(v));
+        let null = || Evaluated::Literal(AstValue::Null);
+        let boolean = |v| Ok(Evaluated::Value(Value::Bool(v)));
+
+        assert_eq!(literal("3").gt_eval(&literal("2")), boolean(true));
+        assert_eq!(literal("2").gt_eval(&literal("3")), boolean(false));
+        assert_eq!(int(2).lt_eval(&literal("2.5")), boolean(true));
+        assert_eq!(int(2).ge_eval(&int(2)), boolean(true));
+        assert_eq!(int(3).le_eval(&int(2)), boolean(false));
+        assert_eq!(int(2).eq_eval(&literal("2.0")), boolean(true));
+        assert_eq!(int(2).ne_eval(&literal("2.0")), boolean(false));
+        assert_eq!(
+            Evaluated::StringRef("b").gt_eval(&Evaluated::StringRef("a")),
+            boolean(true)
+        );
+
+        let comparisons: [fn(&Evaluated<'static>, &Evaluated<'static>) -> _; 6] = [
+            Evaluated::gt_eval,
+            Evaluated::lt_eval,
+            Evaluated::ge_eval,
+            Evaluated::le_eval,
+            Evaluated::eq_eval,
+            Evaluated::ne_eval,
+        ];
+
+        for compare in comparisons.iter() {
+            assert!(compare(&int(1), &null()).unwrap().is_null());
+            assert!(compare(&null(), &int(1)).unwrap().is_null());
+            assert!(compare(&null(), &null()).unwrap().is_null());
+            assert!(compare(&Evaluated::Value(Value::OptI64(None)), &int(1))
+                .unwrap()
+                .is_null());
+            assert_eq!(
+                compare(&int(1), &Evaluated::StringRef("1")),
+                Err(EvaluateError::TypeMismatch.into())
+            );
+        }
+
+        let nan = Evaluated::Value(Value::F64(f64::NAN));
+        assert!(nan.gt_eval(&int(1)).unwrap().is_null());
+        assert_eq!(nan.eq_eval(&nan), boolean(false));
+    }
+
+    #[test]
+    fn to_json() {
+        use chrono::NaiveDate;
+        use serde_json::json;
+
+        let literal = |v| Evaluated::Literal(v);
+        let value = |v| Evaluated::Value(v);
+        let date = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap();
+
+        let cases = vec![
+            (literal(number("42")), json!(42)),
+            (literal(number("-1.5")), json!(-1.5)),
+            (literal(number("18446744073709551615")), json!(u64::MAX)),
+            (
+                literal(AstValue::SingleQuotedString("Glue".to_owned())),
+                json!("Glue"),
+            ),
+            (
+                literal(AstValue::NationalStringLiteral("Glue".to_owned())),
+                json!("Glue"),
+            ),
+            (
+                literal(AstValue::HexStringLiteral("DEADBEEF".to_owned())),
+                json!("3q2+7w=="),
+            ),
+            (literal(AstValue::Boolean(true)), json!(true)),
+            (literal(AstValue::Null), json!(null)),
+            (Evaluated::StringRef("say \"hi\""), json!("say \"hi\"")),
+            (value(Value::I64(-7)), json!(-7)),
+            (value(Value::OptF64(Some(0.5))), json!(0.5)),
+            (value(Value::OptStr(Some("a".to_owned()))), json!("a")),
+            (value(Value::Bool(false)), json!(false)),
+            (
+                value(Value::Decimal("0.10".parse().unwrap())),
+                json!("0.10"),
+            ),
+            (value(Value::Date(date)), json!("2021-01-02")),
+            (
+                value(Value::Timestamp(
+                    date.and_hms_milli_opt(3, 4, 5, 600).unwrap(),
+                )),
+                json!("2021-01-02T03:04:05.600"),
+            ),
+            (value(Value::Bytes(vec![0xff, 0xfe])), json!("//4=")),
+            (value(Value::OptI64(None)), json!(null)),
+            (value(Value::Empty), json!(null)),
+        ];
+
+        for (v, expected) in cases {
+            assert_eq!(v.to_json(), Ok(expected), "{}", v);
+        }
+
+        assert_eq!(
+            value(Value::F64(f64::INFINITY)).to_json(),
+            Err(EvaluateError::UnrepresentableJson("inf".to_owned()).into())
+        );
+        assert_eq!(
+            literal(AstValue::HexStringLiteral("ABC".to_owned())).to_json(),
+            Err(EvaluateError::InvalidHexLiteral("ABC".to_owned()).into())
+        );
+
+        let round_trip = |v: Evaluated<'_>| {
+            let text = v.to_json().unwrap().to_string();
+
+            Evaluated::Value(Value::Str(text)).json_extract("$")
+        };
+
+        for v in [
+            value(Value::I64(i64::MIN)),
+            value(Value::F64(0.1)),
+            value(Value::Str("multi\nline \u{e9}".to_owned())),
+            value(Value::Bool(true)),
+        ] {
+            assert_eq!(round_trip(v.clone()), Ok(v));
+        }
+        assert!(round_trip(value(Value::OptStr(None))).unwrap().is_null());
+        assert_eq!(
+            round_trip(value(Value::Bytes(vec![1, 2, 3]))).and_then(|v| v.decode_base64()),
+            Ok(value(Value::Bytes(vec![1, 2, 3])))
+        );
+    }
+
+    #[test]
+    fn from_json() {
+        use serde_json::json;
+
+        let value = |v| Ok(Evaluated::Value(v));
+
+        assert_eq!(Evaluated::from_json(&json!(42)), value(Value::I64(42)));
+        assert_eq!(
+            Evaluated::from_json(&json!(i64::MIN)),
+            value(Value::I64(i64::MIN))
+        );
+        assert_eq!(
+            Evaluated::from_json(&json!(u64::MAX)),
+            Ok(Evaluated::Literal(number("18446744073709551615")))
+        );
+        assert_eq!(Evaluated::from_json(&json!(-0.5)), value(Value::F64(-0.5)));
+        assert_eq!(
+            Evaluated::from_json(&json!(1e300)),
+            value(Value::F64(1e300))
+        );
+        assert_eq!(
+            Evaluated::from_json(&json!("Glue")),
+            value(Value::Str("Glue".to_owned()))
+        );
+        assert_eq!(Evaluated::from_json(&json!(true)), value(Value::Bool(true)));
+        assert!(Evaluated::from_json(&json!(null)).unwrap().is_null());
+
+        assert_eq!(
+            Evaluated::from_json(&json!([1, 2])),
+            Err(EvaluateError::JsonNotScalar("[1,2]".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::from_json(&json!({"a": 1})),
+            Err(EvaluateError::JsonNotScalar(r#"{"a":1}"#.to_owned()).into())
+        );
+
+        for v in [
+            json!(7),
+            json!(2.5),
+            json!("say \"hi\""),
+            json!(false),
+            json!(null),
+        ] {
+            let found = Evaluated::from_json(&v).and_then(|v| v.to_json());
+
+            assert_eq!(found, Ok(v));
+        }
+    }
+
+    #[test]
+    fn total_cmp() {
+        use chrono::NaiveDate;
+        use Ordering::*;
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        let text = |v: &str| Evaluated::Literal(AstValue::SingleQuotedString(v.to_owned()));
+
+        let mut items = vec![
+            Evaluated::Value(Value::Date(date)),
+            Evaluated::Value(Value::Bytes(vec![0x01])),
+            text("b"),
+            Evaluated::Value(Value::F64(f64::NAN)),
+            Evaluated::Value(Value::I64(10)),
+            Evaluated::Literal(AstValue::Boolean(true)),
+            Evaluated::Literal(AstValue::Null),
+            Evaluated::StringRef("a"),
+            Evaluated::Literal(number("-1.5")),
+            Evaluated::Literal(AstValue::HexStringLiteral("00FF".to_owned())),
+            Evaluated::Value(Value::Bool(false)),
+            Evaluated::Value(Value::Timestamp(date.and_hms_opt(12, 0, 0).unwrap())),
+            Evaluated::Value(Value::Decimal(2.into())),
+            Evaluated::Value(Value::OptStr(None)),
+        ];
+        items.sort_by(|l, r| l.total_cmp(r));
+
+        let found = items.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        let expected = [
+            "NULL",
+            "NULL",
+            "FALSE",
+            "TRUE",
+            "-1.5",
+            "2",
+            "10",
+            "NaN",
+            "'a'",
+            "'b'",
+            "X'00FF'",
+            "X'01'",
+            "DATE '2021-01-01'",
+            "TIMESTAMP '2021-01-01 12:00:00'",
+        ];
+        assert_eq!(found, expected);
+
+        let one = Evaluated::Value(Value::I64(1));
+        let nan = Evaluated::Value(Value::F64(f64::NAN));
+        let null = Evaluated::Literal(AstValue::Null);
+
+        assert_eq!(one.total_cmp(&Evaluated::Literal(number("1.0"))), Equal);
+        assert_eq!(one.total_cmp(&Evaluated::StringRef("1")), Less);
+        assert_eq!(Evaluated::StringRef("1").total_cmp(&one), Greater);
+        assert_eq!(nan.total_cmp(&nan), Equal);
+        assert_eq!(
+            nan.total_cmp(&Evaluated::Value(Value::F64(f64::INFINITY))),
+            Greater
+        );
+        assert_eq!(
+            null.total_cmp(&Evaluated::Value(Value::OptI64(None))),
+            Equal
+        );
+        assert_eq!(
+            null.total_cmp(&Evaluated::Literal(AstValue::Boolean(false))),
+            Less
+        );
+        assert_eq!(
+            Evaluated::Value(Value::Bytes(vec![0xff])).total_cmp(&Evaluated::Literal(
+                AstValue::HexStringLiteral("ff".to_owned())
+            )),
+            Equal
+        );
+        assert_eq!(
+            text("z").total_cmp(&Evaluated::Value(Value::Bytes(vec![]))),
+            Less
+        );
+    }
+
+    #[test]
+    fn scientific_notation() {
+        let literal = |v: &str| Evaluated::Literal(number(v));
+        let thousand = Evaluated::Value(Value::I64(1000));
+
+        assert!(literal("1e3") == thousand);
+        assert!(literal("1e3") == literal("1000"));
+        assert!(literal("1E3") == literal("1000.0"));
+        assert!(literal("1e3") == Evaluated::Value(Value::Decimal(1000.into())));
+        assert!(literal("1.5e-2") == Evaluated::Value(Value::F64(0.015)));
+        assert!(literal("1.5e-2") == literal("0.015"));
+        assert_eq!(
+            literal("1e3").try_partial_cmp(&Evaluated::Value(Value::I64(999))),
+            Ok(Some(Ordering::Greater))
+        );
+        assert_eq!(
+            literal("1.5e-2").try_partial_cmp(&literal("1")),
+            Ok(Some(Ordering::Less))
+        );
+        assert_eq!(
+            thousand.eq_eval(&literal("1e3")),
+            Ok(Evaluated::Value(Value::Bool(true)))
+        );
+
+        assert_eq!(literal("1e3").add(&literal("1")), Ok(literal("1001.0")));
+        assert_eq!(
+            Evaluated::Value(Value::I64(1)).add(&literal("1.5e-2")),
+            Ok(Evaluated::Value(Value::F64(1.015)))
+        );
+
+        let invalid = || EvaluateError::InvalidNumberLiteral("1e".to_owned()).into();
+        assert_eq!(thousand.try_eq(&literal("1e")), Err(invalid()));
+        assert_eq!(literal("1e").try_partial_cmp(&thousand), Err(invalid()));
+        assert_eq!(literal("1e").eq_eval(&literal("1")), Err(invalid()));
+        assert_eq!(literal("1e").add(&literal("1")), Err(invalid()));
+        assert_eq!(literal("2").multiply(&literal("1e")), Err(invalid()));
+    }
+
+    #[test]
+    fn clamp() {
+        let int = |v| Evaluated::Value(Value::I64(v));
+        let low = Evaluated::Literal(number("1"));
+        let high = Evaluated::Literal(number("10"));
+
+        assert_eq!(
+            int(-5).clamp(&low, &high),
+            Ok(Evaluated::Literal(number("1")))
+        );
+        assert_eq!(int(5).clamp(&low, &high), Ok(int(5)));
+        assert_eq!(int(1).clamp(&low, &high), Ok(int(1)));
+        assert_eq!(int(10).clamp(&low, &high), Ok(int(10)));
+        assert_eq!(
+            int(50).clamp(&low, &high),
+            Ok(Evaluated::Literal(number("10")))
+        );
+        assert_eq!(
+            Evaluated::Value(Value::F64(0.5)).clamp(&low, &high),
+            Ok(Evaluated::Literal(number("1")))
+        );
+        assert_eq!(int(7).clamp(&int(7), &int(7)), Ok(int(7)));
+        assert_eq!(
+            Evaluated::StringRef("m").clamp(&Evaluated::StringRef("a"), &Evaluated::StringRef("f")),
+            Ok(Evaluated::StringRef("f"))
+        );
+
+        assert_eq!(
+            int(5).clamp(&high, &low),
+            Err(EvaluateError::InvalidClampBounds {
+                low: "10".to_owned(),
+                high: "1".to_owned(),
+            }
+            .into())
+        );
+        assert_eq!(
+            int(5).clamp(&Evaluated::StringRef("a"), &high),
+            Err(EvaluateError::TypeMismatch.into())
+        );
+        assert_eq!(
+            Evaluated::Value(Value::F64(f64::NAN)).clamp(&low, &high),
+            Err(EvaluateError::IncomparableValues {
+                left: "NaN".to_owned(),
+                right: "1".to_owned(),
+            }
+            .into())
+        );
+        assert_eq!(
+            int(5).clamp(&Evaluated::Literal(AstValue::Boolean(true)), &high),
+            Err(EvaluateError::IncomparableValues {
+                left: "TRUE".to_owned(),
+                right: "10".to_owned(),
+            }
+            .into())
+        );
+
+        let null = Evaluated::Literal(AstValue::Null);
+        assert!(matches!(int(5).clamp(&null, &high), Ok(v) if v.is_null()));
+        assert!(matches!(null.clamp(&low, &high), Ok(v) if v.is_null()));
+    }
+
+    #[test]
+    fn round_with() {
+        use RoundingMode::*;
+
+        let float = |v: f64| Evaluated::Value(Value::F64(v));
+        let round = |v: f64, places, mode| float(v).round_with(places, mode)?.to_value();
+
+        for (mode, expected) in [
+            (HalfUp, [3.0, 4.0, -3.0]),
+            (HalfEven, [2.0, 4.0, -2.0]),
+            (TowardZero, [2.0, 3.0, -2.0]),
+            (AwayFromZero, [3.0, 4.0, -3.0]),
+        ] {
+            for (v, expected) in [2.5, 3.5, -2.5].iter().zip(expected.iter()) {
+                assert_eq!(
+                    round(*v, None, mode),
+                    Ok(Value::F64(*expected)),
+                    "{:?}",
+                    mode
+                );
+            }
+        }
+
+        assert_eq!(round(1.25, Some(1), HalfUp), Ok(Value::F64(1.3)));
+        assert_eq!(round(1.25, Some(1), HalfEven), Ok(Value::F64(1.2)));
+        assert_eq!(round(-1.25, Some(1), HalfEven), Ok(Value::F64(-1.2)));
+        assert_eq!(round(1.29, Some(1), TowardZero), Ok(Value::F64(1.2)));
+        assert_eq!(round(-1.21, Some(1), AwayFromZero), Ok(Value::F64(-1.3)));
+        assert_eq!(round(2.1, None, AwayFromZero), Ok(Value::F64(3.0)));
+        assert_eq!(round(-2.7, None, TowardZero), Ok(Value::F64(-2.0)));
+        assert_eq!(round(1250.0, Some(-2), HalfEven), Ok(Value::F64(1200.0)));
+        assert_eq!(round(1350.0, Some(-2), HalfEven), Ok(Value::F64(1400.0)));
+
+        assert_eq!(
+            Evaluated::Literal(number("2.5")).round_with(None, HalfEven),
+            Ok(Evaluated::Literal(number("2.0")))
+        );
+        assert_eq!(
+            Evaluated::Value(Value::I64(7)).round_with(Some(-1), AwayFromZero),
+            Ok(Evaluated::Value(Value::I64(7)))
+        );
+        assert_eq!(
+            Evaluated::StringRef("2.5").round_with(None, HalfEven),
+            Err(EvaluateError::MathOnNonNumeric("round".to_owned()).into())
+        );
+        assert_eq!(RoundingMode::default(), HalfUp);
+    }
+
+    #[test]
+    fn decimal_math() {
+        use sqlparser::ast::DataType;
+
+        let decimal = |v: &str| Value::Decimal(v.parse().unwrap());
+        let value = |v: &str| Evaluated::Value(decimal(v));
+        let to_value = |v: crate::result::Result<Evaluated<'_>>| v.and_then(|v| v.to_value());
+
+        assert_eq!(to_value(value("-2.5").unary_plus()), Ok(decimal("-2.5")));
+        assert_eq!(to_value(value("2.5").round(None)), Ok(decimal("3")));
+        assert_eq!(to_value(value("1.005").round(Some(2))), Ok(decimal("1.01")));
+        assert_eq!(
+            to_value(value("2.5").round_with(None, RoundingMode::HalfEven)),
+            Ok(decimal("2"))
+        );
+        assert_eq!(to_value(value("1250").round(Some(-2))), Ok(decimal("1300")));
+        assert_eq!(to_value(value("-2.7").floor()), Ok(decimal("-3")));
+        assert_eq!(to_value(value("-2.7").ceil()), Ok(decimal("-2")));
+        assert_eq!(to_value(value("-2.79").trunc(Some(1))), Ok(decimal("-2.7")));
+        assert_eq!(
+            to_value(Evaluated::Value(Value::OptDecimal(None)).floor()),
+            Ok(Value::OptDecimal(None))
+        );
+        assert_eq!(
+            to_value(Evaluated::Value(Value::OptDecimal(Some("0.5".parse().unwrap()))).ceil()),
+            Ok(Value::OptDecimal(Some("1".parse().unwrap())))
+        );
+
+        assert_eq!(value("0.0").try_into_bool(), Ok(false));
+        assert_eq!(value("0.1").try_into_bool(), Ok(true));
+
+        assert_eq!(
+            to_value(value("-1.7").cast(DataType::Int)),
+            Ok(Value::I64(-1))
+        );
+        assert_eq!(
+            to_value(value("1.5").cast(DataType::Float(None))),
+            Ok(Value::F64(1.5))
+        );
+        assert_eq!(
+            to_value(value("1.50").cast(DataType::Text)),
+            Ok(Value::Str("1.50".to_owned()))
+        );
+        assert_eq!(
+            to_value(value("1").cast(DataType::Boolean)),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(
+            to_value(value("1.5").cast(DataType::Decimal(None, None))),
+            Ok(decimal("1.5"))
+        );
+        assert_eq!(
+            to_value(
+                Evaluated::Value(Value::OptDecimal(Some("2".parse().unwrap()))).cast(DataType::Int)
+            ),
+            Ok(Value::OptI64(Some(2)))
+        );
+        assert!(value("2").cast(DataType::Boolean).is_err());
+    }
+
+    #[test]
+    fn try_from_primitive() {
+        use std::convert::{TryFrom, TryInto};
+
+        let failed = |value: &str, target: &str| -> crate::result::Error {
+            EvaluateError::ConversionFailed {
+                value: value.to_owned(),
+                target: target.to_owned(),
+            }
+            .into()
+        };
+        let null = Evaluated::Literal(AstValue::Null);
+        let text = Evaluated::StringRef("Glue");
+        let yes = Evaluated::Literal(AstValue::Boolean(true));
+
+        let five = number("5");
+        assert_eq!(i64::try_from(&Evaluated::LiteralRef(&five)), Ok(5));
+        assert_eq!(
+            i64::try_from(&Evaluated::Value(Value::OptI64(Some(-3)))),
+            Ok(-3)
+        );
+        assert_eq!(
+            i64::try_from(&Evaluated::Literal(number("1.5"))),
+            Err(failed("1.5", "i64"))
+        );
+        assert_eq!(
+            i64::try_from(&Evaluated::Literal(number("9223372036854775808"))),
+            Err(failed("9223372036854775808", "i64"))
+        );
+        assert_eq!(i64::try_from(&text), Err(failed("'Glue'", "i64")));
+        assert_eq!(i64::try_from(&null), Err(failed("NULL", "i64")));
+
+        assert_eq!(f64::try_from(&Evaluated::Literal(number("1.5"))), Ok(1.5));
+        assert_eq!(f64::try_from(&Evaluated::ValueRef(&Value::I64(2))), Ok(2.0));
+        assert_eq!(f64::try_from(&Evaluated::Value(Value::F64(0.25))), Ok(0.25));
+        assert_eq!(
+            f64::try_from(&Evaluated::Value(Value::Decimal("0.5".parse().unwrap()))),
+            Ok(0.5)
+        );
+        assert_eq!(f64::try_from(&yes), Err(failed("TRUE", "f64")));
+        assert_eq!(
+            f64::try_from(&Evaluated::Value(Value::OptF64(None))),
+            Err(failed("NULL", "f64"))
+        );
+
+        let found: crate::result::Result<String> = (&text).try_into();
+        assert_eq!(found, Ok("Glue".to_owned()));
+        assert_eq!(
+            String::try_from(&Evaluated::Value(Value::Str("SQL".to_owned()))),
+            Ok("SQL".to_owned())
+        );
+        assert_eq!(
+            String::try_from(&Evaluated::Literal(number("7"))),
+            Err(failed("7", "String"))
+        );
+        assert_eq!(
+            String::try_from(&Evaluated::Value(Value::Bytes(vec![0xab]))),
+            Err(failed("X'AB'", "String"))
+        );
+
+        assert_eq!(bool::try_from(&yes), Ok(true));
+        assert_eq!(
+            bool::try_from(&Evaluated::ValueRef(&Value::OptBool(Some(false)))),
+            Ok(false)
+        );
+        assert_eq!(
+            bool::try_from(&Evaluated::Literal(number("1"))),
+            Err(failed("1", "bool"))
+        );
+        assert_eq!(bool::try_from(&text), Err(failed("'Glue'", "bool")));
+        assert_eq!(bool::try_from(&null), Err(failed("NULL", "bool")));
+    }
+
+    #[test]
+    fn logical_not() {
+        let yes = Evaluated::Literal(AstValue::Boolean(true));
+        let no = Evaluated::ValueRef(&Value::OptBool(Some(false)));
+
+        assert_eq!(yes.logical_not(), Ok(Evaluated::Value(Value::Bool(false))));
+        assert_eq!(no.logical_not(), Ok(Evaluated::Value(Value::Bool(true))));
+        assert_eq!(
+            Evaluated::Value(Value::I64(0)).logical_not(),
+            Err(EvaluateError::BooleanTypeRequired("0".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::Literal(number("2.5")).logical_not(),
+            Err(EvaluateError::BooleanTypeRequired("2.5".to_owned()).into())
+        );
+
+        for null in [
+            Evaluated::Literal(AstValue::Null),
+            Evaluated::Value(Value::OptBool(None)),
+        ] {
+            assert!(matches!(null.logical_not(), Ok(v) if v.is_null()));
+        }
+
+        assert_eq!(
+            Evaluated::StringRef("true").logical_not(),
+            Err(EvaluateError::BooleanTypeRequired("'true'".to_owned()).into())
+        );
+        assert_eq!(
+            Evaluated::Value(Value::Str("Glue".to_owned())).logical_not(),
+            Err(EvaluateError::BooleanTypeRequired("'Glue'".to_owned()).into())
+        );
     }
 }
@@ -1,48 +1,68 @@
+mod checked;
+mod clock;
 mod error;
 mod evaluated;
 
 use im_rc::HashMap;
 use std::fmt::Debug;
 
-use sqlparser::ast::{BinaryOperator, Expr, Function, Value as AstValue};
+use sqlparser::ast::{BinaryOperator, Expr, Function, UnaryOperator, Value as AstValue};
 
 use super::context::FilterContext;
 use super::select::select;
-use crate::data::Value;
+use crate::data::{get_name, Value};
 use crate::result::Result;
 use crate::store::Store;
 
+pub use checked::Checked;
+pub use clock::{Clock, SystemClock};
 pub use error::EvaluateError;
-pub use evaluated::Evaluated;
+pub use evaluated::{
+    Collation, ComparisonMode, DivisionMode, Evaluated, NormalizationForm, OverflowMode,
+    RoundingMode,
+};
 
 pub fn evaluate<'a, T: 'static + Debug>(
     storage: &'a dyn Store<T>,
+    clock: &'a dyn Clock,
     filter_context: Option<&'a FilterContext<'a>>,
     aggregated: Option<&HashMap<&Function, Value>>,
     expr: &'a Expr,
 ) -> Result<Evaluated<'a>> {
-    let eval = |expr| evaluate(storage, filter_context, aggregated, expr);
+    let eval = |expr| evaluate(storage, clock, filter_context, aggregated, expr);
 
     match expr {
         Expr::Value(value) => match value {
             AstValue::Number(_)
             | AstValue::Boolean(_)
             | AstValue::SingleQuotedString(_)
+            | AstValue::NationalStringLiteral(_)
+            | AstValue::HexStringLiteral(_)
+            | AstValue::Interval { .. }
             | AstValue::Null => Ok(Evaluated::LiteralRef(value)),
-            _ => Err(EvaluateError::Unimplemented.into()),
         },
         Expr::Identifier(ident) => match ident.quote_style {
             Some(_) => Ok(Evaluated::StringRef(&ident.value)),
-            None => filter_context
-                .ok_or_else(|| {
-                    let name = ident.value.to_string();
+            None => {
+                let value = filter_context
+                    .ok_or_else(|| {
+                        let name = ident.value.to_string();
 
-                    EvaluateError::UnreachableEmptyFilterContext(name)
-                })?
-                .get_value(&ident.value)
-                .map(Evaluated::ValueRef),
+                        EvaluateError::UnreachableEmptyFilterContext(name).into()
+                    })
+                    .and_then(|context| context.get_value(&ident.value));
+
+                // A column named `current_timestamp` takes precedence over the keyword.
+                match value {
+                    Ok(value) => Ok(Evaluated::ValueRef(value)),
+                    Err(_) if ident.value.eq_ignore_ascii_case("CURRENT_TIMESTAMP") => {
+                        Ok(Evaluated::now(clock))
+                    }
+                    Err(error) => Err(error),
+                }
+            }
         },
-        Expr::Nested(expr) => eval(&expr),
+        Expr::Nested(expr) => eval(expr),
         Expr::CompoundIdentifier(idents) => {
             if idents.len() != 2 {
                 return Err(EvaluateError::UnsupportedCompoundIdentifier(expr.to_string()).into());
@@ -60,7 +80,7 @@ pub fn evaluate<'a, T: 'static + Debug>(
                 .get_alias_value(table_alias, column)
                 .map(Evaluated::ValueRef)
         }
-        Expr::Subquery(query) => select(storage, &query, filter_context)?
+        Expr::Subquery(query) => select(storage, clock, query, filter_context)?
             .map(|row| row?.take_first_value())
             .map(|value| value.map(Evaluated::Value))
             .next()
@@ -74,16 +94,71 @@ pub fn evaluate<'a, T: 'static + Debug>(
                 BinaryOperator::Minus => l.subtract(&r),
                 BinaryOperator::Multiply => l.multiply(&r),
                 BinaryOperator::Divide => l.divide(&r),
+                BinaryOperator::Modulus => l.modulo(&r),
+                // `^` is power as in PostgreSQL, which spells XOR `#`. The parser has no `#`,
+                // so `bit_xor` is not reachable from SQL.
+                BinaryOperator::BitwiseXor => l.power(&r),
+                BinaryOperator::BitwiseAnd => l.bit_and(&r),
+                BinaryOperator::BitwiseOr => l.bit_or(&r),
+                BinaryOperator::StringConcat => l.concat(&r),
+                BinaryOperator::Gt => l.gt_eval(&r),
+                BinaryOperator::Lt => l.lt_eval(&r),
+                BinaryOperator::GtEq => l.ge_eval(&r),
+                BinaryOperator::LtEq => l.le_eval(&r),
+                BinaryOperator::Eq => l.eq_eval(&r),
+                BinaryOperator::NotEq => l.ne_eval(&r),
                 _ => Err(EvaluateError::Unimplemented.into()),
             }
         }
-        Expr::Function(func) => aggregated
-            .as_ref()
-            .map(|aggregated| match aggregated.get(func) {
-                Some(value) => Ok(Evaluated::Value(value.clone())),
-                None => Err(EvaluateError::UnreachableAggregatedField(func.to_string()).into()),
-            })
-            .unwrap_or_else(|| Err(EvaluateError::UnreachableEmptyAggregated.into())),
+        Expr::Cast { expr, data_type } => eval(expr)?.cast(data_type.clone()),
+        Expr::Extract { field, expr } => eval(expr)?.extract(field.clone()),
+        Expr::UnaryOp { op, expr } => {
+            let v = eval(expr)?;
+
+            match op {
+                UnaryOperator::Plus => v.unary_plus(),
+                UnaryOperator::Minus => v.unary_minus(),
+                UnaryOperator::Not => v.logical_not(),
+            }
+        }
+        Expr::Function(func) => match evaluate_function(func, clock)? {
+            Some(evaluated) => Ok(evaluated),
+            None => aggregated
+                .as_ref()
+                .map(|aggregated| match aggregated.get(func) {
+                    Some(value) => Ok(Evaluated::Value(value.clone())),
+                    None => Err(EvaluateError::UnreachableAggregatedField(func.to_string()).into()),
+                })
+                .unwrap_or_else(|| Err(EvaluateError::UnreachableEmptyAggregated.into())),
+        },
         _ => Err(EvaluateError::Unimplemented.into()),
     }
 }
+
+/// Scalar functions which are evaluated on their own rather than aggregated over rows.
+/// Returns `None` for any other function, such as `COUNT`.
+pub(crate) fn evaluate_function<'a>(
+    func: &Function,
+    clock: &dyn Clock,
+) -> Result<Option<Evaluated<'a>>> {
+    let name = get_name(&func.name)?.to_uppercase();
+
+    match name.as_str() {
+        "NOW" | "CURRENT_TIMESTAMP" => match func.args.len() {
+            0 => Ok(Some(Evaluated::now(clock))),
+            found => Err(EvaluateError::FunctionArgsLengthNotMatching {
+                name,
+                expected: 0,
+                found,
+            }
+            .into()),
+        },
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn is_scalar_function(func: &Function) -> bool {
+    get_name(&func.name)
+        .map(|name| matches!(name.to_uppercase().as_str(), "NOW" | "CURRENT_TIMESTAMP"))
+        .unwrap_or(false)
+}
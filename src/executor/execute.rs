@@ -2,8 +2,9 @@ use serde::Serialize;
 use std::fmt::Debug;
 use thiserror::Error;
 
-use sqlparser::ast::{ObjectType, Statement};
+use sqlparser::ast::{ColumnDef, ObjectType, Statement};
 
+use super::evaluate::{Clock, SystemClock};
 use super::fetch::{fetch, fetch_columns};
 use super::filter::Filter;
 use super::select::select;
@@ -35,9 +36,18 @@ pub enum Payload {
 pub fn execute<T: 'static + Debug, U: Store<T> + StoreMut<T>>(
     storage: U,
     query: &Query,
+) -> MutResult<U, Payload> {
+    execute_with_clock(storage, query, &SystemClock)
+}
+
+/// Same as [`execute`], but `NOW()` and `CURRENT_TIMESTAMP` read the given clock.
+pub fn execute_with_clock<T: 'static + Debug, U: Store<T> + StoreMut<T>>(
+    storage: U,
+    query: &Query,
+    clock: &dyn Clock,
 ) -> MutResult<U, Payload> {
     let Query(query) = query;
-    let prepared = match prepare(&storage, query) {
+    let prepared = match prepare(&storage, clock, query) {
         Ok(prepared) => prepared,
         Err(error) => {
             return Err((storage, error));
@@ -49,7 +59,7 @@ pub fn execute<T: 'static + Debug, U: Store<T> + StoreMut<T>>(
             .insert_schema(&schema)
             .map(|(storage, _)| (storage, Payload::Create)),
         Prepared::Insert(table_name, row) => {
-            let (storage, key) = storage.generate_id(&table_name)?;
+            let (storage, key) = storage.generate_id(table_name)?;
             let (storage, row) = storage.insert_data(&key, row)?;
 
             Ok((storage, Payload::Insert(row)))
@@ -104,6 +114,7 @@ enum Prepared<'a, T> {
 
 fn prepare<'a, T: 'static + Debug>(
     storage: &impl Store<T>,
+    clock: &dyn Clock,
     sql_query: &'a Statement,
 ) -> Result<Prepared<'a, T>> {
     match sql_query {
@@ -116,7 +127,7 @@ fn prepare<'a, T: 'static + Debug>(
             Ok(Prepared::Create(schema))
         }
         Statement::Query(query) => {
-            let rows = select(storage, &query, None)?.collect::<Result<_>>()?;
+            let rows = select(storage, clock, query, None)?.collect::<Result<_>>()?;
 
             Ok(Prepared::Select(rows))
         }
@@ -137,9 +148,20 @@ fn prepare<'a, T: 'static + Debug>(
             assignments,
         } => {
             let table_name = get_name(table_name)?;
-            let columns = fetch_columns(storage, table_name)?;
-            let update = Update::new(storage, table_name, assignments, &columns)?;
-            let filter = Filter::new(storage, selection.as_ref(), None);
+            let Schema { column_defs, .. } = storage.fetch_schema(table_name)?;
+            let columns = column_defs
+                .iter()
+                .map(|ColumnDef { name, .. }| name.clone())
+                .collect::<Vec<_>>();
+            let update = Update::new(
+                storage,
+                clock,
+                table_name,
+                assignments,
+                &columns,
+                &column_defs,
+            )?;
+            let filter = Filter::new(storage, clock, selection.as_ref(), None);
 
             let rows = fetch(storage, table_name, &columns, filter)?
                 .map(|item| {
@@ -157,7 +179,7 @@ fn prepare<'a, T: 'static + Debug>(
         } => {
             let table_name = get_name(table_name)?;
             let columns = fetch_columns(storage, table_name)?;
-            let filter = Filter::new(storage, selection.as_ref(), None);
+            let filter = Filter::new(storage, clock, selection.as_ref(), None);
 
             let rows = fetch(storage, table_name, &columns, filter)?
                 .map(|item| item.map(|(_, key, _)| key))
@@ -31,7 +31,7 @@ pub fn fetch<'a, T: 'static + Debug>(
             |error| Some(Err(error)),
             |(key, row)| {
                 filter
-                    .check(&table_name, columns, &row)
+                    .check(table_name, columns, &row)
                     .map(|pass| pass.as_some((columns, key, row)))
                     .transpose()
             },
@@ -1,12 +1,13 @@
 use boolinator::Boolinator;
 use serde::Serialize;
+use std::cmp::Ordering;
 use std::fmt::Debug;
 use thiserror::Error;
 
 use sqlparser::ast::{BinaryOperator, Expr, Ident, UnaryOperator};
 
 use super::context::{BlendContext, FilterContext};
-use super::evaluate::{evaluate, Evaluated};
+use super::evaluate::{evaluate, Clock, Evaluated};
 use super::select::select;
 use crate::data::Row;
 use crate::result::Result;
@@ -18,8 +19,12 @@ pub enum FilterError {
     Unimplemented,
 }
 
+/// The parser does not support the `ESCAPE` clause, so backslash is always the escape character.
+const LIKE_ESCAPE: Option<char> = Some('\\');
+
 pub struct Filter<'a, T: 'static + Debug> {
     storage: &'a dyn Store<T>,
+    clock: &'a dyn Clock,
     where_clause: Option<&'a Expr>,
     context: Option<&'a FilterContext<'a>>,
 }
@@ -27,11 +32,13 @@ pub struct Filter<'a, T: 'static + Debug> {
 impl<'a, T: 'static + Debug> Filter<'a, T> {
     pub fn new(
         storage: &'a dyn Store<T>,
+        clock: &'a dyn Clock,
         where_clause: Option<&'a Expr>,
         context: Option<&'a FilterContext<'a>>,
     ) -> Self {
         Self {
             storage,
+            clock,
             where_clause,
             context,
         }
@@ -41,14 +48,16 @@ impl<'a, T: 'static + Debug> Filter<'a, T> {
         let context = FilterContext::new(table_alias, columns, row, self.context);
 
         match self.where_clause {
-            Some(expr) => check_expr(self.storage, Some(context).as_ref(), expr),
+            Some(expr) => check_expr(self.storage, self.clock, Some(context).as_ref(), expr),
             None => Ok(true),
         }
     }
 
     pub fn check_blended(&self, blend_context: &BlendContext<'_>) -> Result<bool> {
         match self.where_clause {
-            Some(expr) => check_blended_expr(self.storage, self.context, blend_context, expr),
+            Some(expr) => {
+                check_blended_expr(self.storage, self.clock, self.context, blend_context, expr)
+            }
             None => Ok(true),
         }
     }
@@ -69,6 +78,7 @@ impl<'a, T: 'static + Debug> BlendedFilter<'a, T> {
             filter:
                 Filter {
                     storage,
+                    clock,
                     where_clause,
                     context: next,
                 },
@@ -80,61 +90,58 @@ impl<'a, T: 'static + Debug> BlendedFilter<'a, T> {
 
         where_clause.map_or(Ok(true), |expr| match blend_context {
             Some(blend_context) => {
-                check_blended_expr(*storage, filter_context, blend_context, expr)
+                check_blended_expr(*storage, *clock, filter_context, blend_context, expr)
             }
-            None => check_expr(*storage, filter_context, expr),
+            None => check_expr(*storage, *clock, filter_context, expr),
         })
     }
 }
 
 fn check_expr<'a, T: 'static + Debug>(
     storage: &'a dyn Store<T>,
+    clock: &'a dyn Clock,
     filter_context: Option<&'a FilterContext<'a>>,
     expr: &'a Expr,
 ) -> Result<bool> {
-    let evaluate = |expr| evaluate(storage, filter_context, None, expr);
-    let check = |expr| check_expr(storage, filter_context, expr);
+    let evaluate = |expr| evaluate(storage, clock, filter_context, None, expr);
+    let check = |expr| check_expr(storage, clock, filter_context, expr);
+    let check_unknown = |expr| check_unknown(storage, clock, filter_context, expr);
 
     match expr {
         Expr::BinaryOp { op, left, right } => {
             let zip_evaluate = || Ok((evaluate(left)?, evaluate(right)?));
             let zip_check = || Ok((check(left)?, check(right)?));
+            let zip_cmp = || zip_evaluate().and_then(|(l, r)| l.try_partial_cmp(&r));
 
             match op {
-                BinaryOperator::Eq => zip_evaluate().map(|(l, r)| l == r),
-                BinaryOperator::NotEq => zip_evaluate().map(|(l, r)| l != r),
+                BinaryOperator::Eq => zip_evaluate().and_then(|(l, r)| l.try_eq(&r)),
+                BinaryOperator::NotEq => zip_evaluate()
+                    .and_then(|(l, r)| Ok(!l.is_null() && !r.is_null() && !l.try_eq(&r)?)),
                 BinaryOperator::And => zip_check().map(|(l, r)| l && r),
                 BinaryOperator::Or => zip_check().map(|(l, r)| l || r),
-                BinaryOperator::Lt => zip_evaluate().map(|(l, r)| l < r),
-                BinaryOperator::LtEq => zip_evaluate().map(|(l, r)| l <= r),
-                BinaryOperator::Gt => zip_evaluate().map(|(l, r)| l > r),
-                BinaryOperator::GtEq => zip_evaluate().map(|(l, r)| l >= r),
-                _ => Err(FilterError::Unimplemented.into()),
+                BinaryOperator::Lt => zip_cmp().map(|o| o == Some(Ordering::Less)),
+                BinaryOperator::LtEq => {
+                    zip_cmp().map(|o| matches!(o, Some(Ordering::Less | Ordering::Equal)))
+                }
+                BinaryOperator::Gt => zip_cmp().map(|o| o == Some(Ordering::Greater)),
+                BinaryOperator::GtEq => {
+                    zip_cmp().map(|o| matches!(o, Some(Ordering::Greater | Ordering::Equal)))
+                }
+                BinaryOperator::Like | BinaryOperator::NotLike => {
+                    check_unknown(expr)?.try_into_bool()
+                }
+                _ => evaluate(expr)?.try_into_bool(),
             }
         }
-        Expr::UnaryOp { op, expr } => match op {
-            UnaryOperator::Not => check(&expr).map(|v| !v),
-            _ => Err(FilterError::Unimplemented.into()),
-        },
-        Expr::Nested(expr) => check(&expr),
-        Expr::InList {
-            expr,
-            list,
-            negated,
-        } => {
-            let negated = *negated;
-            let target = evaluate(expr)?;
-
-            list.iter()
-                .filter_map(|expr| {
-                    evaluate(expr).map_or_else(
-                        |error| Some(Err(error)),
-                        |evaluated| (target == evaluated).as_some(Ok(!negated)),
-                    )
-                })
-                .next()
-                .unwrap_or(Ok(negated))
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            ..
         }
+        | Expr::Between { .. }
+        | Expr::InList { .. } => check_unknown(expr)?.try_into_bool(),
+        Expr::Nested(expr) => check(expr),
+        Expr::IsNull(expr) => Ok(evaluate(expr)?.is_null()),
+        Expr::IsNotNull(expr) => Ok(!evaluate(expr)?.is_null()),
         Expr::InSubquery {
             expr,
             subquery,
@@ -143,7 +150,7 @@ fn check_expr<'a, T: 'static + Debug>(
             let negated = *negated;
             let target = evaluate(expr)?;
 
-            select(storage, &subquery, filter_context)?
+            select(storage, clock, subquery, filter_context)?
                 .map(|row| row?.take_first_value())
                 .filter_map(|value| {
                     value.map_or_else(
@@ -154,12 +161,102 @@ fn check_expr<'a, T: 'static + Debug>(
                 .next()
                 .unwrap_or(Ok(negated))
         }
-        _ => Err(FilterError::Unimplemented.into()),
+        _ => evaluate(expr)?.try_into_bool(),
+    }
+}
+
+/// Three-valued truth of a condition, where NULL stands for unknown. `check_expr` takes unknown
+/// as `false`, which only holds until it is negated, so `NOT` and whatever it may wrap are
+/// checked here instead.
+fn check_unknown<'a, T: 'static + Debug>(
+    storage: &'a dyn Store<T>,
+    clock: &'a dyn Clock,
+    filter_context: Option<&'a FilterContext<'a>>,
+    expr: &'a Expr,
+) -> Result<Evaluated<'a>> {
+    let evaluate = |expr| evaluate(storage, clock, filter_context, None, expr);
+    let check_unknown = |expr| check_unknown(storage, clock, filter_context, expr);
+    let unknown = || Ok(Evaluated::from_boolean(None));
+    let known = |v: bool| Ok(Evaluated::from_boolean(Some(v)));
+    let truthiness = |expr| {
+        let v = evaluate(expr)?;
+
+        if v.is_null() {
+            unknown()
+        } else {
+            known(v.try_into_bool()?)
+        }
+    };
+
+    match expr {
+        Expr::BinaryOp { op, left, right } => match op {
+            BinaryOperator::And => check_unknown(left)?.and(&check_unknown(right)?),
+            BinaryOperator::Or => check_unknown(left)?.or(&check_unknown(right)?),
+            BinaryOperator::Eq
+            | BinaryOperator::NotEq
+            | BinaryOperator::Lt
+            | BinaryOperator::LtEq
+            | BinaryOperator::Gt
+            | BinaryOperator::GtEq => evaluate(expr),
+            BinaryOperator::Like | BinaryOperator::NotLike => {
+                let (l, r) = (evaluate(left)?, evaluate(right)?);
+
+                if l.is_null() || r.is_null() {
+                    return unknown();
+                }
+
+                known(l.like(&r, true, LIKE_ESCAPE)? != matches!(op, BinaryOperator::NotLike))
+            }
+            _ => truthiness(expr),
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr,
+        } => check_unknown(expr)?.logical_not(),
+        Expr::Nested(expr) => check_unknown(expr),
+        Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => {
+            let target = evaluate(expr)?;
+            let between = target
+                .ge_eval(&evaluate(low)?)?
+                .and(&target.le_eval(&evaluate(high)?)?)?;
+
+            if *negated {
+                between.logical_not()
+            } else {
+                Ok(between)
+            }
+        }
+        Expr::InList {
+            expr,
+            list,
+            negated,
+        } => {
+            let target = evaluate(expr)?;
+            let list = list.iter().map(evaluate).collect::<Result<Vec<_>>>()?;
+
+            if target.is_in(&list)? {
+                known(!negated)
+            } else if target.is_null() || list.iter().any(Evaluated::is_null) {
+                unknown()
+            } else {
+                known(*negated)
+            }
+        }
+        Expr::IsNull(_) | Expr::IsNotNull(_) | Expr::InSubquery { .. } => {
+            check_expr(storage, clock, filter_context, expr).map(known)?
+        }
+        _ => truthiness(expr),
     }
 }
 
 fn check_blended_expr<T: 'static + Debug>(
     storage: &dyn Store<T>,
+    clock: &dyn Clock,
     filter_context: Option<&FilterContext<'_>>,
     blend_context: &BlendContext<'_>,
     expr: &Expr,
@@ -174,11 +271,13 @@ fn check_blended_expr<T: 'static + Debug>(
 
     let row_context = row
         .as_ref()
-        .map(|row| FilterContext::new(table_alias, &columns, row, filter_context));
+        .map(|row| FilterContext::new(table_alias, columns, row, filter_context));
     let filter_context = row_context.as_ref().or(filter_context);
 
     match next {
-        Some(blend_context) => check_blended_expr(storage, filter_context, blend_context, expr),
-        None => check_expr(storage, filter_context, expr),
+        Some(blend_context) => {
+            check_blended_expr(storage, clock, filter_context, blend_context, expr)
+        }
+        None => check_expr(storage, clock, filter_context, expr),
     }
 }
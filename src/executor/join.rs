@@ -10,6 +10,7 @@ use thiserror::Error as ThisError;
 use sqlparser::ast::{Ident, Join as AstJoin, JoinConstraint, JoinOperator};
 
 use super::context::{BlendContext, FilterContext};
+use super::evaluate::Clock;
 use super::filter::{BlendedFilter, Filter};
 use crate::data::Table;
 use crate::result::Result;
@@ -32,6 +33,7 @@ pub enum JoinError {
 
 pub struct Join<'a, T: 'static + Debug> {
     storage: &'a dyn Store<T>,
+    clock: &'a dyn Clock,
     join_clauses: &'a [AstJoin],
     filter_context: Option<&'a FilterContext<'a>>,
 }
@@ -49,11 +51,13 @@ enum Applied<I1, I2, I3, I4> {
 impl<'a, T: 'static + Debug> Join<'a, T> {
     pub fn new(
         storage: &'a dyn Store<T>,
+        clock: &'a dyn Clock,
         join_clauses: &'a [AstJoin],
         filter_context: Option<&'a FilterContext<'a>>,
     ) -> Self {
         Self {
             storage,
+            clock,
             join_clauses,
             filter_context,
         }
@@ -77,11 +81,19 @@ impl<'a, T: 'static + Debug> Join<'a, T> {
             })
             .fold(init_rows, |rows, (join_clause, join_columns)| {
                 let storage = self.storage;
+                let clock = self.clock;
                 let filter_context = self.filter_context;
                 let map = move |blend_context| {
                     let columns = Rc::clone(&join_columns);
 
-                    join(storage, filter_context, join_clause, columns, blend_context)
+                    join(
+                        storage,
+                        clock,
+                        filter_context,
+                        join_clause,
+                        columns,
+                        blend_context,
+                    )
                 };
 
                 match rows {
@@ -107,6 +119,7 @@ enum Joined<I1, I2, I3> {
 
 fn join<'a, T: 'static + Debug>(
     storage: &'a dyn Store<T>,
+    clock: &'a dyn Clock,
     filter_context: Option<&'a FilterContext<'a>>,
     ast_join: &'a AstJoin,
     columns: Rc<Vec<Ident>>,
@@ -161,7 +174,7 @@ fn join<'a, T: 'static + Debug>(
                 }
             };
 
-            let filter = Filter::new(storage, where_clause, filter_context);
+            let filter = Filter::new(storage, clock, where_clause, filter_context);
             let blended_filter = BlendedFilter::new(&filter, Some(&blend_context));
 
             blended_filter
@@ -28,7 +28,7 @@ impl Limit {
             }
         };
 
-        let limit = limit.map(|value| parse(value)).transpose()?;
+        let limit = limit.map(&parse).transpose()?;
         let offset = offset
             .map(|Offset { value, .. }| parse(value))
             .transpose()?;
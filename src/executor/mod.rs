@@ -13,8 +13,11 @@ mod update;
 pub use aggregate::AggregateError;
 pub use blend::BlendError;
 pub use context::{BlendContextError, FilterContextError};
-pub use evaluate::EvaluateError;
-pub use execute::{execute, ExecuteError, Payload};
+pub use evaluate::{
+    Checked, Clock, Collation, ComparisonMode, DivisionMode, EvaluateError, Evaluated,
+    NormalizationForm, OverflowMode, RoundingMode, SystemClock,
+};
+pub use execute::{execute, execute_with_clock, ExecuteError, Payload};
 pub use filter::FilterError;
 pub use join::JoinError;
 pub use limit::LimitError;
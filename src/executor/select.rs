@@ -9,6 +9,7 @@ use sqlparser::ast::{Ident, Query, SetExpr, TableWithJoins};
 use super::aggregate::Aggregate;
 use super::blend::Blend;
 use super::context::{BlendContext, FilterContext};
+use super::evaluate::Clock;
 use super::fetch::fetch_columns;
 use super::filter::Filter;
 use super::join::Join;
@@ -55,6 +56,7 @@ fn fetch_blended<'a, T: 'static + Debug>(
 
 pub fn select<'a, T: 'static + Debug>(
     storage: &'a dyn Store<T>,
+    clock: &'a dyn Clock,
     query: &'a Query,
     filter_context: Option<&'a FilterContext<'a>>,
 ) -> Result<impl Iterator<Item = Result<Row>> + 'a> {
@@ -92,10 +94,10 @@ pub fn select<'a, T: 'static + Debug>(
         .collect::<Result<_>>()?;
     let join_columns = Rc::new(join_columns);
 
-    let join = Join::new(storage, joins, filter_context);
+    let join = Join::new(storage, clock, joins, filter_context);
     let aggregate = Aggregate::new(projection);
-    let blend = Blend::new(storage, projection);
-    let filter = Filter::new(storage, where_clause, filter_context);
+    let blend = Blend::new(storage, clock, projection);
+    let filter = Filter::new(storage, clock, where_clause, filter_context);
     let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref())?;
 
     let rows = fetch_blended(storage, table, columns)?
@@ -2,10 +2,10 @@ use serde::Serialize;
 use std::fmt::Debug;
 use thiserror::Error;
 
-use sqlparser::ast::{Assignment, Ident};
+use sqlparser::ast::{Assignment, ColumnDef, Ident};
 
 use super::context::FilterContext;
-use super::evaluate::{evaluate, Evaluated};
+use super::evaluate::{evaluate, Clock, Evaluated};
 use crate::data::{Row, Value};
 use crate::result::Result;
 use crate::store::Store;
@@ -24,17 +24,21 @@ pub enum UpdateError {
 
 pub struct Update<'a, T: 'static + Debug> {
     storage: &'a dyn Store<T>,
+    clock: &'a dyn Clock,
     table_name: &'a str,
     fields: &'a [Assignment],
     columns: &'a [Ident],
+    column_defs: &'a [ColumnDef],
 }
 
 impl<'a, T: 'static + Debug> Update<'a, T> {
     pub fn new(
         storage: &'a dyn Store<T>,
+        clock: &'a dyn Clock,
         table_name: &'a str,
         fields: &'a [Assignment],
         columns: &'a [Ident],
+        column_defs: &'a [ColumnDef],
     ) -> Result<Self> {
         for assignment in fields.iter() {
             let Assignment { id, .. } = assignment;
@@ -46,9 +50,11 @@ impl<'a, T: 'static + Debug> Update<'a, T> {
 
         Ok(Self {
             storage,
+            clock,
             table_name,
             fields,
             columns,
+            column_defs,
         })
     }
 
@@ -66,18 +72,23 @@ impl<'a, T: 'static + Debug> Update<'a, T> {
                     .columns
                     .iter()
                     .position(|column| column.value == id.value)
-                    .ok_or_else(|| UpdateError::Unreachable)?;
+                    .ok_or(UpdateError::Unreachable)?;
 
-                let evaluated = evaluate(self.storage, context, None, value)?;
+                let evaluated = evaluate(self.storage, self.clock, context, None, value)?;
                 let Row(values) = &row;
                 let value = &values[index];
 
+                let fit = |evaluated: Evaluated<'_>| match self.column_defs.get(index) {
+                    Some(ColumnDef { data_type, .. }) => evaluated.fit_to(data_type)?.into_value(),
+                    None => evaluated.into_value(),
+                };
+
                 match evaluated {
                     Evaluated::LiteralRef(v) => value.clone_by(v),
                     Evaluated::Literal(v) => value.clone_by(&v),
                     Evaluated::StringRef(v) => Ok(Value::Str(v.to_string())),
-                    Evaluated::ValueRef(v) => Ok(v.clone()),
-                    Evaluated::Value(v) => Ok(v),
+                    Evaluated::ValueRef(v) => fit(Evaluated::ValueRef(v)),
+                    Evaluated::Value(v) => fit(Evaluated::Value(v)),
                 }
             })
     }
@@ -90,10 +101,7 @@ impl<'a, T: 'static + Debug> Update<'a, T> {
             .into_iter()
             .enumerate()
             .map(|(i, value)| {
-                let column = &self
-                    .columns
-                    .get(i)
-                    .ok_or_else(|| UpdateError::ConflictOnSchema)?;
+                let column = &self.columns.get(i).ok_or(UpdateError::ConflictOnSchema)?;
 
                 self.find(&row, column).unwrap_or(Ok(value))
             })
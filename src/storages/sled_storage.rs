@@ -15,11 +15,11 @@ enum StorageError {
     Bincode(#[from] bincode::Error),
 }
 
-impl Into<Error> for StorageError {
-    fn into(self) -> Error {
+impl From<StorageError> for Error {
+    fn from(error: StorageError) -> Self {
         use StorageError::*;
 
-        match self {
+        match error {
             Sled(e) => Error::Storage(Box::new(e)),
             Bincode(e) => Error::Storage(e),
             Store(e) => e.into(),
@@ -128,7 +128,7 @@ impl Store<IVec> for SledStorage {
     fn fetch_schema(&self, table_name: &str) -> Result<Schema> {
         let key = format!("schema/{}", table_name);
         let key = key.as_bytes();
-        let value = try_into!(self.tree.get(&key));
+        let value = try_into!(self.tree.get(key));
         let value = value.ok_or(StoreError::SchemaNotFound)?;
         let statement = try_into!(bincode::deserialize(&value));
 
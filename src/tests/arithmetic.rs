@@ -48,6 +48,25 @@ pub fn arithmetic(mut tester: impl tests::Tester) {
         (5, "SELECT * FROM Arith WHERE id > id / 2;"),
         (3, "SELECT * FROM Arith WHERE id > num / id;"),
         (2, "SELECT * FROM Arith WHERE 10 / id = 2;"),
+        // modulo on WHERE
+        (2, "SELECT * FROM Arith WHERE id % 2 = 0;"),
+        (1, "SELECT * FROM Arith WHERE num % id = 2;"),
+        (2, "SELECT * FROM Arith WHERE -num % 4 = 0;"),
+        (2, "SELECT * FROM Arith WHERE -num % 4 = -2;"),
+        // mixed integer and float on WHERE
+        (1, "SELECT * FROM Arith WHERE id > 4.5;"),
+        (2, "SELECT * FROM Arith WHERE id * 1.5 < 3.5;"),
+        (1, "SELECT * FROM Arith WHERE id = 1.0;"),
+        // unary minus on WHERE
+        (1, "SELECT * FROM Arith WHERE -id = -1;"),
+        (2, "SELECT * FROM Arith WHERE -num > -4;"),
+        (1, "SELECT * FROM Arith WHERE id = -(-2);"),
+        (5, "SELECT * FROM Arith WHERE id > -1;"),
+        (5, "SELECT * FROM Arith WHERE -5 < -3;"),
+        (5, "SELECT * FROM Arith WHERE -5 = -5;"),
+        (0, "SELECT * FROM Arith WHERE -3 < -5;"),
+        (4, "SELECT * FROM Arith WHERE -id < -1;"),
+        (1, "SELECT * FROM Arith WHERE +id = 1;"),
         // etc
         (1, "SELECT * FROM Arith WHERE 1 + 1 = id;"),
         (5, "UPDATE Arith SET id = id + 1;"),
@@ -57,33 +76,80 @@ pub fn arithmetic(mut tester: impl tests::Tester) {
         (5, "UPDATE Arith SET id = id * 2;"),
         (5, "UPDATE Arith SET id = id / 2;"),
         (2, "SELECT * FROM Arith WHERE id <= 2;"),
+        (5, "UPDATE Arith SET num = num * 1.0;"),
+        (1, "SELECT * FROM Arith WHERE num = 6;"),
     ];
 
     for (num, sql) in test_cases.iter() {
         tester.test_rows(sql, *num);
     }
 
+    let non_numeric = |left: &str, right: &str| -> Error {
+        EvaluateError::ArithmeticOnNonNumeric {
+            left: left.to_owned(),
+            right: right.to_owned(),
+        }
+        .into()
+    };
+
     let test_cases = vec![
         (
-            ValueError::AddOnNonNumeric.into(),
+            non_numeric("'A'", "1"),
             "SELECT * FROM Arith WHERE name + id < 1",
         ),
         (
-            ValueError::SubtractOnNonNumeric.into(),
+            non_numeric("'A'", "1"),
             "SELECT * FROM Arith WHERE name - id < 1",
         ),
         (
-            ValueError::MultiplyOnNonNumeric.into(),
+            non_numeric("'A'", "1"),
             "SELECT * FROM Arith WHERE name * id < 1",
         ),
         (
-            ValueError::DivideOnNonNumeric.into(),
+            non_numeric("'A'", "1"),
             "SELECT * FROM Arith WHERE name / id < 1",
         ),
+        (non_numeric("'abc'", "1"), "SELECT 'abc' + 1 FROM Arith"),
+        (
+            ValueError::UnaryMinusOnNonNumeric.into(),
+            "SELECT * FROM Arith WHERE -name = 1",
+        ),
+        (
+            EvaluateError::UnaryPlusOnNonNumeric.into(),
+            "SELECT * FROM Arith WHERE +name = 1",
+        ),
+        (
+            EvaluateError::TypeMismatch.into(),
+            "SELECT * FROM Arith WHERE name = 1",
+        ),
+        (
+            EvaluateError::TypeMismatch.into(),
+            "SELECT * FROM Arith WHERE id > 'A'",
+        ),
+        (
+            ValueError::DivideByZero.into(),
+            "SELECT * FROM Arith WHERE id = 1 / 0",
+        ),
+        (
+            ValueError::DivideByZero.into(),
+            "SELECT * FROM Arith WHERE id / 0 = 1",
+        ),
+        (
+            ValueError::DivideByZero.into(),
+            "SELECT * FROM Arith WHERE id % 0 = 1",
+        ),
         (
             UpdateError::ColumnNotFound("aaa".to_owned()).into(),
             "UPDATE Arith SET aaa = 1",
         ),
+        (
+            EvaluateError::LossyConversion {
+                value: "6.5".to_owned(),
+                target: "INT".to_owned(),
+            }
+            .into(),
+            "UPDATE Arith SET num = num + 0.5",
+        ),
     ];
 
     test_cases
@@ -116,23 +182,49 @@ pub fn blend(mut tester: impl tests::Tester) {
         tester.run(insert_sql).unwrap();
     }
 
-    use Value::I64;
+    use Value::{Str, F64, I64};
 
     let sql = "SELECT 1 * 2 + 1 - 3 / 1 FROM Arith LIMIT 1;";
     let found = tester.run(sql).expect("select");
     let expected = select!(I64; 0);
     assert_eq!(expected, found);
 
+    let sql = "SELECT 1.5 * 2, 7 / 2, 7.0 / 2, 2.5 + 2.5 FROM Arith LIMIT 1;";
+    let found = tester.run(sql).expect("select");
+    let expected = select!(F64 I64 F64 F64; 3.0 3 3.5 5.0);
+    assert_eq!(expected, found);
+
+    let sql =
+        "SELECT CAST(7.9 AS INTEGER), CAST('2.5' AS FLOAT), CAST(id AS TEXT) FROM Arith LIMIT 1;";
+    let found = tester.run(sql).expect("select");
+    let expected = select!(I64 F64 Str; 7 2.5 "1".to_owned());
+    assert_eq!(expected, found);
+
+    let sql = "SELECT 6 & 4, 6 | 1, id & 1 FROM Arith LIMIT 1;";
+    let found = tester.run(sql).expect("select");
+    let expected = select!(I64 I64 I64; 4 7 1);
+    assert_eq!(expected, found);
+
+    let sql = "SELECT 2 ^ 10, 2 ^ -1, id ^ 2 FROM Arith LIMIT 1;";
+    let found = tester.run(sql).expect("select");
+    let expected = select!(I64 F64 I64; 1024 0.5 1);
+    assert_eq!(expected, found);
+
+    let sql = "SELECT 3 > 2, id = 1, num <= id, id <> 1 FROM Arith LIMIT 1;";
+    let found = tester.run(sql).expect("select");
+    let expected = select!(Value::Bool Value::Bool Value::Bool Value::Bool; true true false false);
+    assert_eq!(expected, found);
+
     let found = tester
-        .run("SELECT id, id + 1, id + num, 1 + 1 FROM Arith")
+        .run("SELECT -id, id, id + 1, id + num, 1 + 1 FROM Arith")
         .expect("select");
     let expected = select!(
-        I64 I64 I64 I64;
-        1   2   7   2;
-        2   3   10  2;
-        3   4   7   2;
-        4   5   6   2;
-        5   6   8   2
+        I64  I64 I64 I64 I64;
+        (-1) 1   2   7   2;
+        (-2) 2   3   10  2;
+        (-3) 3   4   7   2;
+        (-4) 4   5   6   2;
+        (-5) 5   6   8   2
     );
     assert_eq!(expected, found);
 
@@ -52,6 +52,15 @@ pub fn blend(mut tester: impl tests::Tester) {
                 3   "Jorno".to_owned()
             ),
         ),
+        (
+            "SELECT name || '-' || id FROM BlendUser",
+            select!(
+                Str;
+                "Taehoon-1".to_owned();
+                "Mike-2".to_owned();
+                "Jorno-3".to_owned()
+            ),
+        ),
         (
             "SELECT player_id, quantity FROM BlendItem",
             select!(I64 I64; 1 1; 2 4; 2 9; 3 2; 3 1),
@@ -26,10 +26,90 @@ CREATE TABLE Test (
     );
     assert_eq!(expected, found);
 
+    let test_cases = [
+        (
+            0,
+            "SELECT id, num FROM Test WHERE id = NULL AND name = \'Hello\'",
+        ),
+        (0, "SELECT id, num FROM Test WHERE id != NULL"),
+        (0, "SELECT id, num FROM Test WHERE id > NULL"),
+        (0, "SELECT id, num FROM Test WHERE NULL = NULL"),
+        (1, "SELECT id, num FROM Test WHERE id != 1"),
+        (1, "SELECT id, num FROM Test WHERE id < 2"),
+        (2, "SELECT id, num FROM Test WHERE id IN (1, 3, NULL)"),
+        (1, "SELECT id, num FROM Test WHERE id NOT IN (3)"),
+        (0, "SELECT id, num FROM Test WHERE id NOT IN (3, NULL)"),
+        (1, "SELECT id, num FROM Test WHERE id IS NULL"),
+        (2, "SELECT id, num FROM Test WHERE id IS NOT NULL"),
+        (0, "SELECT id, num FROM Test WHERE name IS NULL"),
+        (2, "SELECT id, num FROM Test WHERE num BETWEEN 2 AND 4"),
+        (1, "SELECT id, num FROM Test WHERE id BETWEEN 1 AND 2"),
+        (1, "SELECT id, num FROM Test WHERE id NOT BETWEEN 1 AND 2"),
+        (0, "SELECT id, num FROM Test WHERE num BETWEEN 4 AND 2"),
+        (0, "SELECT id, num FROM Test WHERE NOT id"),
+        (1, "SELECT id, num FROM Test WHERE NOT (id > 1)"),
+        (1, "SELECT id, num FROM Test WHERE NOT (id BETWEEN 1 AND 2)"),
+        (1, "SELECT id, num FROM Test WHERE NOT (id IN (3))"),
+        (
+            1,
+            "SELECT id, num FROM Test WHERE NOT (id = 1 OR id IS NULL)",
+        ),
+        (0, "SELECT id, num FROM Test WHERE id + 1 = 1"),
+        (0, "SELECT id, num FROM Test WHERE id - 1 = -1"),
+        (0, "SELECT id, num FROM Test WHERE id * 2 = 0"),
+        (1, "SELECT id, num FROM Test WHERE id / 2 = 0"),
+        (0, "SELECT id, num FROM Test WHERE id % 2 = 0"),
+        (1, "SELECT id, num FROM Test WHERE id + 1 IS NULL"),
+        (1, "SELECT id, num FROM Test WHERE id - 1 IS NULL"),
+        (1, "SELECT id, num FROM Test WHERE id * 2 IS NULL"),
+        (1, "SELECT id, num FROM Test WHERE id / 2 IS NULL"),
+        (3, "SELECT id, num FROM Test WHERE num + NULL IS NULL"),
+        (3, "SELECT id, num FROM Test WHERE 1 + NULL IS NULL"),
+    ];
+
+    for (num, sql) in test_cases.iter() {
+        tester.test_rows(sql, *num);
+    }
+
+    tester.run_and_print("CREATE TABLE Flag (id INTEGER, active BOOLEAN NULL)");
+    tester.run_and_print("INSERT INTO Flag VALUES (1, TRUE)");
+    tester.run_and_print("INSERT INTO Flag VALUES (2, FALSE)");
+    tester.run_and_print("INSERT INTO Flag VALUES (3, NULL)");
+
+    let found = tester
+        .run("SELECT id FROM Flag WHERE NOT active")
+        .expect("select");
+    assert_eq!(select!(I64; 2), found);
+
+    let found = tester
+        .run("SELECT id FROM Flag WHERE NOT (active AND id > 0)")
+        .expect("select");
+    assert_eq!(select!(I64; 2), found);
+
+    let found = tester
+        .run("SELECT id FROM Flag WHERE NOT NOT active")
+        .expect("select");
+    assert_eq!(select!(I64; 1), found);
+
+    tester.run_and_print("UPDATE Test SET id = id * 1.0");
+
+    let found = tester.run("SELECT id FROM Test").expect("select");
+    let expected = select!(OptI64; None; Some(1); Some(3));
+    assert_eq!(expected, found);
+
+    tester.run_and_print("UPDATE Test SET id = id + 1");
+
+    let found = tester.run("SELECT id FROM Test").expect("select");
+    let expected = select!(OptI64; None; Some(2); Some(4));
+    assert_eq!(expected, found);
+
     let found = tester
-        .run("SELECT id, num FROM Test WHERE id = NULL AND name = \'Hello\'")
+        .run("SELECT id + num, id - 1, num * id, id / num FROM Test WHERE id IS NULL")
         .expect("select");
-    let expected = select!(OptI64 I64; None 2);
+    let expected = select!(
+        OptI64 OptI64 OptI64 OptI64;
+        None   None   None   None
+    );
     assert_eq!(expected, found);
 
     tester.run_and_print("UPDATE Test SET id = 2");
@@ -28,8 +28,32 @@ pub fn sql_types(mut tester: impl tests::Tester) {
     let test_sqls = [
         (2, "SELECT * FROM Item;"),
         (1, "SELECT * FROM Item WHERE verified = True;"),
+        (1, "SELECT * FROM Item WHERE verified;"),
+        (1, "SELECT * FROM Item WHERE NOT verified;"),
+        (2, "SELECT * FROM Item WHERE True;"),
+        (2, "SELECT * FROM Item WHERE id;"),
+        (0, "SELECT * FROM Item WHERE NULL;"),
+        (2, "SELECT * FROM Item WHERE id % 2;"),
+        (0, "SELECT * FROM Item WHERE id - 1;"),
+        (1, "SELECT * FROM Item WHERE ratio * 10 - 1;"),
+        (1, "SELECT * FROM Item WHERE verified > False;"),
+        (2, "SELECT * FROM Item WHERE False < True;"),
         (1, "SELECT * FROM Item WHERE ratio > 0.5;"),
         (1, "SELECT * FROM Item WHERE ratio = 0.1;"),
+        (0, "SELECT * FROM Item WHERE ratio = 1;"),
+        (1, "SELECT * FROM Item WHERE ratio * 10 = 9;"),
+        (2, "SELECT * FROM Item WHERE 0.5 < 1;"),
+        (1, "SELECT * FROM Item WHERE content LIKE 'H%';"),
+        (
+            2,
+            "SELECT * FROM Item WHERE content LIKE '_o%' OR content LIKE '%o';",
+        ),
+        (1, "SELECT * FROM Item WHERE content NOT LIKE '%l_o';"),
+        (0, "SELECT * FROM Item WHERE content LIKE 'hello';"),
+        (0, "SELECT * FROM Item WHERE 1.5 < 1;"),
+        (1, "SELECT * FROM Item WHERE content = X'48656C6C6F';"),
+        (1, "SELECT * FROM Item WHERE content = N'World';"),
+        (1, "SELECT * FROM Item WHERE content > X'49';"),
         (
             1,
             "UPDATE Item SET content=\"Foo\" WHERE content=\"World\";",
@@ -44,5 +68,149 @@ pub fn sql_types(mut tester: impl tests::Tester) {
         tester.test_rows(sql, *num);
     }
 
+    tester.test_error(
+        "SELECT * FROM Item WHERE content LIKE 'Hello\\';",
+        EvaluateError::LikePatternEndsWithEscape.into(),
+    );
+
+    let found = tester
+        .run("SELECT NOT verified, NOT NOT verified FROM Item WHERE content = 'Hello';")
+        .expect("select");
+    let expected = select!(
+        Value::Bool Value::Bool;
+        false       true
+    );
+    assert_eq!(expected, found);
+
+    tester.test_error(
+        "SELECT NOT id FROM Item WHERE content = 'Hello';",
+        EvaluateError::BooleanTypeRequired("1".to_owned()).into(),
+    );
+
+    tester.test_error(
+        "SELECT NOT content FROM Item;",
+        EvaluateError::BooleanTypeRequired("'Hello'".to_owned()).into(),
+    );
+
     tester.run_and_print(delete_sql);
+
+    tester.run_and_print("CREATE TABLE Money (id INTEGER, amount DECIMAL);");
+    tester.run_and_print("INSERT INTO Money (id, amount) VALUES (1, 0.1);");
+    tester.run_and_print("INSERT INTO Money (id, amount) VALUES (2, 0.2);");
+
+    use rust_decimal::Decimal;
+    use Value::I64;
+
+    let found = tester
+        .run("SELECT id, amount + 0.2, amount * 3 FROM Money WHERE amount = 0.1;")
+        .expect("select");
+    let decimal = |v: &str| v.parse::<Decimal>().unwrap();
+    let expected = select!(
+        I64 Value::Decimal Value::Decimal;
+        1   decimal("0.3") decimal("0.3")
+    );
+    assert_eq!(expected, found);
+
+    tester.test_rows("SELECT * FROM Money WHERE amount + 0.2 = 0.3;", 1);
+    tester.test_rows("SELECT * FROM Money WHERE amount > 0.15;", 1);
+
+    tester.run_and_print("CREATE TABLE Event (id INTEGER, day DATE, created_at TIMESTAMP);");
+    tester.run_and_print(
+        "INSERT INTO Event (id, day, created_at) VALUES (1, '2020-12-31', '2020-12-31 23:59:59');",
+    );
+    tester.run_and_print(
+        "INSERT INTO Event (id, day, created_at) VALUES (2, '2021-01-01', '2021-01-01T08:00:00');",
+    );
+
+    let test_sqls = [
+        (1, "SELECT * FROM Event WHERE created_at > '2021-01-01';"),
+        (2, "SELECT * FROM Event WHERE created_at < '2021-01-02';"),
+        (1, "SELECT * FROM Event WHERE day = '2021-01-01';"),
+        (
+            1,
+            "SELECT * FROM Event WHERE day < created_at AND day > '2020-12-31';",
+        ),
+    ];
+
+    for (num, sql) in test_sqls.iter() {
+        tester.test_rows(sql, *num);
+    }
+
+    use chrono::NaiveDate;
+
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let found = tester
+        .run(
+            "SELECT day + 7, day - INTERVAL '1' MONTH, day - '2020-12-25' FROM Event WHERE id = 2;",
+        )
+        .expect("select");
+    let expected = select!(
+        Value::Date      Value::Date       I64;
+        date(2021, 1, 8) date(2020, 12, 1) 7
+    );
+    assert_eq!(expected, found);
+
+    let found = tester
+        .run("SELECT EXTRACT(YEAR FROM day), EXTRACT(HOUR FROM created_at) FROM Event;")
+        .expect("select");
+    let expected = select!(
+        I64  I64;
+        2020 23;
+        2021 8
+    );
+    assert_eq!(expected, found);
+
+    let test_sqls = [
+        (1, "SELECT * FROM Event WHERE day + 1 = '2021-01-01';"),
+        (
+            1,
+            "SELECT * FROM Event WHERE created_at - INTERVAL '8 hours' < '2021-01-01';",
+        ),
+        (
+            2,
+            "SELECT * FROM Event WHERE day + INTERVAL '1 day' > created_at;",
+        ),
+    ];
+
+    for (num, sql) in test_sqls.iter() {
+        tester.test_rows(sql, *num);
+    }
+
+    let test_sqls = [
+        (2, "SELECT * FROM Event WHERE created_at < NOW();"),
+        (2, "SELECT * FROM Event WHERE day < CURRENT_TIMESTAMP;"),
+        (1, "UPDATE Event SET created_at = NOW() WHERE id = 1;"),
+        (2, "SELECT * FROM Event WHERE created_at > '2021-01-01';"),
+    ];
+
+    for (num, sql) in test_sqls.iter() {
+        tester.test_rows(sql, *num);
+    }
+
+    tester.test_error(
+        "SELECT * FROM Event WHERE day > 'tomorrow';",
+        EvaluateError::InvalidDateLiteral("tomorrow".to_owned()).into(),
+    );
+
+    tester.run_and_print("CREATE TABLE Blob (id INTEGER, digest BYTEA);");
+    tester.run_and_print("INSERT INTO Blob (id, digest) VALUES (1, X'deadbeef');");
+    tester.run_and_print("INSERT INTO Blob (id, digest) VALUES (2, X'dead');");
+
+    let test_sqls = [
+        (1, "SELECT * FROM Blob WHERE digest = X'DEADBEEF';"),
+        (1, "SELECT * FROM Blob WHERE digest < X'deadbeef';"),
+        (2, "SELECT * FROM Blob WHERE digest >= X'dead';"),
+        (0, "SELECT * FROM Blob WHERE digest > X'deae';"),
+        (1, "UPDATE Blob SET digest = X'00' WHERE id = 2;"),
+        (1, "SELECT * FROM Blob WHERE digest = X'00';"),
+    ];
+
+    for (num, sql) in test_sqls.iter() {
+        tester.test_rows(sql, *num);
+    }
+
+    tester.test_error(
+        "SELECT * FROM Blob WHERE digest = X'dea';",
+        EvaluateError::InvalidHexLiteral("dea".to_owned()).into(),
+    );
 }
@@ -0,0 +1,62 @@
+#[cfg(feature = "sled-storage")]
+use std::convert::TryFrom;
+
+#[cfg(feature = "sled-storage")]
+use chrono::{NaiveDate, NaiveDateTime};
+
+#[cfg(feature = "sled-storage")]
+use gluesql::{execute_with_clock, parse, sled, Payload, Row, SledStorage, Value};
+
+#[cfg(feature = "sled-storage")]
+fn run(storage: &mut Option<SledStorage>, clock: &dyn Fn() -> NaiveDateTime, sql: &str) -> Payload {
+    let query = &parse(sql).unwrap()[0];
+
+    match execute_with_clock(storage.take().unwrap(), query, &clock) {
+        Ok((next, payload)) => {
+            *storage = Some(next);
+
+            payload
+        }
+        Err((_, error)) => panic!("{}: {:?}", sql, error),
+    }
+}
+
+#[cfg(feature = "sled-storage")]
+#[test]
+fn fixed_clock() {
+    let config = sled::Config::default()
+        .path("data/fixed_clock")
+        .temporary(true);
+    let mut storage = Some(SledStorage::try_from(config).expect("SledStorage::new"));
+
+    let fixed = NaiveDate::from_ymd_opt(2020, 10, 14)
+        .and_then(|date| date.and_hms_opt(9, 30, 0))
+        .unwrap();
+    let clock = move || fixed;
+    let mut run = |sql| run(&mut storage, &clock, sql);
+
+    run("CREATE TABLE Item (id INTEGER);");
+    run("INSERT INTO Item VALUES (1);");
+    run("CREATE TABLE Stamp (id INTEGER, current_timestamp TEXT);");
+    run("INSERT INTO Stamp VALUES (1, \"column\");");
+
+    assert_eq!(
+        run("SELECT NOW(), CURRENT_TIMESTAMP() FROM Item;"),
+        Payload::Select(vec![Row(vec![
+            Value::Timestamp(fixed),
+            Value::Timestamp(fixed)
+        ])])
+    );
+    assert_eq!(
+        run("SELECT id FROM Item WHERE CURRENT_TIMESTAMP = NOW();"),
+        Payload::Select(vec![Row(vec![Value::I64(1)])])
+    );
+    assert_eq!(
+        run("SELECT current_timestamp FROM Stamp WHERE current_timestamp = \"column\";"),
+        Payload::Select(vec![Row(vec![Value::Str("column".to_owned())])])
+    );
+    assert_eq!(
+        run("UPDATE Stamp SET current_timestamp = \"updated\" WHERE current_timestamp = \"column\";"),
+        Payload::Update(1)
+    );
+}